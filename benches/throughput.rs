@@ -0,0 +1,48 @@
+//! Passwords per second for bulk generation.
+//!
+//! Run with `cargo bench`. Each case generates a fixed number of passwords
+//! from a seeded RNG, so results are comparable between runs.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use funny_password_generator::formatting::{format_password_with_rng, FormatOptions};
+use funny_password_generator::generator::{generate_password_with_rng, WordConfig};
+use funny_password_generator::rating::ContentRating;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+const PASSWORDS: usize = 100_000;
+
+fn bench(name: &str, mut generate: impl FnMut(&mut StdRng)) {
+    let mut rng = StdRng::seed_from_u64(10);
+    // Warm up lazily built word lists and caches
+    for _ in 0..1_000 {
+        generate(&mut rng);
+    }
+
+    let start = Instant::now();
+    for _ in 0..PASSWORDS {
+        generate(&mut rng);
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    println!("{:<28} {:>10.0} passwords/s", name, PASSWORDS as f64 / elapsed);
+}
+
+fn main() {
+    let options = FormatOptions::default();
+
+    for &rating in ContentRating::ALL {
+        let label = rating.name();
+        let config = WordConfig { rating, ..WordConfig::default() };
+
+        bench(&format!("generate ({})", label), |rng| {
+            black_box(generate_password_with_rng(rng, 5, &config).unwrap());
+        });
+
+        bench(&format!("generate+format ({})", label), |rng| {
+            let password = generate_password_with_rng(rng, 5, &config).unwrap();
+            black_box(format_password_with_rng(password.words, &options, rng));
+        });
+    }
+}
@@ -0,0 +1,142 @@
+//! Choosing "a" or "an" by how the next word sounds, not how it's spelled.
+//!
+//! "An" goes before a vowel sound: "an hourglass", "an NFT", "an x-ray".
+//! "A" goes before a consonant sound, including the "you" of "unicorn" and
+//! "euphoric" and the "w" of "one-eyed". Leading quotes are skipped, so an
+//! air-quoted adjective gets the article its word needs, and only the first
+//! part of a hyphenated word counts.
+
+/// Word beginnings whose "h" is silent.
+const SILENT_H: &[&str] = &["hour", "honest", "honor", "honour", "heir"];
+
+/// Word beginnings where a "u" sounds like "you".
+const YOU_SOUND: &[&str] = &[
+    "unanim", "unicorn", "unicycl", "unif", "union", "uniq", "unis", "unit", "univ", "uku", "ubiq", "uran",
+    "urin", "usa", "use", "usu", "uten", "uter", "util", "utop", "uvu",
+];
+
+/// Letters whose names start with a vowel sound ("an F", "an X").
+const VOWEL_LETTER_NAMES: &str = "aefhilmnorsx";
+
+/// The indefinite article for `word`: "a" or "an".
+pub fn indefinite(word: &str) -> &'static str {
+    if starts_with_vowel_sound(word) {
+        "an"
+    } else {
+        "a"
+    }
+}
+
+/// Whether `word` starts with a vowel sound.
+pub fn starts_with_vowel_sound(word: &str) -> bool {
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+    let first = word.split(['-', ' ']).next().unwrap_or_default();
+    let Some(initial) = first.chars().next() else { return false };
+
+    // Read as a number ("an 8", "an 11", "a 1987")
+    if initial.is_ascii_digit() {
+        let digits = first.chars().take_while(|c| c.is_ascii_digit()).count();
+        return initial == '8' || (digits % 3 == 2 && (first.starts_with("11") || first.starts_with("18")));
+    }
+    // Read letter by letter ("an NFT", "an x-ray", "a UFO")
+    if is_spelled_out(first) {
+        return VOWEL_LETTER_NAMES.contains(initial.to_ascii_lowercase());
+    }
+
+    let lower = first.to_lowercase();
+    if SILENT_H.iter().any(|prefix| lower.starts_with(prefix)) {
+        return true;
+    }
+    if lower.starts_with("eu") || lower.starts_with("ewe") || YOU_SOUND.iter().any(|prefix| lower.starts_with(prefix)) {
+        return false;
+    }
+    if lower == "one" || lower.starts_with("once") {
+        return false;
+    }
+    matches!(initial.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+}
+
+/// Whether a word is said letter by letter: a single letter, an all-caps
+/// acronym, or a lowercase one with no vowels ("nft").
+fn is_spelled_out(word: &str) -> bool {
+    let letters = word.chars().count();
+    letters == 1
+        || (letters > 1 && word.chars().all(|c| c.is_ascii_uppercase()))
+        || !word.chars().any(|c| "aeiouyAEIOUY".contains(c))
+}
+
+/// Fix "a" to "an" (and "an" back to "a") to suit the word that follows.
+pub fn fix_articles<S: AsRef<str>>(words: &[S]) -> Vec<String> {
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| match (word.as_ref(), words.get(i + 1)) {
+            ("a" | "an", Some(next)) => indefinite(next.as_ref()).to_string(),
+            (word, _) => word.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::themes::Theme;
+    use crate::wordlists::LISTS;
+    use crate::wordset::ListKind;
+
+    /// Entries of the word lists and theme packs whose article differs from
+    /// the one their first letter suggests.
+    const EXCEPTIONS: &[(&str, &str)] = &[
+        ("euphoria", "a"),
+        ("euphoric", "a"),
+        ("honor the", "an"),
+        ("hourglasses", "an"),
+        ("hr representative", "an"),
+        ("nft-dealer", "an"),
+        ("one-night-stand", "a"),
+        ("ukuleles", "a"),
+        ("unicorn", "a"),
+        ("utilitarianism", "a"),
+    ];
+
+    #[test]
+    fn test_sounds_rather_than_letters() {
+        for (word, expected) in [
+            ("unicorn", "a"),
+            ("unimpressed", "an"),
+            ("hour", "an"),
+            ("hippo", "a"),
+            ("one-eyed", "a"),
+            ("onion", "an"),
+            ("euphoric", "a"),
+            ("NFT", "an"),
+            ("UFO", "a"),
+            ("x-ray", "an"),
+            ("xylophone", "a"),
+            ("\"honest\"", "an"),
+            ("8", "an"),
+            ("18", "an"),
+            ("1987", "a"),
+            ("walrus", "a"),
+            ("otter", "an"),
+        ] {
+            assert_eq!(indefinite(word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_fixes_articles_both_ways() {
+        assert_eq!(fix_articles(&["a", "otter", "and", "an", "unicorn", "a"]), ["an", "otter", "and", "a", "unicorn", "a"]);
+    }
+
+    #[test]
+    fn test_every_list_entry_gets_the_expected_article() {
+        let themed = Theme::ALL.iter().flat_map(|theme| ListKind::ALL.iter().flat_map(move |&kind| theme.words(kind)));
+        let entries = LISTS.iter().flat_map(|list| list.words.iter()).chain(themed);
+        for &entry in entries {
+            let by_letter = if "aeiou".contains(entry.chars().next().unwrap()) { "an" } else { "a" };
+            let expected = EXCEPTIONS.iter().find(|(word, _)| *word == entry).map_or(by_letter, |(_, article)| *article);
+            assert_eq!(indefinite(entry), expected, "{}", entry);
+        }
+    }
+}
@@ -1,13 +1,17 @@
 use clap::Parser;
 use colored::Colorize;
-use funny_password_generator::words::{
-    get_adjectives, get_professions, get_gerunds, get_objects, get_nouns,
-    ANIMALS,
-};
+use funny_password_generator::denylist::Denylist;
+use funny_password_generator::generator::WordConfig;
+use funny_password_generator::morphology::{inflect, lemma, Form};
+use funny_password_generator::rating::ContentRating;
+use funny_password_generator::themes::{Theme, ThemeMode};
+use funny_password_generator::wordset::{ListKind, WordSet};
 use rand::seq::SliceRandom;
-use rand::Rng;
+use rand::{CryptoRng, Rng, RngCore};
 use std::fmt;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum NameStyle {
@@ -51,6 +55,23 @@ impl NameStyle {
         }
     }
 
+    /// The word lists the style draws from.
+    fn lists(&self) -> &'static [ListKind] {
+        use ListKind::*;
+        match self {
+            NameStyle::Classic | NameStyle::Title => &[Adjective, Animal, Profession],
+            NameStyle::Epithet => &[Adjective, Animal, Profession, Noun],
+            NameStyle::Action => &[Gerund, Animal, Profession],
+            NameStyle::Versus => &[Animal, Profession, Noun],
+            NameStyle::Bearer => &[BearerTitle, Object],
+        }
+    }
+
+    /// Whether every list the style draws from has entries in `words`.
+    fn available(&self, words: &WordSet) -> bool {
+        self.lists().iter().all(|&kind| !words.list(kind).is_empty())
+    }
+
     fn example(&self) -> &'static str {
         match self {
             NameStyle::Classic => "the_grumpy_walrus",
@@ -101,9 +122,29 @@ struct Args {
     #[arg(long)]
     list_styles: bool,
 
-    /// Use only safe-for-work words (NSFW is enabled by default)
+    /// Use only safe-for-work words (NSFW is enabled by default); the same as
+    /// --rating workplace
     #[arg(long)]
     sfw: bool,
+
+    /// How much grown-up content words may have: kids, workplace, cheeky or
+    /// explicit (default: explicit, or workplace with --sfw)
+    #[arg(long, conflicts_with = "sfw")]
+    rating: Option<ContentRating>,
+
+    /// Draw subjects, objects and titles from themed packs (pirates, scifi,
+    /// office, fantasy, kitchen); comma-separate to combine
+    #[arg(long, value_delimiter = ',')]
+    theme: Vec<Theme>,
+
+    /// Whether themes replace the built-in lists (swap) or add to them (augment)
+    #[arg(long, default_value = "swap")]
+    theme_mode: ThemeMode,
+
+    /// Never use the words or phrases listed in this file (one per line,
+    /// # starts a comment)
+    #[arg(long, value_name = "PATH")]
+    deny_file: Option<PathBuf>,
 }
 
 /// Convert a name to snake_case (lowercase with underscores)
@@ -114,123 +155,105 @@ fn to_snake_case(name: &str) -> String {
 }
 
 /// Get a random subject (animal or profession)
-fn random_subject<R: Rng>(rng: &mut R, nsfw: bool) -> &'static str {
-    let professions = get_professions(nsfw);
-
+fn random_subject<'w, R: Rng>(rng: &mut R, words: &'w WordSet) -> &'w str {
     // 60% animals, 40% professions
     if rng.gen_bool(0.6) {
-        ANIMALS.choose(rng).unwrap()
+        words.animals.choose(rng).unwrap()
     } else {
-        professions.choose(rng).copied().unwrap_or("wizard")
-    }
-}
-
-/// Bearer titles for the "bearer" style
-const BEARER_TITLES: &[&str] = &[
-    "Guardian", "Keeper", "Bearer", "Warden", "Master",
-    "Lord", "Lady", "Duke", "Duchess", "Baron",
-    "Baroness", "Count", "Countess", "Herald", "Champion",
-    "Protector", "Defender", "Custodian", "Steward", "Sentinel",
-    "Overseer", "Caretaker", "Harbinger", "Envoy", "Emissary",
-];
-
-/// Convert a gerund to an "-er" noun (e.g., "juggling" -> "Juggler")
-fn gerund_to_er_noun(gerund: &str) -> String {
-    // Remove "ing" and add "er"
-    if gerund.ends_with("ing") {
-        let base = &gerund[..gerund.len() - 3];
-        // Handle doubling rules: if the base ends in a consonant that was doubled
-        // for the -ing form, we keep it (e.g., "running" -> "runner")
-        // Most cases just need the "er" suffix
-        format!("{}er", base)
-    } else {
-        format!("{}er", gerund)
+        words.professions.choose(rng).map_or("wizard", |p| p)
     }
 }
 
 /// Generate a name in Classic style: "the_[adjective]_[subject]"
-fn generate_classic<R: Rng>(rng: &mut R, nsfw: bool) -> String {
-    let adjectives = get_adjectives(nsfw);
-    let adj = adjectives.choose(rng).unwrap();
-    let subject = random_subject(rng, nsfw);
+fn generate_classic<R: Rng>(rng: &mut R, words: &WordSet) -> String {
+    let adj = words.adjectives.choose(rng).unwrap();
+    let subject = random_subject(rng, words);
 
     format!("the {} {}", adj, subject)
 }
 
 /// Generate a name in Title style: "[subject]_the_[adjective]"
-fn generate_title<R: Rng>(rng: &mut R, nsfw: bool) -> String {
-    let adjectives = get_adjectives(nsfw);
-    let subject = random_subject(rng, nsfw);
-    let adj = adjectives.choose(rng).unwrap();
+fn generate_title<R: Rng>(rng: &mut R, words: &WordSet) -> String {
+    let subject = random_subject(rng, words);
+    let adj = words.adjectives.choose(rng).unwrap();
 
     format!("{} the {}", subject, adj)
 }
 
 /// Generate a name in Epithet style: "[adjective]_[subject]_of_[noun]"
-fn generate_epithet<R: Rng>(rng: &mut R, nsfw: bool) -> String {
-    let adjectives = get_adjectives(nsfw);
-    let nouns = get_nouns(nsfw);
-    let adj = adjectives.choose(rng).unwrap();
-    let subject = random_subject(rng, nsfw);
-    let noun = nouns.choose(rng).unwrap();
+fn generate_epithet<R: Rng>(rng: &mut R, words: &WordSet) -> String {
+    let adj = words.adjectives.choose(rng).unwrap();
+    let subject = random_subject(rng, words);
+    let noun = words.nouns.choose(rng).unwrap();
 
     format!("{} {} of {}", adj, subject, noun)
 }
 
 /// Generate a name in Action style: "the_[gerund]_[subject]" or "[subject]_[verb]er"
-fn generate_action<R: Rng>(rng: &mut R, nsfw: bool) -> String {
-    let gerunds = get_gerunds(nsfw);
-    let gerund = gerunds.choose(rng).unwrap();
-    let subject = random_subject(rng, nsfw);
-
-    // 50% chance for each variant
-    if rng.gen_bool(0.5) {
-        // "the_[gerund]_[subject]" - e.g., "the_scheming_wizard"
-        format!("the {} {}", gerund, subject)
-    } else {
-        // "[subject]_[verb]er" - e.g., "dragon_slayer", "pickle_juggler"
-        let er_noun = gerund_to_er_noun(gerund);
-        format!("{} {}", subject, er_noun)
+fn generate_action<R: Rng>(rng: &mut R, words: &WordSet) -> String {
+    let gerund = words.gerunds.choose(rng).unwrap();
+    let subject = random_subject(rng, words);
+
+    // 50% chance for each variant; custom gerunds without a known lemma have
+    // no agent noun, so they always use the first
+    match lemma(gerund) {
+        Some(lemma) if rng.gen_bool(0.5) => {
+            // "[subject]_[verb]er" - e.g., "dragon_slayer", "pickle_juggler"
+            format!("{} {}", subject, inflect(lemma, Form::Agent))
+        }
+        _ => {
+            // "the_[gerund]_[subject]" - e.g., "the_scheming_wizard"
+            format!("the {} {}", gerund, subject)
+        }
     }
 }
 
 /// Generate a name in Versus style: "[subject]_vs_[noun]"
-fn generate_versus<R: Rng>(rng: &mut R, nsfw: bool) -> String {
-    let nouns = get_nouns(nsfw);
-    let subject = random_subject(rng, nsfw);
-    let noun = nouns.choose(rng).unwrap();
+fn generate_versus<R: Rng>(rng: &mut R, words: &WordSet) -> String {
+    let subject = random_subject(rng, words);
+    let noun = words.nouns.choose(rng).unwrap();
 
     format!("{} vs {}", subject, noun)
 }
 
 /// Generate a name in Bearer style: "[title]_of_the_[objects]"
-fn generate_bearer<R: Rng>(rng: &mut R, nsfw: bool) -> String {
-    let objects = get_objects(nsfw);
-    let title = BEARER_TITLES.choose(rng).unwrap();
-    let object = objects.choose(rng).unwrap();
+fn generate_bearer<R: Rng>(rng: &mut R, words: &WordSet) -> String {
+    let title = words.bearer_titles.choose(rng).unwrap();
+    let object = words.objects.choose(rng).unwrap();
 
     format!("{} of the {}", title, object)
 }
 
 /// Generate a name with the specified style
-fn generate_name(style: NameStyle, nsfw: bool) -> String {
-    let mut rng = rand::thread_rng();
+fn generate_name(style: NameStyle, words: &WordSet) -> String {
+    generate_name_with_rng(&mut rand::thread_rng(), style, words)
+}
 
+/// Generate a name with the specified style using the given RNG
+fn generate_name_with_rng<R: RngCore + CryptoRng>(rng: &mut R, style: NameStyle, words: &WordSet) -> String {
     match style {
-        NameStyle::Classic => generate_classic(&mut rng, nsfw),
-        NameStyle::Title => generate_title(&mut rng, nsfw),
-        NameStyle::Epithet => generate_epithet(&mut rng, nsfw),
-        NameStyle::Action => generate_action(&mut rng, nsfw),
-        NameStyle::Versus => generate_versus(&mut rng, nsfw),
-        NameStyle::Bearer => generate_bearer(&mut rng, nsfw),
+        NameStyle::Classic => generate_classic(rng, words),
+        NameStyle::Title => generate_title(rng, words),
+        NameStyle::Epithet => generate_epithet(rng, words),
+        NameStyle::Action => generate_action(rng, words),
+        NameStyle::Versus => generate_versus(rng, words),
+        NameStyle::Bearer => generate_bearer(rng, words),
     }
 }
 
-/// Generate a name with a random style
-fn generate_random_style(nsfw: bool) -> String {
-    let mut rng = rand::thread_rng();
-    let style = *NameStyle::ALL.choose(&mut rng).unwrap();
-    generate_name(style, nsfw)
+/// Generate a name with a random style out of `styles`
+fn generate_random_style(styles: &[NameStyle], words: &WordSet) -> String {
+    generate_random_style_with_rng(&mut rand::thread_rng(), styles, words)
+}
+
+/// Generate a name with a random style out of `styles` using the given RNG
+fn generate_random_style_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    styles: &[NameStyle],
+    words: &WordSet,
+) -> String {
+    let style = *styles.choose(rng).unwrap();
+    generate_name_with_rng(rng, style, words)
 }
 
 fn main() {
@@ -260,14 +283,47 @@ fn main() {
         None
     };
 
-    // NSFW is default, --sfw disables it
-    let nsfw = !args.sfw;
+    let denylist = args.deny_file.as_ref().map(|path| {
+        let denylist = Denylist::from_file(path).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        Arc::new(denylist)
+    });
+
+    // Explicit is default, --sfw limits it to workplace
+    let rating = args.rating.unwrap_or(if args.sfw { ContentRating::Workplace } else { ContentRating::Explicit });
+    let config = WordConfig {
+        rating,
+        themes: args.theme.clone(),
+        theme_mode: args.theme_mode,
+        denylist: denylist.clone(),
+        ..WordConfig::default()
+    };
+    let words = &config.words();
+
+    // Styles that draw from an emptied list can't be generated
+    if let Some(denylist) = &denylist {
+        let emptied = denylist.emptied(&WordConfig { denylist: None, ..config }.words());
+        for kind in emptied.iter().filter(|kind| NameStyle::ALL.iter().any(|s| s.lists().contains(kind))) {
+            eprintln!("Warning: every one of the {} is denied", kind.name().replace('_', " "));
+        }
+    }
+    let available: Vec<NameStyle> = NameStyle::ALL.iter().copied().filter(|s| s.available(words)).collect();
+    if let Some(s) = style.filter(|s| !s.available(words)) {
+        eprintln!("Error: The {} style has no words left with these options.", s);
+        std::process::exit(1);
+    }
+    if available.is_empty() {
+        eprintln!("Error: No style has words left with these options.");
+        std::process::exit(1);
+    }
 
     // Generate names
     for _ in 0..args.count {
         let name = match style {
-            Some(s) => generate_name(s, nsfw),
-            None => generate_random_style(nsfw),
+            Some(s) => generate_name(s, words),
+            None => generate_random_style(&available, words),
         };
         println!("{}", to_snake_case(&name).cyan().bold());
     }
@@ -1,19 +1,34 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::Colorize;
-use funny_password_generator::entropy::{
-    calculate_bruteforce_entropy, calculate_formatting_entropy, estimate_crack_time,
-    estimate_entropy_from_words_with_nsfw,
-};
-use funny_password_generator::generator::{generate_password, generate_password_with_template, WordConfig};
-use funny_password_generator::templates::{render_commas, Template};
-use funny_password_generator::words::ADJECTIVES;
-use rand::seq::SliceRandom;
-use rand::Rng;
+use funny_password_generator::compound::generate_compound_with_rng;
+use funny_password_generator::denylist::Denylist;
+use funny_password_generator::encoding::{decode, encode};
+use funny_password_generator::entropy::{calculate_bruteforce_entropy, estimate_crack_time};
+use funny_password_generator::formatting::{format_compound_with_rng, format_password_with_rng, FormatOptions};
+use funny_password_generator::generator::{generate_password_of_length_with_rng, PasswordLength, WordConfig};
+use funny_password_generator::lang::Language;
+use funny_password_generator::memorability::ListEntropy;
+use funny_password_generator::mix::TemplateMix;
+use funny_password_generator::parser::{parse_compound, parse_password, ParsedPassword};
+use funny_password_generator::pattern::Pattern;
+use funny_password_generator::policy::{PasswordPolicy, PolicyGenerator};
+use funny_password_generator::rating::ContentRating;
+use funny_password_generator::selection::{select_strongest, SelectionStrategy};
+use funny_password_generator::templates::Template;
+use funny_password_generator::themes::{Theme, ThemeMode};
+use funny_password_generator::wordinfo::{Difficulty, WordFilter};
+use funny_password_generator::wordset::{ListKind, WordSet};
+use std::path::PathBuf;
+use std::sync::Arc;
 
 #[derive(Parser)]
 #[command(name = "mkpass")]
 #[command(about = "Generate memorable, funny passwords using Markov-style word chaining")]
+#[command(args_conflicts_with_subcommands = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Minimum number of words in the password
     #[arg(default_value = "5")]
     min_words: usize,
@@ -34,238 +49,304 @@ struct Args {
     #[arg(long)]
     no_spaces: bool,
 
+    /// Separator between the lines of a haiku or couplet
+    #[arg(long, value_name = "SEP", default_value = " / ")]
+    line_separator: String,
+
     /// Show entropy (bits of randomness) alongside the password
     #[arg(long, short = 'e', default_value = "true")]
     show_entropy: bool,
 
     /// Use a specific template (declarative, imperative, question, warning,
-    /// fragment, versus, since, somewhere, shouldnot, requires)
+    /// fragment, versus, since, somewhere, shouldnot, requires, haiku, couplet)
     #[arg(long, short = 't')]
     template: Option<String>,
 
+    /// Draw templates by weight, e.g. "declarative:3,question:2"; "!versus"
+    /// leaves one out and "*" stands for the rest
+    #[arg(long, value_name = "MIX", conflicts_with = "template")]
+    templates: Option<TemplateMix>,
+
+    /// Use your own sentence shape, e.g. "{article} {adjective?}
+    /// {animal|profession} {present_verb} {object}"; repeat to pick among
+    /// several
+    #[arg(long, conflicts_with_all = ["template", "templates"])]
+    pattern: Vec<String>,
+
+    /// Read patterns from this file (one per line, # starts a comment)
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["template", "templates"])]
+    pattern_file: Option<PathBuf>,
+
     /// List all available templates
     #[arg(long)]
     list_templates: bool,
 
-    /// Use only safe-for-work words (NSFW is enabled by default)
+    /// Use only safe-for-work words (NSFW is enabled by default); the same as
+    /// --rating workplace
     #[arg(long)]
     sfw: bool,
-}
 
-/// Capitalize the first letter of a word
-fn capitalize_word(word: &str) -> String {
-    let mut chars = word.chars();
-    match chars.next() {
-        None => String::new(),
-        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-    }
-}
+    /// How much grown-up content words may have: kids, workplace, cheeky or
+    /// explicit (default: explicit, or workplace with --sfw)
+    #[arg(long, conflicts_with = "sfw")]
+    rating: Option<ContentRating>,
 
-/// Capitalize only the first letter of the entire string (skipping non-letter characters like quotes)
-fn capitalize_first(s: &str) -> String {
-    let mut result = String::new();
-    let mut capitalized = false;
+    /// Emit an unbiased uniform draw instead of keeping the strongest 30% of
+    /// 10x candidates (selection costs up to ~1.7 bits, which is subtracted)
+    #[arg(long)]
+    no_select: bool,
+
+    /// Keep extending each password until its pattern-attack entropy reaches
+    /// this many bits, at most 1024 (replaces the minimum word count)
+    #[arg(long, value_parser = parse_bits)]
+    bits: Option<f64>,
+
+    /// Chain this many sentences with connectives ("and then", "because",
+    /// "Meanwhile"); the minimum word count is per sentence, --bits for the
+    /// whole
+    #[arg(long, value_name = "N", default_value = "1", conflicts_with = "policy")]
+    sentences: usize,
+
+    /// Only emit passwords satisfying these rules, e.g.
+    /// "digit,upper,symbol,length=12-64,nospace" (rules: digit, upper, lower,
+    /// symbol, nospace, min=N, max=N, length=MIN-MAX)
+    #[arg(long)]
+    policy: Option<String>,
 
-    for c in s.chars() {
-        if !capitalized && c.is_alphabetic() {
-            result.extend(c.to_uppercase());
-            capitalized = true;
-        } else {
-            result.push(c);
-        }
-    }
+    /// Load custom word lists from a TOML file, or from a directory with one
+    /// <list>.txt file per list (lists not given keep their built-in words)
+    #[arg(long, value_name = "PATH")]
+    wordlist: Option<PathBuf>,
 
-    result
-}
+    /// Draw animals, professions, objects and locations from themed packs
+    /// (pirates, scifi, office, fantasy, kitchen); comma-separate to combine
+    #[arg(long, value_delimiter = ',')]
+    theme: Vec<Theme>,
 
-/// Get appropriate ending punctuation based on sentence type
-fn get_punctuation(words: &[String]) -> &'static str {
-    let mut rng = rand::thread_rng();
+    /// Whether themes replace the built-in lists (swap) or add to them (augment)
+    #[arg(long, default_value = "swap")]
+    theme_mode: ThemeMode,
 
-    if words.is_empty() {
-        return ".";
-    }
+    /// Write passwords in another language: en, fr, de or es (only the
+    /// declarative, versus and since templates exist outside English)
+    #[arg(long, default_value = "en")]
+    lang: Language,
 
-    // Get the first few words (lowercased) to detect sentence type
-    let first_word = words[0].to_lowercase();
-    let first_two: String = if words.len() >= 2 {
-        format!("{} {}", words[0].to_lowercase(), words[1].to_lowercase())
-    } else {
-        first_word.clone()
-    };
+    /// Only use words of at most this many letters (phrases are measured
+    /// word by word)
+    #[arg(long, value_name = "N")]
+    max_word_len: Option<usize>,
 
-    // Question words - always use "?"
-    const QUESTION_WORDS: &[&str] = &[
-        "why", "how", "what", "when", "where", "who", "which",
-    ];
-    if QUESTION_WORDS.contains(&first_word.as_str()) {
-        return "?";
-    }
+    /// Only use words of at most this many syllables
+    #[arg(long, value_name = "N")]
+    max_syllables: Option<usize>,
 
-    // Imperative/command starters - use "!" or "..."
-    const IMPERATIVE_STARTERS: &[&str] = &[
-        "never", "always", "beware", "avoid", "respect", "fear",
-        "watch", "stay", "keep", "bow", "surrender", "obey",
-        "question", "challenge", "embrace", "reject", "honor",
-        "distrust", "worship", "run", "hide", "confront", "welcome",
-        "do not", "never trust", "always fear", "beware of",
-    ];
-    for starter in IMPERATIVE_STARTERS {
-        if first_word == *starter || first_two.starts_with(starter) {
-            const IMPERATIVE_PUNCT: &[&str] = &["!", "..."];
-            return IMPERATIVE_PUNCT.choose(&mut rng).unwrap();
-        }
-    }
+    /// Only use words at most this hard to spell: easy, medium or hard
+    #[arg(long)]
+    max_difficulty: Option<Difficulty>,
 
-    // Warning starters (similar to imperative but more emphatic)
-    const WARNING_STARTERS: &[&str] = &[
-        "stop", "cease", "halt", "prevent", "resist", "abandon",
-        "forget", "forsake", "shun", "evade", "escape", "flee",
-        "renounce", "discontinue", "terminate", "end", "quit",
-        "banish", "prohibit", "forbid", "disallow", "outlaw",
-    ];
-    if WARNING_STARTERS.contains(&first_word.as_str()) {
-        const WARNING_PUNCT: &[&str] = &["!", "..."];
-        return WARNING_PUNCT.choose(&mut rng).unwrap();
-    }
+    /// Leave out hyphenated words
+    #[arg(long)]
+    no_hyphens: bool,
 
-    // Statement punctuation - no "?" for statements
-    const STATEMENT_PUNCT: &[&str] = &[".", "!", "..."];
-    STATEMENT_PUNCT.choose(&mut rng).unwrap()
-}
+    /// Never use the words or phrases listed in this file (one per line,
+    /// # starts a comment)
+    #[arg(long, value_name = "PATH")]
+    deny_file: Option<PathBuf>,
 
-/// Check if a word is an adjective
-fn is_adjective(word: &str) -> bool {
-    ADJECTIVES.contains(&word.to_lowercase().as_str())
+    /// Favour familiar, vivid words over obscure ones, from 0 (every word
+    /// equally likely) to 1; costs entropy, which -e shows
+    #[arg(long, value_name = "M", default_value = "0", value_parser = parse_memorability)]
+    memorability: f64,
 }
 
-/// Check if the first word of a password needs an article prefix
-/// Returns true if the password starts with an adjective (which looks incomplete without an article)
-fn needs_article_prefix(words: &[String]) -> bool {
-    if words.is_empty() {
-        return false;
-    }
-    // Check if first word is an adjective (strip quotes if present)
-    let first_word = words[0].trim_matches('"');
-    is_adjective(first_word)
+#[derive(Subcommand)]
+enum Command {
+    /// Encode bytes given as hex (e.g. a 128-bit recovery key) as funny sentences
+    Encode {
+        /// The bytes to encode, as hexadecimal
+        hex: String,
+    },
+    /// Recover the template and word lists behind a generated password
+    Analyze {
+        /// The password to analyze
+        #[arg(required = true, num_args = 1..)]
+        password: Vec<String>,
+
+        /// Only consider safe-for-work words
+        #[arg(long)]
+        sfw: bool,
+
+        /// Only consider words allowed at this rating (kids, workplace,
+        /// cheeky or explicit)
+        #[arg(long, conflicts_with = "sfw")]
+        rating: Option<ContentRating>,
+
+        /// The template mix the password was drawn from
+        #[arg(long, value_name = "MIX")]
+        templates: Option<TemplateMix>,
+
+        /// A pattern the password was made with; repeat for several
+        #[arg(long, conflicts_with = "templates")]
+        pattern: Vec<String>,
+    },
+    /// Decode sentences produced by `encode` back into hex
+    Decode {
+        /// The sentences, including the closing checksum
+        #[arg(required = true, num_args = 1..)]
+        text: Vec<String>,
+    },
 }
 
-/// Get an appropriate article ("A", "An", or "The") for the password
-fn get_article_prefix(words: &[String]) -> &'static str {
-    let mut rng = rand::thread_rng();
-
-    // Use "The" most of the time (70%) or "A/An" (30%)
-    if rng.gen_bool(0.7) {
-        "The"
-    } else {
-        // Check if first word starts with vowel (after stripping quotes)
-        let first_word = if !words.is_empty() {
-            words[0].trim_matches('"')
-        } else {
-            ""
-        };
-
-        if first_word.chars().next()
-            .map(|c| matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u'))
-            .unwrap_or(false)
-        {
-            "An"
-        } else {
-            "A"
-        }
-    }
+/// The rating chosen on the command line: explicit by default, workplace
+/// with --sfw
+fn content_rating(rating: Option<ContentRating>, sfw: bool) -> ContentRating {
+    rating.unwrap_or(if sfw { ContentRating::Workplace } else { ContentRating::Explicit })
 }
 
-/// Maybe wrap adjectives in sarcastic "air quotes" (30% chance per adjective)
-fn maybe_airquote_adjectives(words: Vec<String>) -> Vec<String> {
-    let mut rng = rand::thread_rng();
-
-    words.into_iter().map(|word| {
-        if is_adjective(&word) && rng.gen_bool(0.3) {
-            format!("\"{}\"", word)
-        } else {
-            word
-        }
-    }).collect()
+/// Parse a memorability between 0 and 1
+fn parse_memorability(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(m) if (0.0..=1.0).contains(&m) => Ok(m),
+        _ => Err(format!("Memorability must be a number from 0 to 1, got '{}'", value)),
+    }
 }
 
-/// Maybe add a dramatic one-word opener (25% chance)
-fn maybe_add_opener() -> Option<String> {
-    let mut rng = rand::thread_rng();
-
-    if !rng.gen_bool(0.25) {
-        return None;
+/// Parse an entropy target above 0 and at most 1024 bits
+fn parse_bits(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(bits) if bits > 0.0 && bits <= 1024.0 => Ok(bits),
+        _ => Err(format!("Bits must be a number above 0 and at most 1024, got '{}'", value)),
     }
+}
 
-    const OPENERS: &[&str] = &[
-        "Beware", "Listen", "Warning", "Attention", "Alert",
-        "Behold", "Alas", "Indeed", "Clearly", "Obviously",
-        "Seriously", "Honestly", "Tragically", "Surprisingly",
-        "Allegedly", "Reportedly", "Shockingly", "Naturally",
-        "Curiously", "Mysteriously", "Suspiciously", "Frankly",
-        "Incredibly", "Absurdly", "Hilariously", "Sadly",
-        "Fortunately", "Unfortunately", "Remarkably", "Notably",
+/// How many bits favouring memorable words takes off each word, on average
+/// and at worst, across the main vocabulary lists
+fn memorability_cost(words: &WordSet, memorability: f64) -> (f64, f64) {
+    const VOCABULARY: &[ListKind] = &[
+        ListKind::Adjective,
+        ListKind::Animal,
+        ListKind::Profession,
+        ListKind::Noun,
+        ListKind::PresentVerb,
+        ListKind::Object,
+        ListKind::Adverb,
     ];
-
-    let opener = OPENERS.choose(&mut rng).unwrap();
-    let punctuation = if rng.gen_bool(0.5) { "!" } else { "..." };
-
-    Some(format!("{}{}", opener, punctuation))
+    let entropies: Vec<ListEntropy> = VOCABULARY
+        .iter()
+        .map(|&kind| words.list(kind))
+        .filter(|list| !list.is_empty())
+        .map(|list| ListEntropy::of(list, memorability))
+        .collect();
+    let mean = |loss: fn(&ListEntropy) -> f64| entropies.iter().map(loss).sum::<f64>() / entropies.len().max(1) as f64;
+    (mean(|e| e.uniform - e.shannon), mean(|e| e.uniform - e.min))
 }
 
-/// Format a password from words based on the provided options
-fn format_password(words: Vec<String>, separator: &str, capitalize_all: bool, no_spaces: bool) -> String {
-    let sep = if no_spaces { "" } else { separator };
-
-    // Render comma markers into actual commas (e.g., "In the shadows, the penguin...")
-    let words = render_commas(words);
-
-    // Check if we need to add an article prefix BEFORE adding air quotes
-    // (so we check the original first word, not a quoted version)
-    let needs_article = needs_article_prefix(&words);
-
-    // Determine punctuation before modifying words (based on sentence type)
-    let punctuation = get_punctuation(&words);
-
-    // Maybe add sarcastic air quotes to some adjectives
-    let words = maybe_airquote_adjectives(words);
+/// Parse a hex string (whitespace and an optional 0x prefix allowed) into bytes
+fn parse_hex(hex: &str) -> Result<Vec<u8>, String> {
+    let trimmed = hex.trim();
+    let digits: Vec<u8> =
+        trimmed.strip_prefix("0x").unwrap_or(trimmed).bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    if !digits.iter().all(u8::is_ascii_hexdigit) {
+        return Err(format!("Invalid hex input: '{}'", hex));
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err(format!("Hex input has an odd number of digits: '{}'", hex));
+    }
+    let digit = |b: u8| (b as char).to_digit(16).unwrap() as u8;
+    Ok(digits.chunks(2).map(|pair| digit(pair[0]) << 4 | digit(pair[1])).collect())
+}
 
-    // Prepend article if needed (after air quotes, so the article comes before any quoted word)
-    let words = if needs_article {
-        let article = get_article_prefix(&words);
-        let mut new_words = vec![article.to_string()];
-        new_words.extend(words);
-        new_words
-    } else {
-        words
+/// Describe every word of the most conservative reading of a password, or of
+/// each of its sentences if it chains several
+fn analyze(password: &str, rating: ContentRating, mix: Option<TemplateMix>) -> Result<String, String> {
+    let config = WordConfig { rating, mix: mix.map(Arc::new), ..WordConfig::default() };
+    let readings = parse_password(password, &config);
+    let mut lines = Vec::new();
+    let (entropy, count) = match readings.first() {
+        Some(reading) => {
+            describe_reading(reading, &mut lines);
+            (reading.entropy(), readings.len())
+        }
+        None => {
+            let compounds = parse_compound(password, &config);
+            let Some(compound) = compounds.first() else {
+                return Err("not recognized as a mkpass password".to_string());
+            };
+            for (i, clause) in compound.clauses.iter().enumerate() {
+                if let Some(connective) = i.checked_sub(1).map(|j| compound.connectives[j]) {
+                    lines.push(format!("  {:<20} {}", connective.words().join(" "), "connective".dimmed()));
+                }
+                describe_reading(clause, &mut lines);
+            }
+            (compound.entropy(), compounds.len())
+        }
     };
 
-    let joined = if capitalize_all {
-        // CamelCase style - capitalize each word
-        words.into_iter().map(|w| capitalize_word(&w)).collect::<Vec<_>>().join(sep)
-    } else {
-        // Normal style - just join, we'll capitalize first letter after
-        words.join(sep)
-    };
+    lines.push(format!(
+        "{} {:.0} bits (crack: ~{}), not counting formatting",
+        "Pattern attack:".dimmed(),
+        entropy,
+        estimate_crack_time(entropy)
+    ));
+    if count > 1 {
+        lines.push(format!("{}", format!("({} possible readings; showing the weakest)", count).dimmed()));
+    }
 
-    // Capitalize first letter and add punctuation
-    let capitalized = capitalize_first(&joined);
-    let with_punctuation = format!("{}{}", capitalized, punctuation);
+    Ok(lines.join("\n"))
+}
 
-    // Maybe add a dramatic opener
-    match maybe_add_opener() {
-        Some(opener) => format!("{} {}", opener, with_punctuation),
-        None => with_punctuation,
+/// List the template and words of one sentence's reading
+fn describe_reading(reading: &ParsedPassword, lines: &mut Vec<String>) {
+    lines.push(format!("{} {}", "Template:".dimmed(), reading.template.name().cyan()));
+    if let Some(opener) = reading.opener {
+        lines.push(format!("  {:<20} {}", opener, "opener".dimmed()));
+    }
+    if let Some(article) = reading.article_prefix {
+        lines.push(format!("  {:<20} {}", article, "article prefix".dimmed()));
+    }
+    for word in &reading.words {
+        lines.push(format!("  {:<20} {}", word.text, word.list.dimmed()));
+    }
+}
+
+/// Run an encode/decode/analyze subcommand
+fn run_command(command: Command) -> Result<String, String> {
+    match command {
+        Command::Analyze { password, sfw, rating, templates, pattern } => {
+            // Patterns are drawn like templates, each equally likely
+            let parsed: Result<Vec<Pattern>, _> = pattern.iter().map(|text| Pattern::parse(text)).collect();
+            let patterns: Vec<Template> =
+                parsed.map_err(|e| e.to_string())?.into_iter().map(Template::custom).collect();
+            let mix = if patterns.is_empty() { templates } else { Some(TemplateMix::uniform(&patterns)) };
+            analyze(&password.join(" "), content_rating(rating, sfw), mix)
+        }
+        Command::Encode { hex } => encode(&parse_hex(&hex)?).map(|text| text.cyan().bold().to_string()).map_err(|e| e.to_string()),
+        Command::Decode { text } => decode(&text.join(" "))
+            .map(|bytes| bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>().cyan().bold().to_string())
+            .map_err(|e| e.to_string()),
     }
 }
 
 fn main() {
     let args = Args::parse();
 
+    // Handle encode/decode/analyze
+    if let Some(command) = args.command {
+        match run_command(command) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     // Handle --list-templates
     if args.list_templates {
         println!("Available templates:");
-        for template in Template::ALL {
+        for template in args.lang.templates() {
             println!("  {}", template.name());
         }
         return;
@@ -274,7 +355,11 @@ fn main() {
     // Parse template if specified
     let template = if let Some(ref name) = args.template {
         match Template::from_name(name) {
-            Some(t) => Some(t),
+            Some(t) if args.lang.supports(&t) => Some(t),
+            Some(t) => {
+                eprintln!("Error: The {} template isn't available in '{}'.", t.name(), args.lang);
+                std::process::exit(1);
+            }
             None => {
                 eprintln!("Error: Unknown template '{}'. Use --list-templates to see available options.", name);
                 std::process::exit(1);
@@ -284,44 +369,222 @@ fn main() {
         None
     };
 
-    // Create word config based on CLI flags (NSFW is default, --sfw disables it)
-    let word_config = WordConfig { nsfw: !args.sfw };
+    // Parse custom patterns, from the command line and then the pattern file
+    let parsed: Result<Vec<Pattern>, _> = args.pattern.iter().map(|text| Pattern::parse(text)).collect();
+    let mut patterns = parsed.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+    if let Some(path) = &args.pattern_file {
+        patterns.extend(Pattern::from_file(path).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }));
+    }
+    if !patterns.is_empty() && args.lang != Language::English {
+        eprintln!("Error: Patterns can only be written in English.");
+        std::process::exit(1);
+    }
+    let patterns: Vec<Template> = patterns.into_iter().map(Template::custom).collect();
+    if args.sentences == 0 {
+        eprintln!("Error: --sentences must be at least 1.");
+        std::process::exit(1);
+    }
+    if args.sentences > 1 && args.lang != Language::English {
+        eprintln!("Error: Sentences can only be chained in English.");
+        std::process::exit(1);
+    }
+    let template = match &patterns[..] {
+        [pattern] => Some(pattern.clone()),
+        _ => template,
+    };
 
-    // Generate 10x more candidates and keep the most secure ones
-    let candidates_count = args.count * 10;
-    let mut candidates: Vec<(String, f64, f64)> = Vec::with_capacity(candidates_count);
+    // Several patterns are drawn like templates, each equally likely
+    let mix = if patterns.len() > 1 { Some(TemplateMix::uniform(&patterns)) } else { args.templates.clone() };
+    if let Some(mix) = &args.templates {
+        for t in mix.templates().into_iter().filter(|t| !args.lang.supports(t)) {
+            eprintln!("Warning: the {} template isn't available in '{}'", t.name(), args.lang);
+        }
+    }
 
-    for _ in 0..candidates_count {
-        let words = match template {
-            Some(t) => generate_password_with_template(t, args.min_words, &word_config),
-            None => generate_password(args.min_words, &word_config),
-        };
+    // Create word config based on CLI flags
+    let rating = content_rating(args.rating, args.sfw);
+    let custom_words = args.wordlist.as_ref().map(|path| {
+        let base = WordSet::builtin(rating);
+        let loaded = if path.is_dir() { WordSet::from_dir(path, base) } else { WordSet::from_toml_file(path, base) };
+        let words = loaded.unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        Arc::new(words)
+    });
+    let denylist = args.deny_file.as_ref().map(|path| {
+        let denylist = Denylist::from_file(path).unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        Arc::new(denylist)
+    });
+    let word_config = WordConfig {
+        rating,
+        words: custom_words,
+        themes: args.theme.clone(),
+        theme_mode: args.theme_mode,
+        language: args.lang,
+        filter: WordFilter {
+            max_word_len: args.max_word_len,
+            max_syllables: args.max_syllables,
+            max_difficulty: args.max_difficulty,
+            no_hyphens: args.no_hyphens,
+        },
+        denylist: denylist.clone(),
+        memorability: args.memorability,
+        mix: mix.map(Arc::new),
+    };
+    let unfiltered = WordConfig { filter: WordFilter::default(), ..word_config.clone() };
+    // Bearer titles are only used by mkname
+    let used_by_mkpass = |kind: &ListKind| *kind != ListKind::BearerTitle;
+    if let Some(denylist) = &denylist {
+        let undenied = WordConfig { denylist: None, ..unfiltered.clone() };
+        for kind in denylist.emptied(&undenied.words()).iter().filter(|kind| used_by_mkpass(kind)) {
+            eprintln!("Warning: every one of the {} is denied", kind.name().replace('_', " "));
+        }
+    }
+    for kind in word_config.filter.unmet(&unfiltered.words()).iter().filter(|kind| used_by_mkpass(kind)) {
+        eprintln!("Warning: no {} meet the word filters, so the closest ones are used", kind.name().replace('_', " "));
+    }
+
+    // Templates that draw from an emptied list can't be generated
+    let available = word_config.templates();
+    if available.is_empty() && args.templates.is_some() {
+        eprintln!("Error: No template in --templates has words left with these options.");
+        std::process::exit(1);
+    }
+    if available.is_empty() {
+        eprintln!("Error: No template has words left with these options.");
+        std::process::exit(1);
+    }
+    if let Some(t) = template.iter().chain(&patterns).find(|t| !word_config.can_generate(t)) {
+        eprintln!("Error: The {} template has no words left with these options.", t.name());
+        std::process::exit(1);
+    }
+
+    // Generate 10x more candidates and keep the most secure ones, unless disabled
+    let strategy = if args.no_select {
+        SelectionStrategy::UNIFORM
+    } else {
+        SelectionStrategy::STRONGEST
+    };
+    let selection_penalty = strategy.entropy_penalty(args.count);
+
+    let format_options = FormatOptions {
+        separator: if args.no_spaces { String::new() } else { args.separator.clone() },
+        line_separator: if args.no_spaces {
+            args.line_separator.chars().filter(|c| !c.is_whitespace()).collect()
+        } else {
+            args.line_separator.clone()
+        },
+        camel_case: args.capitalize,
+        language: args.lang,
+        words: Some(word_config.words()),
+        // Openers don't come from the word lists, so filters turn them off,
+        // and so does a denylist that catches one
+        openers: word_config.filter.is_empty()
+            && !denylist.as_ref().is_some_and(|denylist| args.lang.openers().iter().any(|o| denylist.denies(o))),
+        ..FormatOptions::default()
+    };
+
+    // Generate to a word count, or to the entropy target plus what selection will take off
+    let length = match args.bits {
+        Some(bits) => PasswordLength::MinEntropy(bits + selection_penalty),
+        None => PasswordLength::MinWords(args.min_words),
+    };
+
+    let mut rng = rand::thread_rng();
+
+    // Show what favouring memorable words costs; the figures below count the worst case
+    if args.show_entropy && args.memorability > 0.0 {
+        let (average, worst) = memorability_cost(&word_config.words(), args.memorability);
+        println!(
+            "{}",
+            format!(
+                "Memorability {}: each word is worth {:.1} bits less on average and {:.1} bits less at worst (counted below)",
+                args.memorability, average, worst
+            )
+            .dimmed()
+        );
+    }
 
-        // Count adjectives before formatting (for formatting entropy calculation)
-        let adjective_count = words.iter().filter(|w| is_adjective(w)).count();
+    // Parse and prepare the policy if specified (steering plus rejection sampling)
+    let policy_generator = args.policy.as_ref().map(|spec| {
+        let policy: PasswordPolicy = spec.parse().unwrap_or_else(|e| {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        });
+        PolicyGenerator::new_with_rng(policy, template.clone(), length, &word_config, &format_options, &mut rng)
+            .unwrap_or_else(|e| {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            })
+    });
 
-        let word_entropy = estimate_entropy_from_words_with_nsfw(&words, !args.sfw);
-        let password = format_password(words, &args.separator, args.capitalize, args.no_spaces);
+    let candidates_count = strategy.candidates_for(args.count);
+    let mut candidates: Vec<(String, f64, f64)> = Vec::with_capacity(candidates_count);
 
-        // Total pattern entropy = word selection + formatting choices
-        let formatting_entropy = calculate_formatting_entropy(&password, adjective_count);
-        let pattern_entropy = word_entropy + formatting_entropy;
+    for _ in 0..candidates_count {
+        let (password, pattern_entropy) = match policy_generator {
+            // Entropy already has the rejection penalty subtracted
+            Some(ref generator) => match generator.generate_with_rng(&mut rng) {
+                Ok(compliant) => (compliant.text, compliant.entropy),
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    std::process::exit(1);
+                }
+            },
+            None if args.sentences > 1 => {
+                let compound = generate_compound_with_rng(&mut rng, args.sentences, template.clone(), length, &word_config)
+                    .unwrap_or_else(|e| {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    });
+                let formatted = format_compound_with_rng(&compound, &format_options, &mut rng);
+                let pattern_entropy = compound.entropy() + formatted.entropy();
+                (formatted.text, pattern_entropy)
+            }
+            None => {
+                let generated = generate_password_of_length_with_rng(&mut rng, template.clone(), length, &word_config)
+                    .unwrap_or_else(|e| {
+                        eprintln!("Error: {}", e);
+                        std::process::exit(1);
+                    });
+
+                // Exact entropy of the template's random choices
+                let word_entropy = generated.entropy();
+                let formatted = format_password_with_rng(generated.words, &format_options, &mut rng);
+
+                // Total pattern entropy = word selection + formatting choices
+                let pattern_entropy = word_entropy + formatted.entropy();
+                (formatted.text, pattern_entropy)
+            }
+        };
 
         let bruteforce_entropy = calculate_bruteforce_entropy(&password);
 
         candidates.push((password, pattern_entropy, bruteforce_entropy));
     }
 
-    // Sort by pattern entropy (descending) - keep the most secure
-    candidates.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+    let selected = select_strongest(
+        candidates,
+        args.count,
+        &strategy,
+        |candidate| candidate.1,
+        &mut rng,
+    );
 
-    // Take top 30% and shuffle them (adds unpredictability while maintaining quality)
-    let top_count = (candidates.len() as f64 * 0.3).ceil() as usize;
-    let mut top_candidates: Vec<_> = candidates.into_iter().take(top_count).collect();
-    top_candidates.shuffle(&mut rand::thread_rng());
+    for (password, pattern_entropy, bruteforce_entropy) in selected {
+        // Selecting the strongest candidates narrows the output distribution
+        let pattern_entropy = pattern_entropy - selection_penalty;
 
-    // Pick randomly from the shuffled top 30%
-    for (password, pattern_entropy, bruteforce_entropy) in top_candidates.into_iter().take(args.count) {
         if args.show_entropy {
             let pattern_time = estimate_crack_time(pattern_entropy);
             let bruteforce_time = estimate_crack_time(bruteforce_entropy);
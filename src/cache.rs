@@ -0,0 +1,85 @@
+//! Small caches for data derived from word lists.
+//!
+//! Templates derive the same data from the configured lists over and over:
+//! themed, denied and filtered lists, plural subjects, haiku syllable grids
+//! and rhyme classes. A [`Cache`] builds each value once and hands out `Arc`s
+//! to it. It keeps at most [`CAPACITY`] values, dropping the least recently
+//! used one, so a long-running program that tries many configurations doesn't
+//! keep every one of them.
+//!
+//! Keys name the shared lists a value was derived from with a [`Source`]: a
+//! `Weak` compared by address. While the `Weak` exists the address can't be
+//! reused, and since `Arc::get_mut` refuses to hand out a value that has weak
+//! references, the lists can't change either, so a key always means the same
+//! lists.
+
+use std::sync::{Arc, Mutex, Weak};
+
+/// Most values a [`Cache`] keeps.
+pub(crate) const CAPACITY: usize = 16;
+
+/// Values built from keys of type `K`, most recently used first.
+pub(crate) struct Cache<K, V> {
+    entries: Mutex<Vec<(K, Arc<V>)>>,
+}
+
+impl<K: PartialEq, V> Cache<K, V> {
+    pub(crate) const fn new() -> Self {
+        Cache { entries: Mutex::new(Vec::new()) }
+    }
+
+    /// The value for `key`, built with `build` if it isn't cached.
+    pub(crate) fn get(&self, key: K, build: impl FnOnce() -> V) -> Arc<V> {
+        let mut entries = self.entries.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(i) = entries.iter().position(|(k, _)| *k == key) {
+            let entry = entries.remove(i);
+            let value = Arc::clone(&entry.1);
+            entries.insert(0, entry);
+            return value;
+        }
+        let value = Arc::new(build());
+        entries.truncate(CAPACITY - 1);
+        entries.insert(0, (key, Arc::clone(&value)));
+        value
+    }
+}
+
+/// The shared value a cached one was derived from, compared by address.
+pub(crate) struct Source<T>(Weak<T>);
+
+impl<T> Source<T> {
+    pub(crate) fn of(value: &Arc<T>) -> Self {
+        Source(Arc::downgrade(value))
+    }
+}
+
+impl<T> PartialEq for Source<T> {
+    fn eq(&self, other: &Self) -> bool {
+        Weak::ptr_eq(&self.0, &other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builds_once_and_keeps_recent_values() {
+        let cache = Cache::new();
+        let first = cache.get(0, || "zero".to_string());
+        assert!(Arc::ptr_eq(&first, &cache.get(0, || unreachable!())));
+        for key in 1..=CAPACITY {
+            cache.get(key, || key.to_string());
+        }
+        // Key 0 is now the least recently used and has been dropped
+        assert_eq!(Arc::strong_count(&first), 1);
+        assert_eq!(*cache.get(0, || "again".to_string()), "again");
+    }
+
+    #[test]
+    fn test_sources_compare_by_address() {
+        let (a, b) = (Arc::new(1), Arc::new(1));
+        assert!(Source::of(&a) == Source::of(&a));
+        assert!(Source::of(&a) != Source::of(&b));
+    }
+}
@@ -0,0 +1,183 @@
+//! Several sentences chained into one long passphrase.
+//!
+//! A single template tops out well below the 100+ bits wanted for a vault
+//! master password. A [`CompoundPassword`] chains independently generated
+//! sentences with connectives:
+//!
+//! ```text
+//! The grumpy walrus juggles pickles, and then a sentient accountant sings.
+//! Never trust a caffeinated wizard because the penguin owes nickels!
+//! Somewhere a llama is plotting... Meanwhile, chaos requires more coffee.
+//! ```
+//!
+//! Each sentence records its own decisions (including its template choice),
+//! and each connective is a uniform choice among those the denylist leaves
+//! ([`Connective::available`]), so the entropy of the whole is the sum of its
+//! parts. The connectives are English; [`crate::formatting::format_compound`]
+//! renders the result and [`crate::parser::parse_compound`] reads it back.
+
+use rand::{CryptoRng, Rng, RngCore};
+
+use crate::generator::{generate_password_of_length_with_rng, GenerateError, PasswordLength, WordConfig};
+use crate::password::{Decision, GeneratedPassword};
+use crate::templates::Template;
+
+/// How a sentence is joined to the one before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connective {
+    /// "..., and then ..."
+    AndThen,
+    /// "... because ..."
+    Because,
+    /// "... Meanwhile, ..."
+    Meanwhile,
+}
+
+impl Connective {
+    /// All connectives.
+    pub const ALL: &'static [Connective] = &[Connective::AndThen, Connective::Because, Connective::Meanwhile];
+
+    /// The words of the connective, lowercase.
+    pub fn words(&self) -> &'static [&'static str] {
+        match self {
+            Connective::AndThen => &["and", "then"],
+            Connective::Because => &["because"],
+            Connective::Meanwhile => &["meanwhile"],
+        }
+    }
+
+    /// The connectives `config` allows, each equally likely: those with no
+    /// denied word.
+    pub fn available(config: &WordConfig) -> Vec<Connective> {
+        let denied = |connective: &Connective| {
+            config.denylist.as_ref().is_some_and(|denylist| denylist.denies(&connective.words().join(" ")))
+        };
+        Connective::ALL.iter().copied().filter(|connective| !denied(connective)).collect()
+    }
+
+    /// Whether the connective ends the sentence before it and starts a new one.
+    pub fn starts_sentence(&self) -> bool {
+        *self == Connective::Meanwhile
+    }
+}
+
+/// Sentences chained with connectives, with the full record of how they were made.
+#[derive(Clone, Debug)]
+pub struct CompoundPassword {
+    /// The sentences, in order.
+    pub clauses: Vec<GeneratedPassword>,
+    /// The connective in front of each sentence after the first.
+    pub connectives: Vec<Connective>,
+    /// The connective choices, in order.
+    pub decisions: Vec<Decision>,
+}
+
+impl CompoundPassword {
+    /// Exact entropy in bits: every sentence's decisions plus the connective choices.
+    pub fn entropy(&self) -> f64 {
+        let clauses: f64 = self.clauses.iter().map(GeneratedPassword::entropy).sum();
+        clauses + self.decisions.iter().map(Decision::bits).sum::<f64>()
+    }
+}
+
+/// Generate `sentences` sentences joined by random connectives.
+///
+/// Each sentence comes from `template`, or a random template when `None`.
+/// With [`PasswordLength::MinWords`] every sentence gets at least that many
+/// words; with [`PasswordLength::MinEntropy`] the target is for the whole,
+/// split evenly over the sentences still to come.
+///
+/// # Errors
+///
+/// If a sentence can't be generated (see
+/// [`generate_with_min_entropy`](crate::generator::generate_with_min_entropy)),
+/// or the denylist leaves no connective to join them with.
+pub fn generate_compound(
+    sentences: usize,
+    template: Option<Template>,
+    length: PasswordLength,
+    config: &WordConfig,
+) -> Result<CompoundPassword, GenerateError> {
+    generate_compound_with_rng(&mut rand::thread_rng(), sentences, template, length, config)
+}
+
+/// Generate `sentences` sentences joined by random connectives using the given RNG.
+pub fn generate_compound_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    sentences: usize,
+    template: Option<Template>,
+    length: PasswordLength,
+    config: &WordConfig,
+) -> Result<CompoundPassword, GenerateError> {
+    let sentences = sentences.max(1);
+    let connectives = Connective::available(config);
+    if sentences > 1 && connectives.is_empty() {
+        return Err(GenerateError::NoConnective);
+    }
+    let mut compound = CompoundPassword { clauses: Vec::new(), connectives: Vec::new(), decisions: Vec::new() };
+    for i in 0..sentences {
+        if i > 0 {
+            compound.connectives.push(connectives[rng.gen_range(0..connectives.len())]);
+            compound.decisions.push(Decision::uniform("connective", connectives.len()));
+        }
+        // What is still missing depends only on the choices made so far
+        let length = match length {
+            PasswordLength::MinEntropy(bits) => PasswordLength::MinEntropy((bits - compound.entropy()) / (sentences - i) as f64),
+            words => words,
+        };
+        compound.clauses.push(generate_password_of_length_with_rng(rng, template.clone(), length, config)?);
+    }
+    Ok(compound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_entropy_sums_sentences_and_connectives() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let compound = generate_compound_with_rng(&mut rng, 3, None, PasswordLength::MinWords(4), &WordConfig::default())
+            .unwrap();
+        assert_eq!(compound.clauses.len(), 3);
+        assert_eq!(compound.connectives.len(), 2);
+        let clauses: f64 = compound.clauses.iter().map(GeneratedPassword::entropy).sum();
+        let connectives = 2.0 * (Connective::ALL.len() as f64).log2();
+        assert!((compound.entropy() - clauses - connectives).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_entropy_target_covers_the_whole() {
+        let mut rng = StdRng::seed_from_u64(2);
+        for sentences in 1..4 {
+            let compound =
+                generate_compound_with_rng(&mut rng, sentences, None, PasswordLength::MinEntropy(120.0), &WordConfig::default())
+                    .unwrap();
+            assert_eq!(compound.clauses.len(), sentences);
+            assert!(compound.entropy() >= 120.0, "{}", compound.entropy());
+        }
+    }
+
+    #[test]
+    fn test_denied_connectives_are_never_drawn() {
+        use crate::denylist::Denylist;
+        use std::sync::Arc;
+
+        let config = WordConfig { denylist: Some(Arc::new(Denylist::new(["because"]))), ..WordConfig::default() };
+        assert_eq!(Connective::available(&config), [Connective::AndThen, Connective::Meanwhile]);
+        let mut rng = StdRng::seed_from_u64(3);
+        for _ in 0..50 {
+            let compound = generate_compound_with_rng(&mut rng, 3, None, PasswordLength::MinWords(4), &config).unwrap();
+            assert!(!compound.connectives.contains(&Connective::Because));
+            assert!(compound.decisions.iter().all(|d| d.probability == 0.5));
+        }
+
+        let config = WordConfig { denylist: Some(Arc::new(Denylist::new(["then", "because", "meanwhile"]))), ..config };
+        let length = PasswordLength::MinWords(4);
+        let error = generate_compound_with_rng(&mut rng, 2, None, length, &config).unwrap_err();
+        assert_eq!(error, GenerateError::NoConnective);
+        assert!(generate_compound_with_rng(&mut rng, 1, None, length, &config).is_ok());
+    }
+}
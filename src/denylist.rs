@@ -0,0 +1,148 @@
+//! Words and phrases that must never appear in a password.
+//!
+//! A [`Denylist`] removes every list entry that contains a denied term as a
+//! whole word or run of words, ignoring case: denying "acme" removes "acme"
+//! and "acme anvil" but not "acmeology", and denying "tax return" removes
+//! "tax return" but not "tax". Removing entries shrinks the lists, and the
+//! entropy templates report shrinks with them.
+//!
+//! Prepositions and connectors are filtered too, and a verb goes with the
+//! preposition it always takes ("plots against"). Articles and the "since"
+//! before a year are grammar rather than vocabulary and aren't affected.
+//!
+//! Fragments count their subjects ("three wizards juggle"), so a subject goes
+//! when its plural is denied and a present verb when its plural form is:
+//! denying "wizards" removes "wizard", and denying "juggle" removes
+//! "juggles".
+
+use std::fs;
+use std::path::Path;
+
+use crate::plural::{plural, plural_verb};
+use crate::words::get_verb_preposition;
+use crate::wordset::{ListKind, WordSet, WordSetError};
+
+/// Terms removed from every word list.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Denylist {
+    /// Each term split into lowercase words.
+    terms: Vec<Vec<String>>,
+}
+
+impl Denylist {
+    /// A denylist of the given terms.
+    pub fn new<S: AsRef<str>>(terms: impl IntoIterator<Item = S>) -> Denylist {
+        let terms = terms.into_iter().map(|term| split(term.as_ref())).filter(|words| !words.is_empty()).collect();
+        Denylist { terms }
+    }
+
+    /// Parse one term per line. Blank lines and lines starting with `#` are
+    /// ignored.
+    pub fn parse(text: &str) -> Denylist {
+        Denylist::new(text.lines().map(str::trim).filter(|line| !line.starts_with('#')))
+    }
+
+    /// Read a denylist file (see [`Denylist::parse`]).
+    pub fn from_file(path: &Path) -> Result<Denylist, WordSetError> {
+        let text = fs::read_to_string(path).map_err(|error| WordSetError::Io { path: path.to_path_buf(), error })?;
+        Ok(Denylist::parse(&text))
+    }
+
+    /// True if nothing is denied.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+
+    /// Whether `entry` contains a denied term.
+    pub fn denies(&self, entry: &str) -> bool {
+        let words = split(entry);
+        self.terms.iter().any(|term| words.windows(term.len()).any(|window| window == term.as_slice()))
+    }
+
+    /// Whether `entry`, or the preposition it always takes, is denied.
+    fn removes(&self, entry: &str) -> bool {
+        self.denies(entry) || get_verb_preposition(entry).is_some_and(|preposition| self.denies(preposition))
+    }
+
+    /// Whether `entry` of a `kind` list, or a form a fragment makes of it,
+    /// is denied.
+    fn removes_from(&self, kind: ListKind, entry: &str) -> bool {
+        self.removes(entry)
+            || match kind {
+                ListKind::Animal | ListKind::Profession | ListKind::Noun => self.denies(&plural(entry)),
+                ListKind::PresentVerb => self.denies(&plural_verb(entry)),
+                _ => false,
+            }
+    }
+
+    /// `base` without the denied entries.
+    pub fn apply(&self, base: &WordSet) -> WordSet {
+        let mut set = base.clone();
+        for &kind in ListKind::ALL {
+            set.list_mut(kind).retain(|entry| !self.removes_from(kind, entry));
+        }
+        set.prepositions.retain(|entry| !self.removes(entry));
+        set.connectors_active.retain(|entry| !self.removes(entry));
+        set
+    }
+
+    /// Lists that have entries in `base` but none left once denied entries
+    /// are removed.
+    pub fn emptied(&self, base: &WordSet) -> Vec<ListKind> {
+        ListKind::ALL
+            .iter()
+            .copied()
+            .filter(|&kind| {
+                let list = base.list(kind);
+                !list.is_empty() && list.iter().all(|entry| self.removes_from(kind, entry))
+            })
+            .collect()
+    }
+}
+
+/// Lowercase words of `text`, split at spaces and hyphens.
+fn split(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == '-').filter(|w| !w.is_empty()).map(str::to_lowercase).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rating::ContentRating;
+
+    #[test]
+    fn test_matches_whole_words_and_phrases() {
+        let denylist = Denylist::parse("# competitors\nAcme\n\ntax return\n");
+        assert!(denylist.denies("acme"));
+        assert!(denylist.denies("acme anvil"));
+        assert!(denylist.denies("ACME-approved"));
+        assert!(!denylist.denies("acmeology"));
+        assert!(denylist.denies("tax return"));
+        assert!(!denylist.denies("tax"));
+        assert!(!denylist.denies("return"));
+    }
+
+    #[test]
+    fn test_removes_entries_and_reports_emptied_lists() {
+        let mut base = WordSet::builtin(ContentRating::Workplace).clone();
+        base.conflicts = vec!["versus".into(), "against".into()];
+        let denylist = Denylist::new(["walrus", "versus", "against"]);
+        let set = denylist.apply(&base);
+        assert!(!set.animals.contains(&"walrus".into()));
+        assert_eq!(set.animals.len(), base.animals.len() - 1);
+        assert_eq!(denylist.emptied(&base), vec![ListKind::Conflict]);
+    }
+
+    #[test]
+    fn test_removes_words_whose_counted_forms_are_denied() {
+        let base = WordSet::builtin(ContentRating::Workplace);
+        let set = std::sync::Arc::new(Denylist::new(["wizards", "juggle"]).apply(base));
+        assert!(!set.professions.contains(&"wizard".into()));
+        assert!(!set.present_verbs.contains(&"juggles".into()));
+        for counted in [crate::plural::counted(&set), crate::plural::amounts(&set)] {
+            for list in [&counted.animals, &counted.professions, &counted.nouns, &counted.present_verbs] {
+                assert!(!list.iter().any(|word| ["wizards", "juggle"].contains(&&**word)));
+            }
+        }
+    }
+}
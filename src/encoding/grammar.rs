@@ -0,0 +1,234 @@
+//! The sentence forms the encoding writes.
+//!
+//! These are copies, not the live templates of [`crate::templates`]: eight of
+//! the regular templates as they were when encoding was introduced, with the
+//! order of every choice, the list each one draws from, the prepositions
+//! verbs take and the words that take "an" all written out here. The live
+//! templates are free to change; these must not, or keys stop decoding.
+//! Never edit a form here; add a version.
+
+use super::lists::*;
+
+/// Where a sentence's choices come from.
+pub(super) trait Choices {
+    /// Choose one of `n` options.
+    fn choose(&mut self, n: usize) -> usize;
+}
+
+/// Joins the word before it with a comma: "somewhere, a walrus".
+const COMMA: &str = ",";
+
+/// The years a "since" sentence can name start here.
+const FIRST_YEAR: usize = 1950;
+
+/// Number of years a "since" sentence chooses among.
+const YEARS: usize = 81;
+
+/// A sentence form.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum Form {
+    /// "The grumpy walrus juggles pickles."
+    Declarative,
+    /// "Never trust a caffeinated accountant."
+    Imperative,
+    /// "Why does the walrus overthink?"
+    Question,
+    /// "Do not provoke the sleepy wizard."
+    Warning,
+    /// "Three wizard juggles pickles."
+    Fragment,
+    /// "Penguin versus bureaucracy."
+    Versus,
+    /// "Desperately hoarding pickles since 1987."
+    Since,
+    /// "Somewhere, a penguin is plotting."
+    Somewhere,
+}
+
+/// The words of a sentence, before articles are fixed and commas joined.
+pub(super) struct Written {
+    /// Each word or phrase, in order.
+    pub words: Vec<String>,
+    /// For each choice taken, how many words had been written before it.
+    pub marks: Vec<usize>,
+}
+
+impl Form {
+    /// Every form, in the order its index is encoded.
+    pub(super) const ALL: [Form; 8] = [
+        Form::Declarative,
+        Form::Imperative,
+        Form::Question,
+        Form::Warning,
+        Form::Fragment,
+        Form::Versus,
+        Form::Since,
+        Form::Somewhere,
+    ];
+
+    /// Write a sentence of this form with its choices taken from `choices`.
+    pub(super) fn write(self, choices: &mut impl Choices) -> Written {
+        let mut w = Writer { choices, words: Vec::new(), marks: Vec::new() };
+        match self {
+            Form::Declarative => {
+                w.pick(ARTICLES);
+                w.optional(ADJECTIVES);
+                w.subject();
+                let verb = w.pick(PRESENT_VERBS);
+                w.preposition(verb);
+                w.object();
+            }
+            Form::Imperative => {
+                let imperative = w.pick(IMPERATIVES);
+                if !imperative.split_whitespace().last().is_some_and(|last| ARTICLES.contains(&last)) {
+                    w.pick(ARTICLES);
+                }
+                w.optional(ADJECTIVES);
+                w.subject();
+            }
+            Form::Question => {
+                w.pick(QUESTION_STARTERS);
+                w.pick(ARTICLES);
+                w.optional(ADJECTIVES);
+                w.subject();
+                w.pick(BASE_VERBS);
+            }
+            Form::Warning => {
+                w.pick(WARNINGS);
+                w.pick(BASE_VERBS);
+                w.pick(ARTICLES);
+                w.optional(ADJECTIVES);
+                w.subject();
+            }
+            Form::Fragment => {
+                let list = if w.coin() { NUMBERS } else { ADVERBS };
+                w.pick(list);
+                w.optional(ADJECTIVES);
+                w.subject();
+                let verb = w.pick(PRESENT_VERBS);
+                w.preposition(verb);
+                w.object();
+            }
+            Form::Versus => {
+                w.optional(ADJECTIVES);
+                w.subject();
+                w.pick(CONFLICTS);
+                w.optional(ADJECTIVES);
+                if w.coin() {
+                    w.pick(NOUNS);
+                } else {
+                    w.subject();
+                }
+            }
+            Form::Since => {
+                w.optional(ADVERBS);
+                let gerund = w.pick(GERUNDS);
+                w.preposition(gerund);
+                w.object();
+                if w.coin() {
+                    w.push("since");
+                    let year = FIRST_YEAR + w.choose(YEARS);
+                    w.push(&year.to_string());
+                } else {
+                    w.pick(TIME_MARKERS);
+                }
+            }
+            Form::Somewhere => {
+                w.pick(LOCATIONS);
+                w.push(COMMA);
+                w.pick(ARTICLES);
+                w.optional(ADJECTIVES);
+                w.subject();
+                w.pick(CONNECTORS_ACTIVE);
+                w.pick(GERUNDS);
+            }
+        }
+        Written { words: w.words, marks: w.marks }
+    }
+
+    /// The sentence `words` make in this form, with "a" or "an" to suit the
+    /// next word, commas joined and the closing punctuation, but not yet
+    /// capitalized.
+    pub(super) fn render(self, words: &[String]) -> String {
+        let mut text = String::new();
+        for (i, word) in words.iter().enumerate() {
+            if word == COMMA {
+                text.push_str(COMMA);
+                continue;
+            }
+            if i > 0 {
+                text.push(' ');
+            }
+            match words.get(i + 1) {
+                Some(next) if word == "a" && takes_an(next) => text.push_str("an"),
+                _ => text.push_str(word),
+            }
+        }
+        text.push(if self == Form::Question { '?' } else { '.' });
+        text
+    }
+}
+
+/// Whether `word` takes "an": it starts with a vowel, unless it's one of the
+/// listed words that sound like they start with "you".
+fn takes_an(word: &str) -> bool {
+    word.starts_with(['a', 'e', 'i', 'o', 'u']) && !YOU_SOUND.contains(&word)
+}
+
+/// Takes a sentence's choices and records where each was made.
+struct Writer<'c, C: Choices> {
+    choices: &'c mut C,
+    words: Vec<String>,
+    marks: Vec<usize>,
+}
+
+impl<C: Choices> Writer<'_, C> {
+    fn choose(&mut self, n: usize) -> usize {
+        self.marks.push(self.words.len());
+        self.choices.choose(n)
+    }
+
+    fn coin(&mut self) -> bool {
+        self.choose(2) == 1
+    }
+
+    fn push(&mut self, word: &str) {
+        self.words.push(word.to_string());
+    }
+
+    fn pick(&mut self, list: &[&'static str]) -> &'static str {
+        let word = list[self.choose(list.len())];
+        self.push(word);
+        word
+    }
+
+    fn optional(&mut self, list: &[&'static str]) {
+        if self.coin() {
+            self.pick(list);
+        }
+    }
+
+    /// An animal, profession or noun. Only the first two are ever encoded, as
+    /// a choice among three reads a single bit.
+    fn subject(&mut self) {
+        let list = match self.choose(3) {
+            0 => ANIMALS,
+            1 => PROFESSIONS,
+            _ => NOUNS,
+        };
+        self.pick(list);
+    }
+
+    /// An object or a noun.
+    fn object(&mut self) {
+        let list = if self.coin() { OBJECTS } else { NOUNS };
+        self.pick(list);
+    }
+
+    /// The preposition `verb` needs before its object, if any.
+    fn preposition(&mut self, verb: &str) {
+        if let Some(&(_, preposition)) = VERB_PREPOSITIONS.iter().find(|&&(v, _)| v == verb) {
+            self.push(preposition);
+        }
+    }
+}
@@ -0,0 +1,404 @@
+//! The word lists the encoding reads and writes.
+//!
+//! These are copies, not the live lists of [`crate::words`]: a key has to
+//! decode with the exact entries it was encoded with, long after the live
+//! lists have moved on. Only the first power-of-two entries of a list can be
+//! encoded, so only those are kept. Never edit a list here; add a version.
+
+pub(super) const ADJECTIVES: &[&str] = &[
+    "suspicious", "grumpy", "anxious", "paranoid", "smug", "indignant",
+    "baffled", "bewildered", "exasperated", "melancholy", "euphoric",
+    "mortified", "scandalized", "apoplectic", "despondent", "elated",
+    "flabbergasted", "gobsmacked", "horrified", "incensed", "jubilant", "livid",
+    "nostalgic", "overwhelmed", "perturbed", "resentful", "traumatized",
+    "unimpressed", "vengeful", "wistful", "zealous", "wobbly", "lumpy",
+    "crunchy", "squishy", "gangly", "lanky", "rotund", "scrawny", "spindly",
+    "bulbous", "wrinkly", "jiggly", "prickly", "rubbery", "gelatinous",
+    "bristly", "stubby", "gangrenous", "lopsided", "crooked", "bulging",
+    "deflated", "swollen", "shriveled", "pretentious", "snooty", "earnest",
+    "cynical", "naive", "pompous", "haughty", "condescending", "insufferable",
+    "holier-than-thou", "smirking", "pedantic", "contrarian", "sanctimonious",
+    "obsequious", "aloof", "dismissive", "judgmental", "self-righteous",
+    "melodramatic", "theatrical", "caffeinated", "decaffeinated", "overworked",
+    "haunted", "cursed", "hexed", "enchanted", "possessed", "hungover",
+    "sunburned", "frostbitten", "electrified", "magnetized", "radioactive",
+    "contaminated", "pickled", "marinated", "fossilized", "petrified",
+    "mummified", "carbonated", "interdimensional", "sentient", "rogue", "feral",
+    "domesticated", "telepathic", "omniscient", "spectral", "ethereal",
+    "eldritch", "cosmic", "quantum", "antimatter", "holographic", "pixelated",
+    "glitched", "corrupted", "bootleg", "counterfeit", "unlicensed", "unhinged",
+    "chaotic", "legendary", "mythical", "forbidden", "unauthorized",
+    "contraband", "classified", "redacted", "dubious", "questionable",
+    "sketchy", "shady", "sus", "vintage", "antique", "artisanal", "organic",
+    "free-range", "grass-fed", "locally-sourced", "whimsical", "peculiar",
+    "eccentric", "flamboyant", "dapper", "mischievous", "mysterious", "sparkly",
+    "fuzzy", "fluffy", "sleepy", "cranky", "majestic", "elegant", "startled",
+    "hypnotic", "ridiculous", "philosophical", "turbulent", "sneaky",
+    "confused", "enthusiastic", "dramatic", "optimistic", "sarcastic", "arcane",
+    "celestial", "infernal", "abyssal", "seraphic", "demonic", "draconic",
+    "fey", "sylvan", "vampiric", "lycanthropic", "necromantic", "runic",
+    "astral", "primordial", "elven", "orcish", "dwarven", "goblin", "chimeric",
+    "phantasmal", "wyrd", "otherworldly", "faerie", "basilisk", "cybernetic",
+    "biomechanical", "nanoscale", "plasma-infused", "gravitonic", "hyperspace",
+    "subspace", "transdimensional", "warp-capable", "cryogenic", "bionic",
+    "synthetic", "xenomorphic", "chrono-shifted", "neural-linked",
+    "fusion-powered", "photonic", "tachyonic", "void-touched", "zero-gravity",
+    "doomed", "forsaken", "exiled", "tormented", "damned", "fallen", "banished",
+    "shattered", "sundered", "unraveled", "condemned", "blighted", "ravaged",
+    "bereft", "forlorn", "desolate", "stricken", "afflicted", "beleaguered",
+    "imperiled", "crystalline", "phosphorescent", "iridescent", "opalescent",
+    "translucent", "obsidian", "mercurial", "vitreous", "gossamer", "nebulous",
+    "incandescent", "luminescent", "bioluminescent", "prismatic", "chromatic",
+    "molten", "viscous", "vaporous", "calcified", "vitrified", "wretched",
+    "accursed", "hallowed", "benighted", "besmirched", "betrothed", "bewitched",
+    "ensorcelled",
+];
+
+pub(super) const ANIMALS: &[&str] = &[
+    "axolotl", "tardigrade", "blobfish", "pangolin", "aardvark", "hagfish",
+    "nudibranch", "tarsier", "ayeaye", "hoatzin", "kakapo", "proboscis",
+    "glassfrog", "geoduck", "mudskipper", "sunfish", "oarfish", "lamprey",
+    "lumpfish", "frogfish", "goose", "pigeon", "seagull", "squirrel",
+    "chipmunk", "possum", "skunk", "badger", "beaver", "groundhog", "porcupine",
+    "pelican", "penguin", "puffin", "toucan", "raccoon", "opossum", "woodchuck",
+    "gopher", "marmot", "capybara", "quokka", "wombat", "platypus", "koala",
+    "kangaroo", "wallaby", "lemur", "sloth", "tapir", "okapi", "binturong",
+    "fossa", "kinkajou", "coati", "tenrec", "solenodon", "dhole", "babirusa",
+    "gerenuk", "narwhal", "shoebill", "cassowary", "echidna", "gharial",
+    "tuatara", "saiga", "markhor", "dikdik", "numbat", "bilby", "quoll",
+    "colugo", "olinguito", "uakari", "muntjac", "chevrotain", "bushbaby",
+    "loris", "potoo", "octopus", "squid", "cuttlefish", "nautilus", "jellyfish",
+    "seahorse", "pufferfish", "anglerfish", "lobster", "mantisshrimp",
+    "manatee", "dugong", "walrus", "orca", "dolphin", "porpoise", "seal",
+    "otter", "crab", "shrimp", "starfish", "urchin", "clam", "oyster",
+    "abalone", "flamingo", "peacock", "ostrich", "emu", "kiwi", "owl", "raven",
+    "crow", "magpie", "parrot", "cockatoo", "macaw", "falcon", "hawk", "eagle",
+    "vulture", "condor", "stork", "heron", "ibis", "albatross", "kingfisher",
+    "woodpecker", "hummingbird", "nightjar", "moose", "elk", "caribou", "bison",
+    "yak", "llama", "alpaca", "camel", "hippo", "rhino", "elephant", "giraffe",
+    "zebra", "hyena", "jackal", "dingo", "coyote", "wolf", "fox", "wolverine",
+    "meerkat", "mongoose", "weasel", "ferret", "stoat", "hedgehog", "mole",
+    "shrew", "vole", "dormouse", "chinchilla", "hamster", "gerbil", "panda",
+    "bear", "anteater", "armadillo", "corgi", "dachshund", "pug", "iguana",
+    "gecko", "chameleon", "komodo", "crocodile", "alligator", "tortoise",
+    "python", "cobra", "viper", "salamander", "newt", "toad", "treefrog",
+    "caecilian", "mantis", "beetle", "weevil", "cicada", "katydid", "firefly",
+    "dragonfly", "butterfly", "moth", "bumblebee", "scorpion", "tarantula",
+    "centipede", "millipede", "cricket", "gorilla", "chimp", "orangutan",
+    "gibbon", "mandrill", "baboon", "macaque", "tamarin", "marmoset",
+    "capuchin", "dragon", "phoenix", "griffin", "basilisk", "hydra", "kraken",
+    "chimera", "minotaur", "centaur", "unicorn", "pegasus", "wyvern", "wyrm",
+    "leviathan", "behemoth", "cerberus", "manticore", "sphinx", "gorgon",
+    "harpy", "siren", "mermaid", "cyclops", "banshee", "lamia", "ghoul",
+    "golem", "gargoyle", "djinn", "ifrit", "bigfoot", "sasquatch", "mothman",
+    "chupacabra", "yeti", "nessie", "jackalope", "bunyip", "mokele",
+    "skunk-ape", "jersey-devil", "wendigo", "skinwalker", "dogman", "fresno",
+    "goblin",
+];
+
+pub(super) const PROFESSIONS: &[&str] = &[
+    "accountant", "auditor", "bureaucrat", "actuary", "analyst", "consultant",
+    "manager", "executive", "administrator", "coordinator", "inspector",
+    "regulator", "compliance", "treasurer", "secretary", "receptionist",
+    "clerk", "paralegal", "notary", "registrar", "blacksmith", "alchemist",
+    "scribe", "herald", "apothecary", "cooper", "chandler", "tanner",
+    "fletcher", "wheelwright", "farrier", "cobbler", "milliner", "haberdasher",
+    "cordwainer", "ostler", "lamplighter", "crier", "jester", "minstrel",
+    "cryptographer", "sommelier", "taxidermist", "underwriter",
+    "epidemiologist", "entomologist", "etymologist", "oenologist",
+    "ornithologist", "mycologist", "archivist", "curator", "conservator",
+    "docent", "genealogist", "futurist", "ethicist", "ombudsman", "mediator",
+    "arbitrator", "wizard", "bard", "ranger", "paladin", "druid", "warlock",
+    "sorcerer", "necromancer", "enchanter", "conjurer", "thaumaturge",
+    "artificer", "illusionist", "diviner", "shaman", "oracle", "seer",
+    "prophet", "sage", "mystic", "plumber", "electrician", "mechanic", "welder",
+    "carpenter", "mason", "roofer", "glazier", "plasterer", "tiler",
+    "locksmith", "machinist", "millwright", "rigger", "ironworker",
+    "longshoreman", "stevedore", "trucker", "forklift", "crane", "lawyer",
+    "doctor", "engineer", "architect", "professor", "scientist", "researcher",
+    "economist", "statistician", "mathematician", "physicist", "chemist",
+    "biologist", "geologist", "meteorologist", "astronomer", "linguist",
+    "anthropologist", "sociologist", "psychologist", "sculptor", "painter",
+    "composer", "playwright", "novelist", "poet", "illustrator", "animator",
+    "choreographer", "conductor", "puppeteer", "ventriloquist", "mime",
+    "acrobat", "juggler", "calligrapher", "ceramicist", "glassblower",
+    "woodcarver", "weaver", "barista", "bartender", "cellarmaster", "concierge",
+    "butler", "valet", "chauffeur", "doorman", "bellhop", "maitre", "chef",
+    "baker", "butcher", "fishmonger", "cheesemonger", "florist", "jeweler",
+    "watchmaker", "optician", "tailor", "pirate", "ninja", "viking",
+    "gladiator", "mercenary", "bounty", "smuggler", "spy", "saboteur",
+    "assassin", "explorer", "mountaineer", "diver", "astronaut", "stuntman",
+    "detective", "investigator", "warden", "sheriff", "marshal", "librarian",
+    "indexer", "cartographer", "navigator", "surveyor", "beekeeper",
+    "zookeeper", "falconer", "gamekeeper", "forester", "gravedigger",
+    "undertaker", "embalmer", "coroner", "mortician", "hypnotist", "exorcist",
+    "medium", "clairvoyant", "mentalist", "soothsayer", "martyr", "heretic",
+    "inquisitor", "crusader", "pilgrim", "monk", "nun", "cardinal", "pope",
+    "archbishop", "abbot", "abbess", "deacon", "acolyte", "confessor", "zealot",
+    "apostle", "disciple", "ascetic", "spacewalker", "cosmonaut",
+    "xenobiologist", "starship-captain", "space-pirate", "asteroid-miner",
+    "terraformer", "starchart-navigator", "exoplanetologist", "cyborg",
+    "android", "replicant", "time-traveler", "dimension-hopper",
+    "warp-engineer", "alien-diplomat", "galactic-smuggler", "clone-wrangler",
+    "nanomancer", "biotech-surgeon", "squire", "page", "armorer", "executioner",
+    "torturer", "dungeon-master", "drawbridge-operator", "moat-keeper",
+    "castle-jester", "dragon-tamer", "gargoyle-whisperer", "siege-engineer",
+    "court-poisoner", "royal-taster", "jousting-champion", "trebuchet-operator",
+];
+
+pub(super) const NOUNS: &[&str] = &[
+    "entropy", "chaos", "bureaucracy", "democracy", "capitalism", "socialism",
+    "anarchy", "hierarchy", "monarchy", "tyranny", "liberty", "equality",
+    "justice", "karma", "fate", "destiny", "irony", "sarcasm", "hypocrisy",
+    "empathy", "nostalgia", "existentialism", "paranoia", "anxiety", "euphoria",
+    "melancholy", "ennui", "apathy", "angst", "serenity", "despair", "bliss",
+    "dread", "confusion", "bewilderment", "frustration", "contentment",
+    "regret", "hope", "anticipation", "gravity", "wifi", "algorithms",
+    "physics", "mathematics", "economics", "psychology", "sociology",
+    "philosophy", "logic", "rhetoric", "grammar", "syntax", "semantics",
+    "pragmatics", "thermodynamics", "relativity", "quantum", "chemistry",
+    "biology", "shenanigans", "tomfoolery", "kerfuffle", "hullabaloo",
+    "brouhaha", "rigmarole", "bamboozlement", "quandary", "skulduggery",
+    "hijinks", "malarkey", "gobbledygook", "flimflam", "balderdash",
+    "poppycock", "codswallop", "hokum", "bunkum", "twaddle", "drivel", "humbug",
+    "hogwash", "claptrap", "buffoonery", "absurdity", "foolishness", "lunacy",
+    "madness", "pandemonium", "mayhem", "paradox", "singularity", "hypothesis",
+    "theorem", "axiom", "postulate", "corollary", "conjecture", "theory",
+    "phenomenon", "anomaly", "spectrum", "equilibrium", "momentum", "inertia",
+    "velocity", "acceleration", "amplitude", "frequency", "wavelength",
+    "magnitude", "dimension", "continuum", "infinity", "recursion",
+    "conspiracy", "prophecy", "legacy", "mystery", "history", "memory",
+    "ambiguity", "complexity", "simplicity", "symmetry", "asymmetry", "harmony",
+    "discord",
+];
+
+pub(super) const OBJECTS: &[&str] = &[
+    "staplers", "paperclips", "spreadsheets", "receipts", "invoices", "binders",
+    "highlighters", "sticky notes", "whiteboards", "calendars", "folders",
+    "envelopes", "tape dispensers", "hole punchers", "scissors", "calculators",
+    "notepads", "clipboards", "lanyards", "nameplates", "pickles", "waffles",
+    "tacos", "spaghetti", "bagels", "pretzels", "croissants", "burritos",
+    "dumplings", "pancakes", "sandwiches", "nachos", "muffins", "cupcakes",
+    "donuts", "biscuits", "churros", "empanadas", "pierogis", "noodles",
+    "lasagna", "ravioli", "gnocchi", "risotto", "quesadillas", "socks",
+    "spatulas", "toasters", "curtains", "pillows", "blankets", "towels",
+    "hangers", "coasters", "placemats", "candles", "vases", "lampshades",
+    "doorstops", "bookends", "umbrellas", "slippers", "bathrobes", "potholders",
+    "trivets", "laundry", "dishes", "groceries", "furniture", "appliances",
+    "rubber ducks", "kazoos", "suspenders", "monocles", "bowties", "top hats",
+    "capes", "cufflinks", "pocket watches", "magnifying glasses", "snow globes",
+    "lava lamps", "bobbleheads", "stress balls", "fidget spinners",
+    "whoopee cushions", "fake mustaches", "googly eyes", "pinwheels",
+    "kaleidoscopes", "yard gnomes", "flamingo statues", "trophies",
+    "participation awards", "novelty mugs", "keyboards", "cables", "dongles",
+    "batteries", "chargers", "headphones", "webcams", "routers", "flash drives",
+    "hard drives", "monitors", "speakers", "microphones", "adapters", "remotes",
+    "tablets", "smartwatches", "earbuds", "power banks", "surge protectors",
+    "modems", "printers", "scanners", "fax machines", "server racks",
+    "accordions", "tambourines", "bagpipes", "banjos", "ukuleles", "harmonicas",
+    "maracas", "triangles",
+];
+
+pub(super) const ADVERBS: &[&str] = &[
+    "aggressively", "boldly", "confidently", "defiantly", "fiercely",
+    "triumphantly", "majestically", "pompously", "arrogantly", "smugly",
+    "tentatively", "reluctantly", "hesitantly", "nervously", "timidly",
+    "sheepishly", "meekly", "gingerly", "cautiously", "warily", "gleefully",
+    "furiously", "mournfully", "bitterly", "joyfully", "tearfully", "angrily",
+    "happily", "sadly", "longingly", "ominously", "mysteriously",
+    "dramatically", "theatrically", "cryptically", "hauntingly", "eerily",
+    "menacingly", "forebodingly", "darkly", "chaotically", "frantically",
+    "wildly", "recklessly", "maniacally", "hysterically", "feverishly",
+    "desperately", "madly", "violently", "calmly", "serenely", "peacefully",
+    "stoically", "methodically", "deliberately", "patiently", "gracefully",
+    "elegantly", "smoothly", "absurdly", "ridiculously", "comically",
+    "hilariously",
+];
+
+pub(super) const PRESENT_VERBS: &[&str] = &[
+    "juggles", "throws", "builds", "destroys", "launches", "catches", "climbs",
+    "crawls", "dances", "dives", "dodges", "drags", "drops", "flings", "grabs",
+    "hammers", "hurls", "jumps", "kicks", "leaps", "lifts", "punches", "pushes",
+    "rolls", "runs", "shakes", "shoves", "slides", "spins", "stomps", "swings",
+    "tosses", "wrestles", "yanks", "zooms", "contemplates", "questions",
+    "analyzes", "doubts", "ponders", "calculates", "considers", "daydreams",
+    "imagines", "memorizes", "philosophizes", "reasons", "reflects",
+    "speculates", "theorizes", "overthinks", "wonders", "broods", "deliberates",
+    "evaluates", "bamboozles", "discombobulates", "flabbergasts",
+    "hornswoggles", "befuddles", "baffles", "bewilders", "boggles", "confounds",
+    "dumbfounds", "flummoxes", "gobsmacks", "mystifies", "perplexes",
+    "stupefies", "waddles", "wobbles", "yodels", "snorkels", "gallivants",
+    "canoodles", "lollygags", "moseys", "skedaddles", "vamooses", "smuggles",
+    "hoards", "schemes", "plots", "lurks", "connives", "conspires",
+    "eavesdrops", "pilfers", "prowls", "snoops", "stalks", "sneaks", "tiptoes",
+    "whispers", "embezzles", "forages", "scavenges", "sleuths", "spies",
+    "audits", "reviews", "investigates", "catalogs", "documents",
+    "administrates", "alphabetizes", "annotates", "appraises", "archives",
+    "assesses", "certifies", "classifies", "compiles", "coordinates",
+    "delegates", "drafts", "facilitates", "forecasts", "implements", "indexes",
+    "inspects", "inventories", "legislates", "notarizes", "broadcasts",
+    "negotiates", "debates",
+];
+
+pub(super) const BASE_VERBS: &[&str] = &[
+    "juggle", "throw", "build", "destroy", "launch", "catch", "climb", "crawl",
+    "dance", "dive", "dodge", "drag", "drop", "fling", "grab", "hammer", "hurl",
+    "jump", "kick", "leap", "lift", "punch", "push", "roll", "run", "shake",
+    "shove", "slide", "spin", "stomp", "swing", "toss", "wrestle", "yank",
+    "zoom", "contemplate", "question", "analyze", "doubt", "ponder",
+    "calculate", "consider", "daydream", "imagine", "memorize", "philosophize",
+    "reason", "reflect", "speculate", "theorize", "overthink", "wonder",
+    "brood", "deliberate", "evaluate", "bamboozle", "discombobulate",
+    "flabbergast", "hornswoggle", "befuddle", "baffle", "bewilder", "boggle",
+    "confound", "dumbfound", "flummox", "gobsmack", "mystify", "perplex",
+    "stupefy", "waddle", "wobble", "yodel", "snorkel", "gallivant", "canoodle",
+    "lollygag", "mosey", "skedaddle", "vamoose", "smuggle", "hoard", "scheme",
+    "plot", "lurk", "connive", "conspire", "eavesdrop", "pilfer", "prowl",
+    "snoop", "stalk", "sneak", "tiptoe", "whisper", "embezzle", "forage",
+    "scavenge", "sleuth", "spy", "audit", "review", "investigate", "catalog",
+    "document", "administrate", "alphabetize", "annotate", "appraise",
+    "archive", "assess", "certify", "classify", "compile", "coordinate",
+    "delegate", "draft", "facilitate", "forecast", "implement", "index",
+    "inspect", "inventory", "legislate", "notarize", "broadcast", "negotiate",
+    "debate",
+];
+
+pub(super) const GERUNDS: &[&str] = &[
+    "juggling", "throwing", "building", "destroying", "launching", "catching",
+    "climbing", "crawling", "dancing", "diving", "dodging", "dragging",
+    "dropping", "flinging", "grabbing", "hammering", "hurling", "jumping",
+    "kicking", "leaping", "lifting", "punching", "pushing", "rolling",
+    "running", "shaking", "shoving", "sliding", "spinning", "stomping",
+    "swinging", "tossing", "wrestling", "yanking", "zooming", "contemplating",
+    "questioning", "analyzing", "doubting", "pondering", "calculating",
+    "considering", "daydreaming", "imagining", "memorizing", "philosophizing",
+    "reasoning", "reflecting", "speculating", "theorizing", "overthinking",
+    "wondering", "brooding", "deliberating", "evaluating", "bamboozling",
+    "discombobulating", "flabbergasting", "hornswoggling", "befuddling",
+    "baffling", "bewildering", "boggling", "confounding", "dumbfounding",
+    "flummoxing", "gobsmacking", "mystifying", "perplexing", "stupefying",
+    "waddling", "wobbling", "yodeling", "snorkeling", "gallivanting",
+    "canoodling", "lollygagging", "moseying", "skedaddling", "vamoosing",
+    "smuggling", "hoarding", "scheming", "plotting", "lurking", "conniving",
+    "conspiring", "eavesdropping", "pilfering", "prowling", "snooping",
+    "stalking", "sneaking", "tiptoeing", "whispering", "embezzling", "foraging",
+    "scavenging", "sleuthing", "spying", "auditing", "reviewing",
+    "investigating", "cataloging", "documenting", "administrating",
+    "alphabetizing", "annotating", "appraising", "archiving", "assessing",
+    "certifying", "classifying", "compiling", "coordinating", "delegating",
+    "drafting", "facilitating", "forecasting", "implementing", "indexing",
+    "inspecting", "inventorying", "legislating", "notarizing", "broadcasting",
+    "negotiating", "debating",
+];
+
+pub(super) const IMPERATIVES: &[&str] = &[
+    "never trust", "always fear", "beware of", "never underestimate",
+    "always question", "do not provoke", "respect the", "fear the", "avoid the",
+    "never ignore", "always suspect", "watch out for", "do not underestimate",
+    "never befriend", "always monitor", "stay away from", "keep distance from",
+    "never anger", "bow before", "surrender to", "obey the", "question the",
+    "challenge the", "embrace the", "reject the", "honor the", "distrust the",
+    "never mock", "always appease", "worship the", "run from", "hide from",
+];
+
+pub(super) const ARTICLES: &[&str] = &[
+    "the", "a",
+];
+
+pub(super) const QUESTION_STARTERS: &[&str] = &[
+    "why does", "why would", "how does", "how could", "when will", "where did",
+    "what makes", "who let", "who authorized", "when did", "where would",
+    "what if", "why must", "how dare", "who decided", "what possesses",
+    "why can't", "how might", "where should", "when might", "who permitted",
+    "what drives", "why won't", "how should", "where might", "when could",
+    "who allows", "what compels", "why shouldn't", "how would", "where has",
+    "when should",
+];
+
+pub(super) const WARNINGS: &[&str] = &[
+    "do not", "never", "avoid", "beware", "stop", "cease", "halt", "prevent",
+    "resist", "refuse to", "abandon", "forget", "forsake", "reject", "ignore",
+    "shun", "evade", "escape", "flee", "abstain from", "renounce",
+    "discontinue", "terminate", "end", "quit", "forgo", "relinquish",
+    "surrender", "eliminate", "abolish", "banish", "prohibit",
+];
+
+pub(super) const CONFLICTS: &[&str] = &[
+    "versus", "against", "defeats", "fears", "challenges", "opposes", "resists",
+    "confronts", "battles", "outsmarts", "humiliates", "destroys",
+    "annihilates", "conquers", "vanquishes", "overwhelms", "dominates",
+    "undermines", "sabotages", "thwarts", "outmaneuvers", "outwits",
+    "confounds", "baffles", "perplexes", "confuses", "bewilders", "mystifies",
+    "flummoxes", "stumps", "rivals", "combats",
+];
+
+pub(super) const LOCATIONS: &[&str] = &[
+    "somewhere", "out there", "in the void", "beyond logic", "in accounting",
+    "at midnight", "underground", "inevitably", "allegedly", "reportedly",
+    "supposedly", "apparently", "in the shadows", "behind closed doors",
+    "in the basement", "in the attic", "offshore", "overseas",
+    "in the distance", "at headquarters", "in the warehouse", "at the border",
+    "in stealth mode", "under surveillance", "off the grid", "in plain sight",
+    "beneath the surface", "across dimensions", "in another timeline",
+    "at the edge of reason", "in the matrix", "beyond the horizon",
+];
+
+pub(super) const TIME_MARKERS: &[&str] = &[
+    "since forever", "since breakfast", "since the incident", "until dawn",
+    "before coffee", "after midnight", "during chaos", "throughout history",
+    "beyond reason", "without warning", "against protocol",
+    "since the beginning", "until the end", "before sunrise", "after dusk",
+    "during the meeting", "throughout eternity", "beyond comprehension",
+    "without explanation", "against all odds", "since childhood",
+    "until retirement", "before the merger", "after the crash",
+    "during the eclipse", "throughout the crisis", "beyond repair",
+    "without hesitation", "against advice", "since the awakening",
+    "until further notice", "before the deadline",
+];
+
+pub(super) const NUMBERS: &[&str] = &[
+    "three", "seven", "twelve", "forty-two", "exactly nine", "countless",
+    "several", "many", "too many", "precisely six", "almost eight",
+    "roughly eleven", "approximately four", "exactly thirteen", "nearly twenty",
+    "about fifty", "over ninety", "under five", "merely two", "only one",
+    "at least ten", "no more than fifteen", "fewer than thirty",
+    "upwards of forty", "a dozen", "a hundred", "a thousand", "infinite",
+    "zero", "negative five", "pi", "an undisclosed number of",
+];
+
+pub(super) const CONNECTORS_ACTIVE: &[&str] = &[
+    "is", "was", "keeps", "seems", "appears", "remains", "continues", "stays",
+    "began", "has been", "will be", "might be", "could be", "should be",
+    "must be", "would be", "may be", "started", "stopped", "finished",
+    "resumed", "persists in", "insists on", "excels at", "fails at",
+    "struggles with", "thrives on", "survives by", "specializes in",
+    "dabbles in", "experiments with", "contemplates",
+];
+/// Every present tense and gerund of the lists above that needs a
+/// preposition before its object.
+pub(super) const VERB_PREPOSITIONS: &[(&str, &str)] = &[
+    ("climbs", "on"), ("crawls", "over"), ("dances", "with"), ("dives", "into"), ("jumps", "over"),
+    ("leaps", "over"), ("runs", "from"), ("stomps", "on"), ("zooms", "past"), ("daydreams", "about"),
+    ("philosophizes", "about"), ("reflects", "on"), ("speculates", "about"), ("theorizes", "about"),
+    ("wonders", "about"), ("broods", "over"), ("deliberates", "on"), ("waddles", "toward"),
+    ("wobbles", "toward"), ("yodels", "about"), ("snorkels", "near"), ("gallivants", "with"),
+    ("canoodles", "with"), ("lollygags", "around"), ("moseys", "toward"), ("skedaddles", "from"),
+    ("vamooses", "from"), ("schemes", "about"), ("plots", "against"), ("lurks", "near"),
+    ("connives", "with"), ("conspires", "against"), ("eavesdrops", "on"), ("prowls", "around"),
+    ("snoops", "on"), ("sneaks", "past"), ("tiptoes", "around"), ("forages", "for"),
+    ("scavenges", "for"), ("sleuths", "for"), ("spies", "on"),
+    ("climbing", "on"), ("crawling", "over"), ("dancing", "with"), ("diving", "into"), ("jumping", "over"),
+    ("leaping", "over"), ("running", "from"), ("stomping", "on"), ("zooming", "past"),
+    ("daydreaming", "about"), ("philosophizing", "about"), ("reflecting", "on"), ("speculating", "about"),
+    ("theorizing", "about"), ("wondering", "about"), ("brooding", "over"), ("deliberating", "on"),
+    ("waddling", "toward"), ("wobbling", "toward"), ("yodeling", "about"), ("snorkeling", "near"),
+    ("gallivanting", "with"), ("canoodling", "with"), ("lollygagging", "around"), ("moseying", "toward"),
+    ("skedaddling", "from"), ("vamoosing", "from"), ("scheming", "about"), ("plotting", "against"),
+    ("lurking", "near"), ("conniving", "with"), ("conspiring", "against"), ("eavesdropping", "on"),
+    ("prowling", "around"), ("snooping", "on"), ("sneaking", "past"), ("tiptoeing", "around"),
+    ("foraging", "for"), ("scavenging", "for"), ("sleuthing", "for"), ("spying", "on"),
+];
+
+/// Words of the lists above that start with a vowel but the sound of "you",
+/// so take "a" rather than "an".
+pub(super) const YOU_SOUND: &[&str] = &["euphoric", "euphoria", "unicorn"];
@@ -0,0 +1,459 @@
+//! Reversible encoding of arbitrary bytes as funny sentences.
+//!
+//! In the spirit of BIP39, but with whole sentences: the bytes of a recovery
+//! key or TOTP seed are turned into a few grammatical sentences and a closing
+//! checksum, and [`decode`] turns them back into the exact same bytes.
+//!
+//! # Scheme
+//!
+//! The payload is a version byte ([`VERSION`]) and a length byte followed by
+//! the data, read as a bit stream (most significant bit first). Each sentence
+//! takes one of eight forms, frozen copies of regular templates such as
+//! [`Template::Declarative`](crate::templates::Template::Declarative), with
+//! its choices taken from that stream instead of an RNG:
+//!
+//! - a choice among `n` options reads `floor(log2(n))` bits and picks from the
+//!   first power-of-two entries of the list (so eight forms give three bits,
+//!   and a list of 313 adjectives gives eight);
+//! - every optional word or coin flip reads one bit.
+//!
+//! Sentences are added until the stream is exhausted; the last one is padded
+//! with zero bits, which the length byte lets the decoder ignore. A CRC-16 of
+//! the payload picks the closing adjective and animal ("Vengeful walrus!").
+//!
+//! Decoding replays the forms, searching for the choices that reproduce
+//! each sentence word for word, and keeps the readings whose checksum
+//! matches. Text that more than one payload checks out for is rejected as
+//! [`Ambiguous`](EncodingError::Ambiguous) rather than guessed at.
+//!
+//! # Versions
+//!
+//! As with a BIP39 word list, everything a key depends on is frozen: the word
+//! lists and the sentence forms (copies kept in this module, not the live
+//! ones), the prepositions verbs take, the choice of "a" or "an", and the
+//! checksum. Fragments keep a singular subject ("three wizard juggles"),
+//! whatever the generator does today. Changing any of this needs a new
+//! [`VERSION`], and text written with every earlier one must still decode.
+
+mod grammar;
+mod lists;
+
+use std::fmt;
+
+use crate::formatting::capitalize_first;
+
+use grammar::{Choices, Form};
+
+/// Largest payload that fits the one-byte length prefix.
+pub const MAX_BYTES: usize = u8::MAX as usize;
+
+/// The version [`encode`] writes.
+pub const VERSION: u8 = 1;
+
+/// Upper bound on alternative readings kept while decoding.
+const MAX_READINGS: usize = 1024;
+
+/// Why bytes could not be encoded or a text could not be decoded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EncodingError {
+    /// There is no data to encode.
+    Empty,
+    /// The data is longer than [`MAX_BYTES`].
+    TooLong(usize),
+    /// No sentence form reproduces this sentence.
+    UnrecognizedSentence(String),
+    /// The text has no sentences, or no closing checksum.
+    MissingChecksum,
+    /// The sentences hold fewer bits than their length byte announces.
+    Truncated,
+    /// The checksum does not match the decoded data.
+    ChecksumMismatch,
+    /// The text was written by a version of the encoding this one can't read.
+    UnsupportedVersion(u8),
+    /// The sentences can be read as this many different payloads that all
+    /// pass the checksum.
+    Ambiguous(usize),
+}
+
+impl fmt::Display for EncodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodingError::Empty => write!(f, "there are no bytes to encode"),
+            EncodingError::TooLong(len) => {
+                write!(f, "{} bytes is more than the {} that can be encoded", len, MAX_BYTES)
+            }
+            EncodingError::UnrecognizedSentence(sentence) => {
+                write!(f, "no sentence form produces '{}'", sentence)
+            }
+            EncodingError::MissingChecksum => write!(f, "missing checksum"),
+            EncodingError::Truncated => write!(f, "the sentences are missing data"),
+            EncodingError::ChecksumMismatch => write!(f, "checksum does not match; check for typos"),
+            EncodingError::UnsupportedVersion(version) => {
+                write!(f, "written by encoding version {}, but only version {} can be read", version, VERSION)
+            }
+            EncodingError::Ambiguous(readings) => {
+                write!(f, "the sentences read {} different ways that all match the checksum", readings)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncodingError {}
+
+/// Bytes in front of the data: the version byte and the length byte.
+const HEADER: usize = 2;
+
+/// The header followed by the data.
+fn payload(data: &[u8]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(data.len() + HEADER);
+    payload.push(VERSION);
+    payload.push(data.len() as u8);
+    payload.extend_from_slice(data);
+    payload
+}
+
+/// Read the header and the data it announces from a bit string.
+fn read_payload(bits: &[bool]) -> Result<Vec<u8>, EncodingError> {
+    let bytes: Vec<u8> =
+        bits.chunks_exact(8).map(|chunk| chunk.iter().fold(0, |acc, &b| acc << 1 | u8::from(b))).collect();
+    match bytes.first() {
+        Some(&VERSION) => {}
+        Some(&version) => return Err(EncodingError::UnsupportedVersion(version)),
+        None => return Err(EncodingError::Truncated),
+    }
+    let len = usize::from(*bytes.get(HEADER - 1).ok_or(EncodingError::Truncated)?);
+    let end = HEADER + len;
+    if bytes.len() < end {
+        return Err(EncodingError::Truncated);
+    }
+    Ok(bytes[..end].to_vec())
+}
+
+/// The closing checksum words of `payload`, lowercase.
+fn checksum(payload: &[u8]) -> Vec<&'static str> {
+    let [high, low] = crc16(payload).to_be_bytes();
+    vec![lists::ADJECTIVES[usize::from(high)], lists::ANIMALS[usize::from(low)]]
+}
+
+/// Encode `data` as sentences followed by a checksum.
+pub fn encode(data: &[u8]) -> Result<String, EncodingError> {
+    if data.is_empty() {
+        return Err(EncodingError::Empty);
+    }
+    if data.len() > MAX_BYTES {
+        return Err(EncodingError::TooLong(data.len()));
+    }
+    Ok(write(&payload(data)))
+}
+
+/// Write `payload` as sentences and their checksum.
+fn write(payload: &[u8]) -> String {
+    let mut reader = BitReader { bits: to_bits(payload), pos: 0 };
+    let mut sentences = Vec::new();
+
+    loop {
+        let form = Form::ALL[reader.choose(Form::ALL.len())];
+        let written = form.write(&mut reader);
+        sentences.push(capitalize_first(&form.render(&written.words)));
+        if reader.pos >= reader.bits.len() {
+            break;
+        }
+    }
+
+    let checksum = checksum(payload).join(" ");
+    sentences.push(format!("{}!", capitalize_first(&checksum)));
+
+    sentences.join(" ")
+}
+
+/// Decode text produced by [`encode`] back into the original bytes.
+///
+/// Capitalization, quotes and the exact sentence punctuation are ignored.
+pub fn decode(text: &str) -> Result<Vec<u8>, EncodingError> {
+    let mut sentences = split_sentences(text);
+    let checksum = match sentences.pop() {
+        Some(words) if !sentences.is_empty() => words,
+        _ => return Err(EncodingError::MissingChecksum),
+    };
+    // Every way of reading the sentences so far, as bit strings
+    let mut readings: Vec<Vec<bool>> = vec![Vec::new()];
+    for sentence in &sentences {
+        let parses = parse_sentence(sentence);
+        if parses.is_empty() {
+            return Err(EncodingError::UnrecognizedSentence(sentence.join(" ")));
+        }
+        readings = readings
+            .iter()
+            .flat_map(|prefix| parses.iter().map(move |bits| [prefix.as_slice(), bits].concat()))
+            .take(MAX_READINGS)
+            .collect();
+    }
+
+    checked_data(readings, &checksum)
+}
+
+/// The data of the one reading whose payload matches `closing`.
+fn checked_data(readings: Vec<Vec<bool>>, closing: &[String]) -> Result<Vec<u8>, EncodingError> {
+    let mut matches: Vec<Vec<u8>> = Vec::new();
+    let mut error = EncodingError::ChecksumMismatch;
+    for bits in readings {
+        match read_payload(&bits) {
+            Ok(payload) if checksum(&payload) == *closing => {
+                if !matches.contains(&payload) {
+                    matches.push(payload);
+                }
+            }
+            Ok(_) => {}
+            // A reading that names another version says more than one that is cut short
+            Err(e) if !matches!(error, EncodingError::UnsupportedVersion(_)) => error = e,
+            Err(_) => {}
+        }
+    }
+
+    match &matches[..] {
+        [payload] => Ok(payload[HEADER..].to_vec()),
+        [] => Err(error),
+        _ => Err(EncodingError::Ambiguous(matches.len())),
+    }
+}
+
+/// Number of bits a choice among `n` options carries: floor(log2(n)).
+fn usable_bits(n: usize) -> u32 {
+    if n <= 1 {
+        0
+    } else {
+        usize::BITS - 1 - n.leading_zeros()
+    }
+}
+
+fn to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)).collect()
+}
+
+/// CRC-16 (polynomial 0x1021) of the payload.
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0u16, |crc, &byte| {
+        (0..8).fold(crc ^ u16::from(byte) << 8, |crc, _| if crc & 0x8000 != 0 { crc << 1 ^ 0x1021 } else { crc << 1 })
+    })
+}
+
+/// Feeds sentence choices from a bit stream, padding with zeros at the end.
+struct BitReader {
+    bits: Vec<bool>,
+    pos: usize,
+}
+
+impl Choices for BitReader {
+    fn choose(&mut self, n: usize) -> usize {
+        (0..usable_bits(n)).fold(0, |acc, _| {
+            let bit = self.bits.get(self.pos).copied().unwrap_or(false);
+            self.pos += 1;
+            acc << 1 | usize::from(bit)
+        })
+    }
+}
+
+/// Replays a fixed prefix of choices (then zeros), noting how many encodable
+/// options each choice point had.
+struct PrefixSource<'a> {
+    prefix: &'a [usize],
+    options: Vec<usize>,
+}
+
+impl Choices for PrefixSource<'_> {
+    fn choose(&mut self, n: usize) -> usize {
+        let value = self.prefix.get(self.options.len()).copied().unwrap_or(0);
+        self.options.push(1 << usable_bits(n));
+        value
+    }
+}
+
+/// Split text into sentences of normalized (lowercase, unpunctuated) words.
+fn split_sentences(text: &str) -> Vec<Vec<String>> {
+    let mut sentences = Vec::new();
+    let mut current = Vec::new();
+
+    for raw in text.split_whitespace() {
+        let ends_sentence = raw.trim_end_matches('"').ends_with(['.', '?', '!']);
+        let word = raw.trim_matches(|c: char| matches!(c, '"' | ',' | '.' | '?' | '!')).to_lowercase();
+        if !word.is_empty() {
+            current.push(word);
+        }
+        if ends_sentence && !current.is_empty() {
+            sentences.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        sentences.push(current);
+    }
+
+    sentences
+}
+
+/// Written words as normalized tokens (commas dropped, phrases split).
+fn tokens(words: &[String]) -> Vec<String> {
+    words
+        .iter()
+        .flat_map(|w| w.split_whitespace())
+        .filter(|w| *w != ",")
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Whether two tokens match, treating "a" and "an" as the same word.
+fn same_word(written: &str, target: &str) -> bool {
+    written == target || (written == "a" && target == "an")
+}
+
+/// Every bit string that makes some form produce exactly `target`.
+fn parse_sentence(target: &[String]) -> Vec<Vec<bool>> {
+    let form_bits = usable_bits(Form::ALL.len());
+    let mut parses = Vec::new();
+
+    for (index, &form) in Form::ALL.iter().enumerate() {
+        let mut found = Vec::new();
+        search(form, &mut Vec::new(), target, &mut found);
+
+        for (choices, options) in found {
+            let mut bits: Vec<bool> = (0..form_bits).rev().map(|i| index >> i & 1 == 1).collect();
+            for (value, count) in choices.iter().zip(options) {
+                bits.extend((0..usable_bits(count)).rev().map(|i| value >> i & 1 == 1));
+            }
+            parses.push(bits);
+        }
+    }
+
+    parses
+}
+
+/// Depth-first search over choice sequences, pruning as soon as the words a
+/// prefix commits to stop matching the target.
+fn search(form: Form, prefix: &mut Vec<usize>, target: &[String], found: &mut Vec<(Vec<usize>, Vec<usize>)>) {
+    let mut source = PrefixSource { prefix: prefix.as_slice(), options: Vec::new() };
+    let written = form.write(&mut source);
+    let options = source.options;
+
+    // Finished without needing another choice: a parse only if everything matches
+    if options.len() <= prefix.len() {
+        let words = tokens(&written.words);
+        if words.len() == target.len() && words.iter().zip(target).all(|(w, t)| same_word(w, t)) {
+            found.push((prefix.clone(), options));
+        }
+        return;
+    }
+
+    // Words written before the next open choice are fixed by the prefix
+    let committed = tokens(&written.words[..written.marks[prefix.len()]]);
+    if committed.len() > target.len() || !committed.iter().zip(target).all(|(w, t)| same_word(w, t)) {
+        return;
+    }
+
+    for value in 0..options[prefix.len()] {
+        prefix.push(value);
+        search(form, prefix, target, found);
+        prefix.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usable_bits() {
+        assert_eq!(usable_bits(1), 0);
+        assert_eq!(usable_bits(2), 1);
+        assert_eq!(usable_bits(10), 3);
+        assert_eq!(usable_bits(256), 8);
+        assert_eq!(usable_bits(313), 8);
+    }
+
+    #[test]
+    fn test_crc16_known_value() {
+        // CRC-16/XMODEM check value
+        assert_eq!(crc16(b"123456789"), 0x31C3);
+    }
+
+    #[test]
+    fn test_payload_bits_round_trip() {
+        let payload = payload(&[0xde, 0xad, 0xbe, 0xef]);
+        let mut bits = to_bits(&payload);
+        bits.extend([false; 5]);
+        assert_eq!(read_payload(&bits), Ok(payload.clone()));
+        assert_eq!(read_payload(&bits[..payload.len() * 8 - 8]), Err(EncodingError::Truncated));
+        assert_eq!(read_payload(&to_bits(&[3, 0])), Err(EncodingError::UnsupportedVersion(3)));
+    }
+
+    #[test]
+    fn test_round_trip_short_inputs() {
+        for data in [&[0][..], &[0xff], &[1, 2, 3, 4, 5, 6, 7, 8]] {
+            let text = encode(data).unwrap();
+            assert_eq!(decode(&text).unwrap(), data, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_decode_ignores_case_and_quotes() {
+        let text = encode(b"hi").unwrap();
+        let shouted = format!("\"{}\"", text.to_uppercase());
+        assert_eq!(decode(&shouted).unwrap(), b"hi");
+    }
+
+    #[test]
+    fn test_wrong_checksum_is_rejected() {
+        let text = encode(b"key").unwrap();
+        let body = &text[..text.trim_end_matches('!').rfind('.').unwrap() + 1];
+        let expected = checksum(&payload(b"key"));
+        let adjective = if expected[0] == lists::ADJECTIVES[0] { lists::ADJECTIVES[1] } else { lists::ADJECTIVES[0] };
+        let tampered = format!("{} {} {}!", body, capitalize_first(adjective), expected[1]);
+        assert_eq!(decode(&tampered), Err(EncodingError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn test_empty_and_too_long() {
+        assert_eq!(encode(&[]), Err(EncodingError::Empty));
+        assert_eq!(encode(&[0; 256]), Err(EncodingError::TooLong(256)));
+    }
+
+    #[test]
+    fn test_encoding_is_frozen() {
+        assert_eq!(
+            encode(&[0xde, 0xad, 0xbe, 0xef]).unwrap(),
+            "The tuatara slides whoopee cushions. Redacted gamekeeper versus axolotl. Exasperated sasquatch!"
+        );
+        assert_eq!(
+            encode(&[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd, 0xee, 0xff]).unwrap(),
+            "The dikdik juggles bureaucracy. Always question the resentful enchanter. \
+             What drives the hypnotic gamekeeper zoom? Halt connive the forlorn baboon. \
+             Smoothly embezzling cables since forever. Besmirched vulture!"
+        );
+    }
+
+    #[test]
+    fn test_forms_render_their_own_articles_and_commas() {
+        let words = |words: &[&str]| words.iter().map(|w| w.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            Form::Somewhere.render(&words(&["somewhere", ",", "a", "unicorn", "is", "plotting"])),
+            "somewhere, a unicorn is plotting."
+        );
+        assert_eq!(Form::Question.render(&words(&["why does", "a", "orca", "dance"])), "why does an orca dance?");
+    }
+
+    #[test]
+    fn test_ambiguous_readings_are_rejected() {
+        // Two payloads of different lengths with the same CRC-16
+        let short = payload(&[7]);
+        let long =
+            (0..=u16::MAX).map(|data| payload(&data.to_be_bytes())).find(|long| crc16(long) == crc16(&short)).unwrap();
+        let closing: Vec<String> = checksum(&short).into_iter().map(String::from).collect();
+        let readings = vec![to_bits(&short), to_bits(&long), to_bits(&short)];
+        assert_eq!(checked_data(readings, &closing), Err(EncodingError::Ambiguous(2)));
+        assert_eq!(checked_data(vec![to_bits(&short)], &closing), Ok(vec![7]));
+    }
+
+    #[test]
+    fn test_later_versions_are_reported() {
+        let text = write(&[3, 2, 0xbe, 0xef]);
+        assert_eq!(decode(&text), Err(EncodingError::UnsupportedVersion(3)));
+    }
+}
@@ -1,12 +1,20 @@
 //! Entropy estimation for generated passwords.
 //!
 //! This module calculates the approximate entropy (bits of randomness) in passwords
-//! generated by the funny password generator. The entropy accounts for:
+//! generated by the legacy Markov generator. The entropy accounts for:
 //! - The number of possible words in each category
 //! - The branching factor at each transition point (weighted by probability)
+//!
+//! Template-generated passwords carry their exact entropy with them
+//! (see [`GeneratedPassword::entropy`](crate::password::GeneratedPassword::entropy)),
+//! so the estimators here are only needed for strings of unknown origin. A
+//! finished template password can be parsed back into its exact choices with
+//! [`parse_password`](crate::parser::parse_password).
 
+use crate::rating::ContentRating;
 use crate::transitions::{get_transitions, Category, Transition};
 use crate::words::*;
+use crate::wordset::WordSet;
 
 /// Represents the entropy contribution from a single step in password generation.
 #[derive(Debug)]
@@ -16,17 +24,18 @@ pub struct EntropyStep {
     pub transition_entropy: f64,
 }
 
-/// Get the word count for a category, accounting for NSFW mode.
-fn get_category_word_count(category: Category, nsfw: bool) -> usize {
+/// Get the word count for a category, accounting for the content rating.
+fn get_category_word_count(category: Category, rating: ContentRating) -> usize {
+    let words = WordSet::builtin(rating);
     match category {
-        Category::Adjective => get_adjectives(nsfw).len(),
-        Category::Profession => get_professions(nsfw).len(),
-        Category::Noun => get_nouns(nsfw).len(),
-        Category::Object => get_objects(nsfw).len(),
-        Category::Adverb => get_adverbs(nsfw).len(),
-        Category::Gerund => get_gerunds(nsfw).len(),
-        Category::PresentVerb => get_present_verbs(nsfw).len(),
-        // These categories don't have NSFW variants
+        Category::Adjective => words.adjectives.len(),
+        Category::Profession => words.professions.len(),
+        Category::Noun => words.nouns.len(),
+        Category::Object => words.objects.len(),
+        Category::Adverb => words.adverbs.len(),
+        Category::Gerund => words.gerunds.len(),
+        Category::PresentVerb => words.present_verbs.len(),
+        // These categories don't depend on the rating
         _ => category.words().len(),
     }
 }
@@ -37,15 +46,16 @@ fn get_category_word_count(category: Category, nsfw: bool) -> usize {
 /// 1. Word selection entropy: log2(number of words in category) for each word
 /// 2. Transition entropy: weighted entropy of choosing the next category
 pub fn calculate_entropy(categories: &[Category]) -> f64 {
-    calculate_entropy_with_nsfw(categories, false)
+    calculate_entropy_with_rating(categories, ContentRating::Workplace)
 }
 
-/// Calculate the entropy (in bits) for a sequence of categories, with NSFW mode.
+/// Calculate the entropy (in bits) for a sequence of categories, drawing from
+/// the words allowed at `rating`.
 ///
 /// The total entropy is the sum of:
 /// 1. Word selection entropy: log2(number of words in category) for each word
 /// 2. Transition entropy: weighted entropy of choosing the next category
-pub fn calculate_entropy_with_nsfw(categories: &[Category], nsfw: bool) -> f64 {
+pub fn calculate_entropy_with_rating(categories: &[Category], rating: ContentRating) -> f64 {
     let mut total_entropy = 0.0;
     let mut prev_category: Option<Category> = None;
 
@@ -55,8 +65,8 @@ pub fn calculate_entropy_with_nsfw(categories: &[Category], nsfw: bool) -> f64 {
         let transition_entropy = calculate_transition_entropy(transitions, category);
         total_entropy += transition_entropy;
 
-        // Add word selection entropy (accounting for NSFW mode)
-        let word_count = get_category_word_count(category, nsfw);
+        // Add word selection entropy (accounting for the rating)
+        let word_count = get_category_word_count(category, rating);
         if word_count > 0 {
             let word_entropy = (word_count as f64).log2();
             total_entropy += word_entropy;
@@ -160,34 +170,31 @@ pub fn estimate_password_entropy(password: &str) -> f64 {
 ///
 /// Returns the approximate entropy in bits.
 pub fn estimate_entropy_from_words(words: &[String]) -> f64 {
-    estimate_entropy_from_words_with_nsfw(words, false)
+    estimate_entropy_from_words_with_rating(words, ContentRating::Workplace)
 }
 
-/// Estimate the entropy from a vector of words, with NSFW mode.
+/// Estimate the entropy from a vector of words drawn from the lists allowed at
+/// `rating`.
 ///
 /// Returns the approximate entropy in bits.
-pub fn estimate_entropy_from_words_with_nsfw(words: &[String], nsfw: bool) -> f64 {
-    let categories = estimate_categories_from_words_with_nsfw(words, nsfw);
-    calculate_entropy_with_nsfw(&categories, nsfw)
-}
-
-/// Parse a vector of words into categories for entropy estimation.
-fn estimate_categories_from_words(words: &[String]) -> Vec<Category> {
-    estimate_categories_from_words_with_nsfw(words, false)
+pub fn estimate_entropy_from_words_with_rating(words: &[String], rating: ContentRating) -> f64 {
+    let categories = estimate_categories_from_words_with_rating(words, rating);
+    calculate_entropy_with_rating(&categories, rating)
 }
 
-/// Parse a vector of words into categories for entropy estimation, with NSFW mode.
-fn estimate_categories_from_words_with_nsfw(words: &[String], nsfw: bool) -> Vec<Category> {
+/// Parse a vector of words into categories for entropy estimation, at a content rating.
+fn estimate_categories_from_words_with_rating(words: &[String], rating: ContentRating) -> Vec<Category> {
     let mut categories = Vec::new();
 
-    // Get word lists (with NSFW if enabled)
-    let adjectives = get_adjectives(nsfw);
-    let professions = get_professions(nsfw);
-    let nouns = get_nouns(nsfw);
-    let gerunds = get_gerunds(nsfw);
-    let present_verbs = get_present_verbs(nsfw);
-    let objects = get_objects(nsfw);
-    let adverbs = get_adverbs(nsfw);
+    // Get the word lists allowed at this rating
+    let set = WordSet::builtin(rating);
+    let adjectives = &set.adjectives;
+    let professions = &set.professions;
+    let nouns = &set.nouns;
+    let gerunds = &set.gerunds;
+    let present_verbs = &set.present_verbs;
+    let objects = &set.objects;
+    let adverbs = &set.adverbs;
 
     for word in words {
         let word_str = word.as_str();
@@ -196,21 +203,21 @@ fn estimate_categories_from_words_with_nsfw(words: &[String], nsfw: bool) -> Vec
 
         let category = if ARTICLES.contains(&normalized) {
             Category::Article
-        } else if adjectives.contains(&word_str) {
+        } else if adjectives.iter().any(|w| **w == *word_str) {
             Category::Adjective
         } else if ANIMALS.contains(&word_str) {
             Category::Animal
-        } else if professions.contains(&word_str) {
+        } else if professions.iter().any(|w| **w == *word_str) {
             Category::Profession
-        } else if nouns.contains(&word_str) {
+        } else if nouns.iter().any(|w| **w == *word_str) {
             Category::Noun
-        } else if gerunds.contains(&word_str) {
+        } else if gerunds.iter().any(|w| **w == *word_str) {
             Category::Gerund
-        } else if present_verbs.contains(&word_str) {
+        } else if present_verbs.iter().any(|w| **w == *word_str) {
             Category::PresentVerb
-        } else if objects.contains(&word_str) {
+        } else if objects.iter().any(|w| **w == *word_str) {
             Category::Object
-        } else if adverbs.contains(&word_str) {
+        } else if adverbs.iter().any(|w| **w == *word_str) {
             Category::Adverb
         } else if PREPOSITIONS.contains(&word_str) {
             Category::Preposition
@@ -284,96 +291,6 @@ pub fn estimate_bruteforce_crack_time(password: &str) -> String {
     estimate_crack_time(entropy)
 }
 
-/// Calculate entropy from formatting choices applied during password display.
-/// This accounts for random elements added in format_password():
-/// - Dramatic openers (25% chance, 30 options × 2 punctuation = 60 total)
-/// - Air quotes on adjectives (30% chance per adjective)
-/// - Smart punctuation (1-3 options depending on sentence type)
-/// - Article prefix for adjective-starting passwords (The/A/An)
-pub fn calculate_formatting_entropy(password: &str, adjective_count: usize) -> f64 {
-    let mut entropy = 0.0;
-
-    // Dramatic openers: 25% chance of appearing
-    // If present: 30 words × 2 punctuation = 60 options
-    // Entropy = P(opener) × log2(60) + P(no opener) × 0
-    // Using weighted average: 0.25 × log2(60) ≈ 1.47 bits average contribution
-    // But we detect if one is present to give accurate entropy
-    const OPENERS: &[&str] = &[
-        "Beware", "Listen", "Warning", "Attention", "Alert",
-        "Behold", "Alas", "Indeed", "Clearly", "Obviously",
-        "Seriously", "Honestly", "Tragically", "Surprisingly",
-        "Allegedly", "Reportedly", "Shockingly", "Naturally",
-        "Curiously", "Mysteriously", "Suspiciously", "Frankly",
-        "Incredibly", "Absurdly", "Hilariously", "Sadly",
-        "Fortunately", "Unfortunately", "Remarkably", "Notably",
-    ];
-
-    let has_opener = OPENERS.iter().any(|&opener| {
-        password.starts_with(opener) || password.starts_with(&opener.to_lowercase())
-    });
-
-    if has_opener {
-        // Opener present: log2(30 words × 2 punctuation) + log2(1/0.25) for the choice to add it
-        entropy += (30.0_f64 * 2.0).log2(); // ~5.9 bits for which opener
-        entropy += (1.0 / 0.25_f64).log2(); // ~2 bits for choosing to have opener
-    } else {
-        // No opener: log2(1/0.75) for the choice not to add it
-        entropy += (1.0 / 0.75_f64).log2(); // ~0.42 bits
-    }
-
-    // Air quotes: 30% chance per adjective
-    // Each adjective with quotes: log2(1/0.3) ≈ 1.74 bits
-    // Each adjective without quotes: log2(1/0.7) ≈ 0.51 bits
-    let quoted_adjectives = password.matches('"').count() / 2; // pairs of quotes
-    let unquoted_adjectives = adjective_count.saturating_sub(quoted_adjectives);
-
-    if quoted_adjectives > 0 {
-        entropy += quoted_adjectives as f64 * (1.0 / 0.3_f64).log2();
-    }
-    if unquoted_adjectives > 0 {
-        entropy += unquoted_adjectives as f64 * (1.0 / 0.7_f64).log2();
-    }
-
-    // Punctuation entropy (detecting which was used)
-    let ends_with_question = password.ends_with('?');
-    let ends_with_exclaim = password.ends_with('!');
-    let ends_with_ellipsis = password.ends_with("...");
-    let ends_with_period = password.ends_with('.') && !ends_with_ellipsis;
-
-    // Questions always get "?" - 0 bits (deterministic)
-    // Imperatives/warnings get "!" or "..." - 1 bit
-    // Statements get ".", "!", or "..." - ~1.58 bits
-    if ends_with_question {
-        // Deterministic, no entropy
-    } else if ends_with_exclaim || ends_with_ellipsis {
-        // Could be imperative (2 options) or statement (3 options)
-        // Conservative estimate: assume 2-3 options average
-        entropy += 1.0; // ~1 bit
-    } else if ends_with_period {
-        // Statement with period (1 of 3 options)
-        entropy += (3.0_f64).log2(); // ~1.58 bits
-    }
-
-    // Article prefix for adjective-starting passwords
-    // The (70%), A (15%), An (15%) - but only when first word is adjective
-    // Entropy ≈ -0.7×log2(0.7) - 0.15×log2(0.15) - 0.15×log2(0.15) ≈ 1.18 bits
-    // We detect this by checking if password starts with "The ", "A ", or "An "
-    if password.starts_with("The ") || password.starts_with("A ") || password.starts_with("An ") {
-        // Check if this was an added article (not part of original template)
-        // by seeing if second word looks like an adjective
-        let words: Vec<&str> = password.split_whitespace().collect();
-        if words.len() > 1 {
-            let second_word = words[1].trim_matches('"').to_lowercase();
-            if get_adjectives(false).contains(&second_word.as_str()) {
-                // Article was likely added - account for The(70%)/A/An(30%) choice
-                entropy += 1.2; // approximate weighted entropy
-            }
-        }
-    }
-
-    entropy
-}
-
 fn format_duration(seconds: f64) -> String {
     const SECONDS_PER_YEAR: f64 = 31536000.0;
     const UNIVERSE_AGE_YEARS: f64 = 13.8e9; // ~13.8 billion years
@@ -0,0 +1,631 @@
+//! Turning raw generated words into the sentence a user actually sees.
+//!
+//! The generators return lowercase word lists. This module renders them the way
+//! `mkpass` shows them: commas rendered, an article in front of a dangling
+//! adjective, optional sarcastic "air quotes", a capital letter, ending
+//! punctuation that fits the sentence type and an optional dramatic opener.
+//! The lines of a haiku or couplet are joined with their own separator, and
+//! the sentences of a [`CompoundPassword`] with their connectives.
+//!
+//! Every random choice made here is recorded as a [`Decision`], exactly like
+//! the template generators do, so the entropy added by formatting is known
+//! precisely instead of being guessed back from the finished string.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::article::indefinite;
+use crate::cache::{Cache, Source};
+use crate::compound::{CompoundPassword, Connective};
+use crate::generator::WordConfig;
+use crate::lang::Language;
+use crate::password::Decision;
+use crate::templates::{render_commas, LINE_MARKER};
+use crate::wordset::WordSet;
+use rand::seq::SliceRandom;
+use rand::{CryptoRng, Rng, RngCore};
+
+/// Dramatic one-word openers (added 25% of the time when enabled).
+pub const OPENERS: &[&str] = &[
+    "Beware", "Listen", "Warning", "Attention", "Alert",
+    "Behold", "Alas", "Indeed", "Clearly", "Obviously",
+    "Seriously", "Honestly", "Tragically", "Surprisingly",
+    "Allegedly", "Reportedly", "Shockingly", "Naturally",
+    "Curiously", "Mysteriously", "Suspiciously", "Frankly",
+    "Incredibly", "Absurdly", "Hilariously", "Sadly",
+    "Fortunately", "Unfortunately", "Remarkably", "Notably",
+];
+
+/// How the end of the sentence is punctuated.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Punctuation {
+    /// Pick punctuation that fits the sentence type: "?" for questions,
+    /// "!" or "..." for commands and warnings, ".", "!" or "..." otherwise.
+    #[default]
+    Smart,
+    /// Always end with a period.
+    Period,
+    /// No ending punctuation.
+    None,
+}
+
+/// Options controlling how words are rendered into a password.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FormatOptions {
+    /// Separator placed between words.
+    pub separator: String,
+    /// Separator placed between the lines of a haiku or couplet.
+    pub line_separator: String,
+    /// Capitalize every word (CamelCase style) instead of only the first.
+    pub camel_case: bool,
+    /// Allow a dramatic opener ("Behold!", "Sadly...") in front.
+    pub openers: bool,
+    /// Allow sarcastic "air quotes" around adjectives.
+    pub air_quotes: bool,
+    /// Ending punctuation style.
+    pub punctuation: Punctuation,
+    /// Language of the password, which picks the openers. The article prefix
+    /// and air quotes only apply to English.
+    pub language: Language,
+    /// The word lists the password was drawn from (see
+    /// [`WordConfig::words`]), whose adjectives get the article prefix and
+    /// air quotes. `None` means the lists of the default configuration.
+    pub words: Option<Arc<WordSet>>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            separator: " ".to_string(),
+            line_separator: " / ".to_string(),
+            camel_case: false,
+            openers: true,
+            air_quotes: true,
+            punctuation: Punctuation::Smart,
+            language: Language::English,
+            words: None,
+        }
+    }
+}
+
+/// A formatted password together with the random decisions formatting made.
+#[derive(Clone, Debug)]
+pub struct FormattedPassword {
+    /// The final password text.
+    pub text: String,
+    /// Every random formatting decision, in order.
+    pub decisions: Vec<Decision>,
+}
+
+impl FormattedPassword {
+    /// Exact entropy added by formatting, in bits.
+    pub fn entropy(&self) -> f64 {
+        self.decisions.iter().map(Decision::bits).sum()
+    }
+}
+
+/// Capitalize the first letter of a word
+pub fn capitalize_word(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+    }
+}
+
+/// Capitalize only the first letter of the entire string (skipping non-letter characters like quotes)
+pub fn capitalize_first(s: &str) -> String {
+    let mut result = String::new();
+    let mut capitalized = false;
+
+    for c in s.chars() {
+        if !capitalized && c.is_alphabetic() {
+            result.extend(c.to_uppercase());
+            capitalized = true;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+/// Lowercase only the first letter of the entire string, for a sentence
+/// carried on after a connective
+fn lowercase_first(s: &str) -> String {
+    match s.char_indices().find(|(_, c)| c.is_alphabetic()) {
+        Some((i, c)) => format!("{}{}{}", &s[..i], c.to_lowercase(), &s[i + c.len_utf8()..]),
+        None => s.to_string(),
+    }
+}
+
+/// The adjectives of recent word lists as sets, so formatting doesn't scan a
+/// list once per word.
+static ADJECTIVE_SETS: Cache<Source<WordSet>, HashSet<Arc<str>>> = Cache::new();
+
+/// The adjectives of `set`, as a set
+fn adjectives(set: &Arc<WordSet>) -> Arc<HashSet<Arc<str>>> {
+    ADJECTIVE_SETS.get(Source::of(set), || set.adjectives.iter().cloned().collect())
+}
+
+/// Check if a word is one of the adjectives
+fn is_adjective(word: &str, adjectives: &HashSet<Arc<str>>) -> bool {
+    adjectives.contains(word.to_lowercase().as_str())
+}
+
+/// Check if the first word of a password needs an article prefix
+/// Returns true if the password starts with an adjective of `set` (which looks incomplete without an article)
+pub fn needs_article_prefix(words: &[String], set: &Arc<WordSet>) -> bool {
+    starts_with_adjective(words, &adjectives(set))
+}
+
+/// Check if the first word is one of the adjectives
+fn starts_with_adjective(words: &[String], adjectives: &HashSet<Arc<str>>) -> bool {
+    if words.is_empty() {
+        return false;
+    }
+    // Check if first word is an adjective (strip quotes if present)
+    let first_word = words[0].trim_matches('"');
+    is_adjective(first_word, adjectives)
+}
+
+/// Pick one of `options` uniformly and record the choice
+fn choose<R: RngCore + CryptoRng>(
+    label: &'static str,
+    options: &[&'static str],
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> &'static str {
+    decisions.push(Decision::uniform(label, options.len()));
+    options.choose(rng).unwrap()
+}
+
+/// Flip a biased coin and record the outcome
+fn chance<R: RngCore + CryptoRng>(
+    label: &'static str,
+    p: f64,
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> bool {
+    let hit = rng.gen_bool(p);
+    decisions.push(Decision::new(label, if hit { p } else { 1.0 - p }));
+    hit
+}
+
+/// Get appropriate ending punctuation based on sentence type
+fn get_punctuation<R: RngCore + CryptoRng>(
+    words: &[String],
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> &'static str {
+    if words.is_empty() {
+        return ".";
+    }
+
+    // Get the first few words (lowercased) to detect sentence type
+    let first_word = words[0].to_lowercase();
+    let first_two: String = if words.len() >= 2 {
+        format!("{} {}", words[0].to_lowercase(), words[1].to_lowercase())
+    } else {
+        first_word.clone()
+    };
+
+    // Question words - always use "?"
+    const QUESTION_WORDS: &[&str] = &[
+        "why", "how", "what", "when", "where", "who", "which",
+    ];
+    if QUESTION_WORDS.contains(&first_word.as_str()) {
+        return "?";
+    }
+
+    // Imperative/command starters - use "!" or "..."
+    const IMPERATIVE_STARTERS: &[&str] = &[
+        "never", "always", "beware", "avoid", "respect", "fear",
+        "watch", "stay", "keep", "bow", "surrender", "obey",
+        "question", "challenge", "embrace", "reject", "honor",
+        "distrust", "worship", "run", "hide", "confront", "welcome",
+        "do not", "never trust", "always fear", "beware of",
+    ];
+    for starter in IMPERATIVE_STARTERS {
+        if first_word == *starter || first_two.starts_with(starter) {
+            return choose("punctuation", &["!", "..."], rng, decisions);
+        }
+    }
+
+    // Warning starters (similar to imperative but more emphatic)
+    const WARNING_STARTERS: &[&str] = &[
+        "stop", "cease", "halt", "prevent", "resist", "abandon",
+        "forget", "forsake", "shun", "evade", "escape", "flee",
+        "renounce", "discontinue", "terminate", "end", "quit",
+        "banish", "prohibit", "forbid", "disallow", "outlaw",
+    ];
+    if WARNING_STARTERS.contains(&first_word.as_str()) {
+        return choose("punctuation", &["!", "..."], rng, decisions);
+    }
+
+    // Statement punctuation - no "?" for statements
+    choose("punctuation", &[".", "!", "..."], rng, decisions)
+}
+
+/// The ending punctuation `options` asks for, fitting the sentence `words` begin
+fn ending_punctuation<R: RngCore + CryptoRng>(
+    words: &[String],
+    options: &FormatOptions,
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> &'static str {
+    match options.punctuation {
+        Punctuation::Smart => get_punctuation(words, rng, decisions),
+        Punctuation::Period => ".",
+        Punctuation::None => "",
+    }
+}
+
+/// Get an appropriate article ("A", "An", or "The") for the password
+fn get_article_prefix<R: RngCore + CryptoRng>(
+    words: &[String],
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> &'static str {
+    // Use "The" most of the time (70%) or "A/An" (30%)
+    if chance("article prefix", 0.7, rng, decisions) {
+        "The"
+    } else {
+        // Match the sound of the first word (quotes are skipped)
+        match words.first().map(|word| indefinite(word)) {
+            Some("an") => "An",
+            _ => "A",
+        }
+    }
+}
+
+/// Maybe wrap adjectives in sarcastic "air quotes" (30% chance per adjective)
+fn maybe_airquote_adjectives<R: RngCore + CryptoRng>(
+    words: Vec<String>,
+    adjectives: &HashSet<Arc<str>>,
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> Vec<String> {
+    words.into_iter().map(|word| {
+        if is_adjective(&word, adjectives) && chance("air quotes", 0.3, rng, decisions) {
+            format!("\"{}\"", word)
+        } else {
+            word
+        }
+    }).collect()
+}
+
+/// Maybe add a dramatic one-word opener (25% chance)
+fn maybe_add_opener<R: RngCore + CryptoRng>(
+    language: Language,
+    rng: &mut R,
+    decisions: &mut Vec<Decision>,
+) -> Option<String> {
+    if !chance("opener", 0.25, rng, decisions) {
+        return None;
+    }
+
+    let opener = choose("opener word", language.openers(), rng, decisions);
+    let punctuation = choose("opener punctuation", &["!", "..."], rng, decisions);
+
+    Some(format!("{}{}", opener, punctuation))
+}
+
+/// Format a password from words based on the provided options
+pub fn format_password(words: Vec<String>, options: &FormatOptions) -> FormattedPassword {
+    format_password_with_rng(words, options, &mut rand::thread_rng())
+}
+
+/// Format a password from words based on the provided options using the given RNG
+pub fn format_password_with_rng<R: RngCore + CryptoRng>(
+    words: Vec<String>,
+    options: &FormatOptions,
+    rng: &mut R,
+) -> FormattedPassword {
+    let mut decisions = Vec::new();
+
+    // An opener would throw off a haiku's syllable count, or leave a
+    // couplet's first line longer than its second
+    let haiku = words.iter().any(|w| w == LINE_MARKER);
+
+    // Render comma markers into actual commas (e.g., "In the shadows, the penguin...")
+    let words = render_commas(words);
+
+    // Check if we need to add an article prefix BEFORE adding air quotes
+    // (so we check the original first word, not a quoted version)
+    let english = options.language == Language::English;
+    let set = options.words.clone().unwrap_or_else(|| WordConfig::default().words());
+    let adjectives = adjectives(&set);
+    let needs_article = english && starts_with_adjective(&words, &adjectives);
+
+    // Determine punctuation before modifying words (based on sentence type)
+    let punctuation = ending_punctuation(&words, options, rng, &mut decisions);
+
+    // Maybe add sarcastic air quotes to some adjectives
+    let words = if options.air_quotes && english {
+        maybe_airquote_adjectives(words, &adjectives, rng, &mut decisions)
+    } else {
+        words
+    };
+
+    // Prepend article if needed (after air quotes, so the article comes before any quoted word)
+    let words = if needs_article {
+        let article = get_article_prefix(&words, rng, &mut decisions);
+        let mut new_words = vec![article.to_string()];
+        new_words.extend(words);
+        new_words
+    } else {
+        words
+    };
+
+    // Multi-word entries ("rubber ducks") are split so the separator applies to them too
+    let sep = options.separator.as_str();
+    let lines: Vec<String> = words
+        .split(|w| w == LINE_MARKER)
+        .map(|line| {
+            let words = line.iter().flat_map(|w| w.split_whitespace());
+            if options.camel_case {
+                // CamelCase style - capitalize each word
+                words.map(capitalize_word).collect::<Vec<_>>().join(sep)
+            } else {
+                // Normal style - just join, we'll capitalize first letter after
+                words.collect::<Vec<_>>().join(sep)
+            }
+        })
+        .collect();
+    let joined = lines.join(&options.line_separator);
+
+    // Capitalize first letter and add punctuation
+    let capitalized = capitalize_first(&joined);
+    let with_punctuation = format!("{}{}", capitalized, punctuation);
+
+    // Maybe add a dramatic opener
+    let opener = if options.openers && !haiku {
+        maybe_add_opener(options.language, rng, &mut decisions)
+    } else {
+        None
+    };
+    let text = match opener {
+        Some(opener) => format!("{}{}{}", opener, sep, with_punctuation),
+        None => with_punctuation,
+    };
+
+    FormattedPassword { text, decisions }
+}
+
+/// Format the sentences of a compound password and join them with their connectives
+pub fn format_compound(password: &CompoundPassword, options: &FormatOptions) -> FormattedPassword {
+    format_compound_with_rng(password, options, &mut rand::thread_rng())
+}
+
+/// Format the sentences of a compound password and join them with their
+/// connectives using the given RNG
+///
+/// Each sentence is formatted as on its own, minus the ending punctuation and
+/// with an opener only in front of the first. A sentence carried on after
+/// "and then" or "because" starts in lowercase; every sentence that ends
+/// (before "Meanwhile" and at the end) gets the punctuation that fits the
+/// sentence it started with, so a question keeps its "?".
+pub fn format_compound_with_rng<R: RngCore + CryptoRng>(
+    password: &CompoundPassword,
+    options: &FormatOptions,
+    rng: &mut R,
+) -> FormattedPassword {
+    let mut decisions = Vec::new();
+    let sep = options.separator.as_str();
+    let mut text = String::new();
+    let mut sentence: &[String] = &[];
+
+    for (i, clause) in password.clauses.iter().enumerate() {
+        let clause_options = FormatOptions {
+            openers: options.openers && i == 0,
+            punctuation: Punctuation::None,
+            ..options.clone()
+        };
+        let formatted = format_password_with_rng(clause.words.clone(), &clause_options, rng);
+        decisions.extend(formatted.decisions);
+
+        let Some(&connective) = i.checked_sub(1).and_then(|j| password.connectives.get(j)) else {
+            sentence = &clause.words;
+            text = formatted.text;
+            continue;
+        };
+        let mut words: Vec<String> = connective.words().iter().map(|w| w.to_string()).collect();
+        if options.camel_case {
+            words = words.iter().map(|w| capitalize_word(w)).collect();
+        }
+        if connective.starts_sentence() {
+            text += ending_punctuation(sentence, options, rng, &mut decisions);
+            sentence = &clause.words;
+            words[0] = capitalize_word(&words[0]);
+            if let Some(last) = words.last_mut() {
+                last.push(',');
+            }
+        } else if connective == Connective::AndThen {
+            text.push(',');
+        }
+        let clause_text = if options.camel_case { formatted.text } else { lowercase_first(&formatted.text) };
+        text = [text, words.join(sep), clause_text].join(sep);
+    }
+    text += ending_punctuation(sentence, options, rng, &mut decisions);
+
+    FormattedPassword { text, decisions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::words::ADJECTIVES;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn words(list: &[&str]) -> Vec<String> {
+        list.iter().map(|w| w.to_string()).collect()
+    }
+
+    /// The built-in lists with `adjectives` in place of their own.
+    fn with_adjectives(adjectives: &[&'static str]) -> FormatOptions {
+        let mut set = (*WordConfig::default().words()).clone();
+        set.adjectives = adjectives.iter().map(|&a| a.into()).collect();
+        FormatOptions { words: Some(Arc::new(set)), ..plain() }
+    }
+
+    fn plain() -> FormatOptions {
+        FormatOptions {
+            openers: false,
+            air_quotes: false,
+            punctuation: Punctuation::Period,
+            ..FormatOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_plain_formatting_is_deterministic() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let formatted = format_password_with_rng(words(&["the", "penguin", "sings"]), &plain(), &mut rng);
+        assert_eq!(formatted.text, "The penguin sings.");
+        assert!(formatted.decisions.is_empty());
+        assert_eq!(formatted.entropy(), 0.0);
+    }
+
+    #[test]
+    fn test_haiku_lines_use_the_line_separator() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let haiku = words(&["the", "caffeinated", LINE_MARKER, "narwhal", "smuggles", "rubber ducks", LINE_MARKER, "near", "an", "owl"]);
+        let options = FormatOptions { separator: "-".to_string(), openers: true, ..plain() };
+        let formatted = format_password_with_rng(haiku, &options, &mut rng);
+        assert_eq!(formatted.text, "The-caffeinated / narwhal-smuggles-rubber-ducks / near-an-owl.");
+        assert!(formatted.decisions.is_empty());
+    }
+
+    #[test]
+    fn test_compound_sentences_are_joined_by_their_connectives() {
+        use crate::password::{Decision, GeneratedPassword};
+        use crate::templates::Template;
+
+        let clause = |template, list: &[&str]| GeneratedPassword { template, words: words(list), decisions: Vec::new() };
+        let mut compound = CompoundPassword {
+            clauses: vec![
+                clause(Template::Question, &["why", "would", "the", "penguin", "sing"]),
+                clause(Template::Declarative, &["the", "walrus", "juggles", "pickles"]),
+                clause(Template::Fragment, &["three", "wizards", "debating", "entropy"]),
+                clause(Template::Requires, &["chaos", "requires", "coffee"]),
+            ],
+            connectives: vec![Connective::Because, Connective::Meanwhile, Connective::AndThen],
+            decisions: vec![Decision::uniform("connective", 3); 3],
+        };
+        let mut rng = StdRng::seed_from_u64(1);
+        let formatted = format_compound_with_rng(&compound, &plain(), &mut rng);
+        assert_eq!(
+            formatted.text,
+            "Why would the penguin sing because the walrus juggles pickles. Meanwhile, three wizards debating entropy, and then chaos requires coffee."
+        );
+        assert!(formatted.decisions.is_empty());
+
+        // Smart punctuation fits the sentence each one started with
+        compound.connectives[1] = Connective::Because;
+        let options = FormatOptions { punctuation: Punctuation::Smart, ..plain() };
+        let formatted = format_compound_with_rng(&compound, &options, &mut rng);
+        assert!(formatted.text.ends_with("coffee?"), "{}", formatted.text);
+
+        let options = FormatOptions { separator: String::new(), camel_case: true, ..plain() };
+        let formatted = format_compound_with_rng(&compound, &options, &mut rng);
+        assert!(formatted.text.starts_with("WhyWouldThePenguinSingBecauseTheWalrus"), "{}", formatted.text);
+    }
+
+    #[test]
+    fn test_camel_case_and_separator() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let options = FormatOptions {
+            separator: "-".to_string(),
+            camel_case: true,
+            punctuation: Punctuation::None,
+            ..plain()
+        };
+        let formatted = format_password_with_rng(words(&["the", "penguin", "sings"]), &options, &mut rng);
+        assert_eq!(formatted.text, "The-Penguin-Sings");
+    }
+
+    #[test]
+    fn test_separator_applies_inside_multi_word_entries() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let options = FormatOptions { separator: String::new(), openers: true, ..plain() };
+        for _ in 0..20 {
+            let formatted = format_password_with_rng(words(&["keeper", "of", "rubber ducks"]), &options, &mut rng);
+            assert!(!formatted.text.contains(' '), "{}", formatted.text);
+        }
+    }
+
+    #[test]
+    fn test_questions_have_no_punctuation_entropy() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let options = FormatOptions { punctuation: Punctuation::Smart, ..plain() };
+        let formatted = format_password_with_rng(words(&["why", "does", "the", "penguin", "sing"]), &options, &mut rng);
+        assert!(formatted.text.ends_with('?'));
+        assert_eq!(formatted.entropy(), 0.0);
+    }
+
+    #[test]
+    fn test_statement_punctuation_is_recorded() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let options = FormatOptions { punctuation: Punctuation::Smart, ..plain() };
+        let formatted = format_password_with_rng(words(&["the", "penguin", "sings"]), &options, &mut rng);
+        assert!((formatted.entropy() - 3.0_f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_article_prefix_for_leading_adjective() {
+        let adjective = ADJECTIVES[0];
+        let mut rng = StdRng::seed_from_u64(3);
+        let formatted = format_password_with_rng(words(&[adjective, "penguin"]), &plain(), &mut rng);
+        let first = formatted.text.split(' ').next().unwrap();
+        assert!(["The", "A", "An"].contains(&first), "{}", formatted.text);
+        assert_eq!(formatted.decisions.len(), 1);
+        assert_eq!(formatted.decisions[0].label, "article prefix");
+    }
+
+    #[test]
+    fn test_adjectives_come_from_the_active_word_lists() {
+        let options = FormatOptions { air_quotes: true, ..with_adjectives(&["zorbular"]) };
+        let mut rng = StdRng::seed_from_u64(4);
+        let formatted = format_password_with_rng(words(&["zorbular", "penguin"]), &options, &mut rng);
+        let labels: Vec<_> = formatted.decisions.iter().map(|d| d.label).collect();
+        assert_eq!(labels, ["air quotes", "article prefix"], "{}", formatted.text);
+
+        // A built-in adjective missing from the lists is just a word
+        let formatted = format_password_with_rng(words(&[ADJECTIVES[0], "penguin"]), &options, &mut rng);
+        assert!(formatted.decisions.is_empty(), "{}", formatted.text);
+    }
+
+    #[test]
+    fn test_every_adjective_gets_an_air_quote_decision() {
+        let options = FormatOptions { air_quotes: true, ..plain() };
+        let input = words(&["the", ADJECTIVES[0], "penguin", "and", "the", ADJECTIVES[1], "walrus"]);
+        for seed in 0..20 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let formatted = format_password_with_rng(input.clone(), &options, &mut rng);
+            let quotes = formatted.decisions.iter().filter(|d| d.label == "air quotes").count();
+            assert_eq!(quotes, 2);
+            let quoted = formatted.text.matches('"').count() / 2;
+            let expected: f64 = quoted as f64 * (1.0 / 0.3_f64).log2()
+                + (2 - quoted) as f64 * (1.0 / 0.7_f64).log2();
+            assert!((formatted.entropy() - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_opener_entropy_matches_output() {
+        let options = FormatOptions { openers: true, ..plain() };
+        for seed in 0..40 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let formatted = format_password_with_rng(words(&["the", "penguin", "sings"]), &options, &mut rng);
+            let has_opener = OPENERS.iter().any(|o| formatted.text.starts_with(o));
+            let expected = if has_opener {
+                2.0 + (OPENERS.len() as f64).log2() + 1.0
+            } else {
+                (1.0 / 0.75_f64).log2()
+            };
+            assert!((formatted.entropy() - expected).abs() < 1e-9, "{}", formatted.text);
+        }
+    }
+}
@@ -1,33 +1,340 @@
-use rand::Rng;
+use std::fmt;
+use std::sync::{Arc, LazyLock};
 
-use crate::templates::Template;
+use rand::{CryptoRng, Rng, RngCore};
+
+use crate::article::fix_articles;
+use crate::cache::{Cache, Source};
+use crate::denylist::Denylist;
+use crate::lang::Language;
+use crate::mix::TemplateMix;
+use crate::password::{Decision, GeneratedPassword};
+use crate::rating::ContentRating;
+use crate::templates::{couplet_fits, haiku_fits, Template};
+use crate::themes::{themed, Theme, ThemeMode};
 use crate::transitions::{get_transitions, Category, Transition};
+use crate::wordinfo::WordFilter;
+use crate::words::CONNECTORS;
+use crate::wordset::{ListKind, WordSet};
 
 /// Configuration for word selection during password generation.
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct WordConfig {
-    /// Whether to include NSFW/adult words.
-    pub nsfw: bool,
+    /// How much grown-up content the built-in word lists may contain.
+    pub rating: ContentRating,
+    /// Custom word lists to draw from instead of the built-in ones.
+    pub words: Option<Arc<WordSet>>,
+    /// Vocabulary packs applied on top of the word lists.
+    pub themes: Vec<Theme>,
+    /// Whether themes replace the lists they cover or add to them.
+    pub theme_mode: ThemeMode,
+    /// The language passwords are written in. Custom word lists, themes and
+    /// filters only apply to English.
+    pub language: Language,
+    /// Limits on word length, syllables and spelling difficulty.
+    pub filter: WordFilter,
+    /// Words and phrases removed from every list.
+    pub denylist: Option<Arc<Denylist>>,
+    /// How strongly to favour familiar, vivid words, from 0 (every word
+    /// equally likely) to 1. See [`crate::memorability`].
+    pub memorability: f64,
+    /// How likely each template is to be drawn, when not every template of
+    /// the language is equally likely.
+    pub mix: Option<Arc<TemplateMix>>,
+}
+
+/// What the word lists of a [`WordConfig`] are built from.
+#[derive(PartialEq)]
+struct WordsKey {
+    rating: ContentRating,
+    words: Option<Source<WordSet>>,
+    themes: Vec<Theme>,
+    theme_mode: ThemeMode,
+    denylist: Option<Source<Denylist>>,
+    filter: WordFilter,
 }
 
+/// Word lists built for recent configurations.
+static WORDS: Cache<WordsKey, WordSet> = Cache::new();
+
+/// What the templates available to a [`WordConfig`] depend on.
+#[derive(PartialEq)]
+struct TemplatesKey {
+    words: Source<WordSet>,
+    language: Language,
+    mix: Option<Source<TemplateMix>>,
+}
+
+/// Templates available with recent configurations.
+static TEMPLATES: Cache<TemplatesKey, Vec<Template>> = Cache::new();
+
+impl WordConfig {
+    /// The word lists this configuration draws from: the custom set if there
+    /// is one, otherwise the built-in lists, with any themes, the denylist
+    /// and then the filter applied. The lists of recent configurations are
+    /// kept, so asking again is cheap.
+    pub fn words(&self) -> Arc<WordSet> {
+        let denylist = self.denylist.as_ref().filter(|denylist| !denylist.is_empty());
+        if self.themes.is_empty() && denylist.is_none() && self.filter.is_empty() {
+            return self.words.clone().unwrap_or_else(|| WordSet::shared(self.rating));
+        }
+        let key = WordsKey {
+            rating: self.rating,
+            words: self.words.as_ref().map(Source::of),
+            themes: self.themes.clone(),
+            theme_mode: self.theme_mode,
+            denylist: denylist.map(Source::of),
+            filter: self.filter,
+        };
+        WORDS.get(key, || {
+            let base = self.words.as_deref().unwrap_or_else(|| WordSet::builtin(self.rating));
+            let mut set = if self.themes.is_empty() {
+                base.clone()
+            } else {
+                themed(base, &self.themes, self.theme_mode, self.rating)
+            };
+            if let Some(denylist) = denylist {
+                set = denylist.apply(&set);
+            }
+            if !self.filter.is_empty() {
+                set = self.filter.apply(&set);
+            }
+            set
+        })
+    }
+
+    /// The templates that can be generated: those of the mix, or else of the
+    /// configured language, whose word lists all have entries left. Like the
+    /// word lists, they are kept for recent configurations.
+    pub fn templates(&self) -> Arc<Vec<Template>> {
+        let key = TemplatesKey {
+            words: Source::of(&self.words()),
+            language: self.language,
+            mix: self.mix.as_ref().map(Source::of),
+        };
+        TEMPLATES.get(key, || {
+            let templates = match &self.mix {
+                Some(mix) => mix.templates(),
+                None => self.language.templates().to_vec(),
+            };
+            templates.into_iter().filter(|t| self.can_generate(t)).collect()
+        })
+    }
+
+    /// The decision recorded for drawing a random template from `available`,
+    /// the [`templates`](Self::templates) of this configuration: weighted by
+    /// the mix if there is one, otherwise uniform.
+    pub fn template_decision(&self, available: &[Template]) -> Decision {
+        match &self.mix {
+            Some(mix) => mix.decision(available),
+            None => Decision::uniform("template", available.len()),
+        }
+    }
+
+    /// Whether `template` can be generated: it exists in the configured
+    /// language and, in English, none of the lists it draws from or is
+    /// extended with is empty (and, for a haiku, they have words that fit the syllable count, and for
+    /// a couplet, words that rhyme).
+    pub fn can_generate(&self, template: &Template) -> bool {
+        let custom = matches!(template, Template::Custom(_));
+        if self.language != Language::English {
+            return !custom && self.language.supports(template);
+        }
+        let words = self.words();
+        // Every template can be extended with a prepositional phrase
+        const EXTENSION: [ListKind; 4] = [ListKind::Adjective, ListKind::Animal, ListKind::Profession, ListKind::Noun];
+        !words.prepositions.is_empty()
+            && !words.articles.is_empty()
+            && EXTENSION.iter().all(|&kind| !words.list(kind).is_empty())
+            && (custom || self.language.supports(template))
+            && template.lists().iter().all(|&kind| !words.list(kind).is_empty())
+            && (*template != Template::Somewhere || !words.connectors_active.is_empty())
+            && (*template != Template::Haiku || haiku_fits(&words))
+            && (*template != Template::Couplet || couplet_fits(&words))
+    }
+}
+
+/// Why a password could not be generated.
+#[derive(Clone, Debug, PartialEq)]
+pub enum GenerateError {
+    /// No template has words left in every list it draws from.
+    NoTemplate,
+    /// The requested template draws from a list with no words left, or isn't
+    /// written in the configured language.
+    Unavailable(Template),
+    /// The entropy target is not a finite number of bits.
+    InvalidTarget(f64),
+    /// The denylist leaves no connective to join sentences with.
+    NoConnective,
+    /// Lengthening the password stopped adding entropy before it reached the
+    /// target, because every list it extends with has a single entry.
+    Stalled { reached: f64, target: f64 },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::NoTemplate => write!(f, "no template has words left with these options"),
+            GenerateError::Unavailable(template) => {
+                write!(f, "the {} template has no words left with these options", template.name())
+            }
+            GenerateError::NoConnective => write!(f, "every connective is denied, so sentences can't be joined"),
+            GenerateError::InvalidTarget(bits) => write!(f, "{} is not a valid entropy target", bits),
+            GenerateError::Stalled { reached, target } => write!(
+                f,
+                "lengthening the password stopped adding entropy at {:.1} of {:.1} bits; the word lists are too small",
+                reached, target
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenerateError {}
+
 /// Generate a password using a randomly selected template.
 /// This allows the caller to apply custom formatting (separators, capitalization, etc.)
-pub fn generate_password(min_words: usize, config: &WordConfig) -> Vec<String> {
-    let mut rng = rand::thread_rng();
-    let template = Template::random(&mut rng);
-    template.generate(&mut rng, min_words, config)
+///
+/// The template choice is recorded as the first decision, so the entropy of the
+/// result covers the full choice space.
+///
+/// # Errors
+///
+/// If no template is available (see [`WordConfig::templates`]).
+pub fn generate_password(min_words: usize, config: &WordConfig) -> Result<GeneratedPassword, GenerateError> {
+    generate_password_with_rng(&mut rand::thread_rng(), min_words, config)
+}
+
+/// Generate a password using a randomly selected template and the given RNG.
+///
+/// Pass a seeded `StdRng` to reproduce exact outputs, or `OsRng` to draw
+/// straight from the operating system.
+pub fn generate_password_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    min_words: usize,
+    config: &WordConfig,
+) -> Result<GeneratedPassword, GenerateError> {
+    let (template, template_choice) = random_template(rng, config)?;
+    // Only available templates are drawn
+    let mut password = template.generate_available(rng, min_words, config);
+    password.decisions.insert(0, template_choice);
+    Ok(password)
+}
+
+/// Pick one of the templates available with `config`, by the weights of its
+/// mix if it has one.
+fn random_template(rng: &mut impl Rng, config: &WordConfig) -> Result<(Template, Decision), GenerateError> {
+    let templates = config.templates();
+    if templates.is_empty() {
+        return Err(GenerateError::NoTemplate);
+    }
+    if let Some(mix) = &config.mix {
+        return Ok(mix.choose(rng, &templates));
+    }
+    let template = templates[rng.gen_range(0..templates.len())].clone();
+    Ok((template, config.template_decision(&templates)))
 }
 
 /// Generate a password using a specific template.
-pub fn generate_password_with_template(template: Template, min_words: usize, config: &WordConfig) -> Vec<String> {
-    let mut rng = rand::thread_rng();
-    template.generate(&mut rng, min_words, config)
+///
+/// # Errors
+///
+/// If the template can't be generated with `config` (see
+/// [`WordConfig::can_generate`]).
+pub fn generate_password_with_template(
+    template: Template,
+    min_words: usize,
+    config: &WordConfig,
+) -> Result<GeneratedPassword, GenerateError> {
+    generate_password_with_template_with_rng(&mut rand::thread_rng(), template, min_words, config)
+}
+
+/// Generate a password using a specific template and the given RNG.
+pub fn generate_password_with_template_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    template: Template,
+    min_words: usize,
+    config: &WordConfig,
+) -> Result<GeneratedPassword, GenerateError> {
+    template.generate(rng, min_words, config)
+}
+
+/// Generate a password whose exact entropy is at least `min_bits`.
+///
+/// The bits spent on the random template choice count toward the target.
+/// Formatting and any selection or policy penalties are not included, so add
+/// those to the target if the final displayed figure must meet it.
+///
+/// # Errors
+///
+/// If no template is available, `min_bits` isn't finite, or the word lists
+/// are too small to reach it (see [`Template::generate_with_min_entropy`]).
+pub fn generate_with_min_entropy(min_bits: f64, config: &WordConfig) -> Result<GeneratedPassword, GenerateError> {
+    generate_with_min_entropy_with_rng(&mut rand::thread_rng(), min_bits, config)
+}
+
+/// Generate a password whose exact entropy is at least `min_bits` using the given RNG.
+pub fn generate_with_min_entropy_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    min_bits: f64,
+    config: &WordConfig,
+) -> Result<GeneratedPassword, GenerateError> {
+    if !min_bits.is_finite() {
+        return Err(GenerateError::InvalidTarget(min_bits));
+    }
+    let (template, template_choice) = random_template(rng, config)?;
+    let mut password = template.generate_with_min_entropy(rng, min_bits - template_choice.bits(), config)?;
+    password.decisions.insert(0, template_choice);
+    Ok(password)
+}
+
+/// How long a generated password should be.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PasswordLength {
+    /// At least this many content words (a hint for fixed-structure templates).
+    MinWords(usize),
+    /// At least this many bits of exact entropy.
+    MinEntropy(f64),
+}
+
+/// Generate a password of the given length, from a specific template or a random one.
+///
+/// # Errors
+///
+/// If the template, or every template, is unavailable, or an entropy target
+/// can't be reached (see [`generate_with_min_entropy`]).
+pub fn generate_password_of_length_with_rng<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    template: Option<Template>,
+    length: PasswordLength,
+    config: &WordConfig,
+) -> Result<GeneratedPassword, GenerateError> {
+    match (template, length) {
+        (Some(t), PasswordLength::MinWords(n)) => generate_password_with_template_with_rng(rng, t, n, config),
+        (None, PasswordLength::MinWords(n)) => generate_password_with_rng(rng, n, config),
+        (Some(t), PasswordLength::MinEntropy(bits)) => t.generate_with_min_entropy(rng, bits, config),
+        (None, PasswordLength::MinEntropy(bits)) => generate_with_min_entropy_with_rng(rng, bits, config),
+    }
 }
 
 /// Generate a password using the original declarative Markov-style generator.
 /// This is the legacy implementation preserved for reference and comparison.
 pub fn generate_password_declarative(min_words: usize) -> Vec<String> {
-    let mut rng = rand::thread_rng();
+    generate_password_declarative_with_rng(&mut rand::thread_rng(), min_words)
+}
+
+/// Generate a password using the legacy Markov-style generator and the given RNG.
+pub fn generate_password_declarative_with_rng<R: RngCore + CryptoRng>(rng: &mut R, min_words: usize) -> Vec<String> {
+    generate_password_declarative_with_config(rng, min_words, &WordConfig::default())
+}
+
+/// Generate a password using the legacy Markov-style generator, drawing from
+/// `config`'s word lists. Categories whose list is empty are skipped.
+pub fn generate_password_declarative_with_config<R: RngCore + CryptoRng>(
+    rng: &mut R,
+    min_words: usize,
+    config: &WordConfig,
+) -> Vec<String> {
+    let word_set = config.words();
     let mut words: Vec<&str> = Vec::new();
     let mut content_word_count = 0;
     let mut current_category: Option<Category> = None;
@@ -40,8 +347,9 @@ pub fn generate_password_declarative(min_words: usize) -> Vec<String> {
         }
 
         let next_category = select_next_category(
-            &mut rng,
+            rng,
             transitions,
+            &word_set,
             content_word_count,
             min_words,
             in_prep_phrase,
@@ -56,8 +364,8 @@ pub fn generate_password_declarative(min_words: usize) -> Vec<String> {
             in_prep_phrase = true;
         }
 
-        let word_list = next_category.words();
-        let word = word_list[rng.gen_range(0..word_list.len())];
+        let word_list = category_words(next_category, &word_set);
+        let word = &*word_list[rng.gen_range(0..word_list.len())];
         words.push(word);
 
         if next_category.counts_toward_minimum() {
@@ -71,35 +379,33 @@ pub fn generate_password_declarative(min_words: usize) -> Vec<String> {
     fix_articles(&words)
 }
 
-/// Fix "a" to "an" when followed by a word starting with a vowel
-fn fix_articles(words: &[&str]) -> Vec<String> {
-    let mut result = Vec::new();
+/// [`CONNECTORS`] as list entries.
+static CONNECTOR_ENTRIES: LazyLock<Vec<Arc<str>>> =
+    LazyLock::new(|| CONNECTORS.iter().map(|&connector| Arc::from(connector)).collect());
 
-    for (i, word) in words.iter().enumerate() {
-        if *word == "a" {
-            if let Some(next_word) = words.get(i + 1) {
-                if starts_with_vowel_sound(next_word) {
-                    result.push("an".to_string());
-                    continue;
-                }
-            }
-        }
-        result.push(word.to_string());
+/// The list a category draws from in `words`. Connectors are grammar and
+/// always come from [`CONNECTORS`].
+fn category_words(category: Category, words: &WordSet) -> &[Arc<str>] {
+    match category {
+        Category::Adjective => &words.adjectives,
+        Category::Animal => &words.animals,
+        Category::Profession => &words.professions,
+        Category::Noun => &words.nouns,
+        Category::Gerund => &words.gerunds,
+        Category::PresentVerb => &words.present_verbs,
+        Category::Object => &words.objects,
+        Category::Adverb => &words.adverbs,
+        Category::Article => &words.articles,
+        Category::Preposition => &words.prepositions,
+        Category::Connector => &CONNECTOR_ENTRIES,
+        Category::End => &[],
     }
-
-    result
-}
-
-fn starts_with_vowel_sound(word: &str) -> bool {
-    word.chars()
-        .next()
-        .map(|c| matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u'))
-        .unwrap_or(false)
 }
 
 fn select_next_category(
     rng: &mut impl Rng,
     transitions: &[Transition],
+    words: &WordSet,
     content_word_count: usize,
     min_words: usize,
     in_prep_phrase: bool,
@@ -114,19 +420,24 @@ fn select_next_category(
             if !can_end && t.category == Category::End {
                 return false;
             }
+            // Skip categories with nothing left to pick
+            if t.category != Category::End && category_words(t.category, words).is_empty() {
+                return false;
+            }
             // In prepositional phrases, don't allow verbs (creates run-on sentences)
-            if in_prep_phrase {
-                if matches!(t.category, Category::Connector | Category::PresentVerb | Category::Gerund) {
-                    return false;
-                }
+            if in_prep_phrase
+                && matches!(t.category, Category::Connector | Category::PresentVerb | Category::Gerund)
+            {
+                return false;
             }
             true
         })
         .cloned()
         .collect();
 
-    // If we can't continue but haven't reached min_words, extend with preposition
-    if filtered.is_empty() && !can_end {
+    // If we can't continue but haven't reached min_words, extend with a
+    // preposition, or stop short when there are none left
+    if filtered.is_empty() && !can_end && !words.prepositions.is_empty() {
         return Category::Preposition;
     }
 
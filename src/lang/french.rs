@@ -0,0 +1,216 @@
+//! French: gendered articles with elision ("l'escargot") and the "du"/"des"
+//! contractions. Most adjectives follow the noun; a few short ones precede it.
+
+use super::{f, m, post, pre, Adjective, Article, Case, Gender, Grammar, Lexicon, Noun, Number};
+
+pub(crate) struct French;
+
+const ANIMALS: &[Noun] = &[
+    m("pingouin", "pingouins"),
+    m("lama", "lamas"),
+    f("girafe", "girafes"),
+    m("blaireau", "blaireaux"),
+    f("loutre", "loutres"),
+    m("escargot", "escargots"),
+    f("chèvre", "chèvres"),
+    m("hippopotame", "hippopotames"),
+    f("taupe", "taupes"),
+    m("morse", "morses"),
+    f("poule", "poules"),
+    m("canard", "canards"),
+    f("limace", "limaces"),
+    m("paresseux", "paresseux"),
+    f("grenouille", "grenouilles"),
+    m("écureuil", "écureuils"),
+    f("mouette", "mouettes"),
+    m("chameau", "chameaux"),
+    f("autruche", "autruches"),
+    m("ornithorynque", "ornithorynques"),
+    f("baleine", "baleines"),
+    m("flamant", "flamants"),
+    f("tortue", "tortues"),
+    m("koala", "koalas"),
+    f("fouine", "fouines"),
+    m("phoque", "phoques"),
+    f("souris", "souris"),
+    m("crapaud", "crapauds"),
+    f("vache", "vaches"),
+];
+
+const PROFESSIONS: &[Noun] = &[
+    m("dentiste", "dentistes"),
+    m("comptable", "comptables"),
+    m("plombier", "plombiers"),
+    f("boulangère", "boulangères"),
+    m("notaire", "notaires"),
+    f("bibliothécaire", "bibliothécaires"),
+    m("facteur", "facteurs"),
+    f("ministre", "ministres"),
+    m("pompier", "pompiers"),
+    f("concierge", "concierges"),
+    m("jardinier", "jardiniers"),
+    f("coiffeuse", "coiffeuses"),
+    m("astronaute", "astronautes"),
+    m("huissier", "huissiers"),
+    f("sorcière", "sorcières"),
+    m("magicien", "magiciens"),
+    f("chirurgienne", "chirurgiennes"),
+    m("stagiaire", "stagiaires"),
+    m("douanier", "douaniers"),
+    f("présidente", "présidentes"),
+    m("sommelier", "sommeliers"),
+    m("pirate", "pirates"),
+    f("détective", "détectives"),
+    m("cuisinier", "cuisiniers"),
+    f("pharmacienne", "pharmaciennes"),
+    m("archiviste", "archivistes"),
+];
+
+const OBJECTS: &[Noun] = &[
+    m("cornichon", "cornichons"),
+    f("chaussette", "chaussettes"),
+    m("grille-pain", "grille-pain"),
+    f("baguette", "baguettes"),
+    m("parapluie", "parapluies"),
+    f("casserole", "casseroles"),
+    m("fromage", "fromages"),
+    f("trompette", "trompettes"),
+    m("croissant", "croissants"),
+    f("brouette", "brouettes"),
+    m("tournevis", "tournevis"),
+    f("agrafeuse", "agrafeuses"),
+    m("trombone", "trombones"),
+    f("moustache", "moustaches"),
+    m("accordéon", "accordéons"),
+    f("pantoufle", "pantoufles"),
+    m("radis", "radis"),
+    f("crêpe", "crêpes"),
+    m("parchemin", "parchemins"),
+    f("bougie", "bougies"),
+    m("biscuit", "biscuits"),
+    f("fourchette", "fourchettes"),
+    m("tuba", "tubas"),
+    f("chaise", "chaises"),
+    m("navet", "navets"),
+    f("huître", "huîtres"),
+    m("ballon", "ballons"),
+    f("tartine", "tartines"),
+];
+
+const ADJECTIVES: &[Adjective] = &[
+    pre("petit", "petite", "petits", "petites"),
+    pre("gros", "grosse", "gros", "grosses"),
+    pre("grand", "grande", "grands", "grandes"),
+    pre("jeune", "jeune", "jeunes", "jeunes"),
+    pre("joli", "jolie", "jolis", "jolies"),
+    pre("mauvais", "mauvaise", "mauvais", "mauvaises"),
+    post("grincheux", "grincheuse", "grincheux", "grincheuses"),
+    post("ridicule", "ridicule", "ridicules", "ridicules"),
+    post("mystérieux", "mystérieuse", "mystérieux", "mystérieuses"),
+    post("fatigué", "fatiguée", "fatigués", "fatiguées"),
+    post("bavard", "bavarde", "bavards", "bavardes"),
+    post("maladroit", "maladroite", "maladroits", "maladroites"),
+    post("enragé", "enragée", "enragés", "enragées"),
+    post("légendaire", "légendaire", "légendaires", "légendaires"),
+    post("affamé", "affamée", "affamés", "affamées"),
+    post("frileux", "frileuse", "frileux", "frileuses"),
+    post("prétentieux", "prétentieuse", "prétentieux", "prétentieuses"),
+    post("gluant", "gluante", "gluants", "gluantes"),
+    post("suspect", "suspecte", "suspects", "suspectes"),
+    post("distrait", "distraite", "distraits", "distraites"),
+    post("radioactif", "radioactive", "radioactifs", "radioactives"),
+    post("rouillé", "rouillée", "rouillés", "rouillées"),
+    post("sournois", "sournoise", "sournois", "sournoises"),
+    post("chauve", "chauve", "chauves", "chauves"),
+    post("timide", "timide", "timides", "timides"),
+];
+
+const VERBS: &[&str] = &[
+    "mange", "vole", "cache", "collectionne", "lèche", "renifle", "avale", "critique", "négocie", "confisque",
+    "décore", "adopte", "kidnappe", "espionne", "repeint", "vend", "achète", "taxe", "chatouille", "cuisine",
+    "examine", "répare", "emballe", "empile", "bénit", "recycle", "photographie", "interroge",
+];
+
+const ADVERBS: &[&str] = &[
+    "sournoisement", "joyeusement", "lentement", "bruyamment", "secrètement", "timidement", "furieusement",
+    "gracieusement", "maladroitement", "fièrement", "paresseusement", "discrètement", "nerveusement", "poliment",
+    "brutalement", "tendrement", "rarement", "solennellement", "distraitement", "goulûment",
+];
+
+const OPENERS: &[&str] = &[
+    "Attention", "Hélas", "Incroyable", "Franchement", "Évidemment", "Bizarrement", "Heureusement",
+    "Malheureusement", "Apparemment", "Sérieusement", "Sacrebleu", "Zut",
+];
+
+impl Grammar for French {
+    const LEXICON: &'static Lexicon = &Lexicon {
+        animals: ANIMALS,
+        professions: PROFESSIONS,
+        objects: OBJECTS,
+        adjectives: ADJECTIVES,
+        verbs: VERBS,
+        adverbs: ADVERBS,
+        openers: OPENERS,
+        versus: "contre",
+        since: "depuis",
+        beside: "à côté de",
+    };
+
+    fn article(article: Article, _case: Case, number: Number, gender: Gender, adjective_first: bool) -> Option<&'static str> {
+        let feminine = gender == Gender::Feminine;
+        Some(match (article, number) {
+            (Article::Definite, Number::Singular) => if feminine { "la" } else { "le" },
+            (Article::Definite, Number::Plural) => "les",
+            (Article::Indefinite, Number::Singular) => if feminine { "une" } else { "un" },
+            // "des cornichons", but "de petits cornichons"
+            (Article::Indefinite, Number::Plural) => if adjective_first { "de" } else { "des" },
+        })
+    }
+
+    fn adjective(adjective: &Adjective, _article: Article, _case: Case, number: Number, gender: Gender) -> String {
+        adjective.agree(gender, number).to_string()
+    }
+
+    fn contract(words: Vec<String>) -> Vec<String> {
+        // Elide first, so "de le escargot" becomes "de l'escargot" rather than "du escargot".
+        let mut elided: Vec<String> = Vec::new();
+        let mut words = words.into_iter().peekable();
+        while let Some(word) = words.next() {
+            let apostrophe = match word.as_str() {
+                "le" | "la" => "l'",
+                "de" => "d'",
+                _ => "",
+            };
+            match words.peek() {
+                Some(next) if !apostrophe.is_empty() && elides(next) => {
+                    let next = words.next().unwrap();
+                    elided.push(format!("{}{}", apostrophe, next));
+                }
+                _ => elided.push(word),
+            }
+        }
+
+        let mut result: Vec<String> = Vec::new();
+        for word in elided {
+            if result.last().is_some_and(|last| last == "de") {
+                let contracted = match word.as_str() {
+                    "le" => Some("du"),
+                    "les" => Some("des"),
+                    _ => None,
+                };
+                if let Some(contracted) = contracted {
+                    *result.last_mut().unwrap() = contracted.to_string();
+                    continue;
+                }
+            }
+            result.push(word);
+        }
+        result
+    }
+}
+
+/// Whether an article before `word` loses its vowel. Every "h" in the
+/// lexicon is mute.
+fn elides(word: &str) -> bool {
+    word.chars().next().is_some_and(|c| "aeiouyàâäéèêëîïôöûüh".contains(c.to_lowercase().next().unwrap_or(c)))
+}
@@ -0,0 +1,213 @@
+//! German: three genders, articles and adjective endings declined by case,
+//! capitalized nouns, and adjectives always before the noun. Weak masculine
+//! nouns ("der Bär, den Bären") are left out of the lexicon so that nouns
+//! never change in the singular.
+
+use super::{f, m, n, Adjective, Article, Case, Gender, Grammar, Lexicon, Noun, Number};
+
+pub(crate) struct German;
+
+const ANIMALS: &[Noun] = &[
+    m("Pinguin", "Pinguine"),
+    n("Lama", "Lamas"),
+    f("Giraffe", "Giraffen"),
+    m("Dachs", "Dachse"),
+    m("Otter", "Otter"),
+    f("Schnecke", "Schnecken"),
+    f("Ziege", "Ziegen"),
+    n("Nilpferd", "Nilpferde"),
+    m("Maulwurf", "Maulwürfe"),
+    n("Walross", "Walrosse"),
+    n("Huhn", "Hühner"),
+    f("Ente", "Enten"),
+    n("Faultier", "Faultiere"),
+    m("Frosch", "Frösche"),
+    n("Eichhörnchen", "Eichhörnchen"),
+    f("Möwe", "Möwen"),
+    n("Kamel", "Kamele"),
+    m("Strauß", "Strauße"),
+    n("Schnabeltier", "Schnabeltiere"),
+    m("Wal", "Wale"),
+    m("Flamingo", "Flamingos"),
+    f("Schildkröte", "Schildkröten"),
+    m("Koala", "Koalas"),
+    n("Wiesel", "Wiesel"),
+    m("Seehund", "Seehunde"),
+    f("Maus", "Mäuse"),
+    f("Kröte", "Kröten"),
+    f("Kuh", "Kühe"),
+    m("Igel", "Igel"),
+];
+
+const PROFESSIONS: &[Noun] = &[
+    m("Zahnarzt", "Zahnärzte"),
+    m("Buchhalter", "Buchhalter"),
+    m("Klempner", "Klempner"),
+    f("Bäckerin", "Bäckerinnen"),
+    m("Notar", "Notare"),
+    f("Bibliothekarin", "Bibliothekarinnen"),
+    m("Briefträger", "Briefträger"),
+    f("Ministerin", "Ministerinnen"),
+    m("Feuerwehrmann", "Feuerwehrleute"),
+    m("Hausmeister", "Hausmeister"),
+    m("Gärtner", "Gärtner"),
+    f("Friseurin", "Friseurinnen"),
+    f("Astronautin", "Astronautinnen"),
+    m("Gerichtsvollzieher", "Gerichtsvollzieher"),
+    f("Hexe", "Hexen"),
+    m("Zauberer", "Zauberer"),
+    f("Chirurgin", "Chirurginnen"),
+    m("Zöllner", "Zöllner"),
+    f("Präsidentin", "Präsidentinnen"),
+    m("Sommelier", "Sommeliers"),
+    f("Detektivin", "Detektivinnen"),
+    m("Koch", "Köche"),
+    f("Apothekerin", "Apothekerinnen"),
+    m("Archivar", "Archivare"),
+    m("Bürgermeister", "Bürgermeister"),
+    f("Lehrerin", "Lehrerinnen"),
+];
+
+const OBJECTS: &[Noun] = &[
+    f("Gurke", "Gurken"),
+    f("Socke", "Socken"),
+    m("Toaster", "Toaster"),
+    n("Baguette", "Baguettes"),
+    m("Regenschirm", "Regenschirme"),
+    m("Kochtopf", "Kochtöpfe"),
+    m("Käse", "Käse"),
+    f("Trompete", "Trompeten"),
+    n("Croissant", "Croissants"),
+    f("Schubkarre", "Schubkarren"),
+    m("Schraubenzieher", "Schraubenzieher"),
+    m("Tacker", "Tacker"),
+    f("Büroklammer", "Büroklammern"),
+    m("Schnurrbart", "Schnurrbärte"),
+    n("Akkordeon", "Akkordeons"),
+    m("Hausschuh", "Hausschuhe"),
+    n("Radieschen", "Radieschen"),
+    m("Pfannkuchen", "Pfannkuchen"),
+    f("Kerze", "Kerzen"),
+    m("Keks", "Kekse"),
+    f("Gabel", "Gabeln"),
+    f("Tuba", "Tuben"),
+    m("Stuhl", "Stühle"),
+    f("Rübe", "Rüben"),
+    f("Auster", "Austern"),
+    m("Ballon", "Ballons"),
+    f("Brezel", "Brezeln"),
+    n("Würstchen", "Würstchen"),
+];
+
+/// An adjective stem; endings are added by [`German::adjective`].
+const fn stem(stem: &'static str) -> Adjective {
+    Adjective { forms: [stem; 4], before: true }
+}
+
+const ADJECTIVES: &[Adjective] = &[
+    stem("mürrisch"),
+    stem("lächerlich"),
+    stem("geheimnisvoll"),
+    stem("müd"),
+    stem("gesprächig"),
+    stem("tollpatschig"),
+    stem("wütend"),
+    stem("legendär"),
+    stem("hungrig"),
+    stem("verfroren"),
+    stem("eingebildet"),
+    stem("klebrig"),
+    stem("verdächtig"),
+    stem("zerstreut"),
+    stem("radioaktiv"),
+    stem("rostig"),
+    stem("hinterlistig"),
+    stem("kahl"),
+    stem("schüchtern"),
+    stem("faul"),
+    stem("gierig"),
+    stem("nervös"),
+    stem("verschlafen"),
+    stem("pompös"),
+    stem("winzig"),
+];
+
+const VERBS: &[&str] = &[
+    "frisst", "klaut", "versteckt", "sammelt", "leckt", "beschnüffelt", "verschluckt", "kritisiert",
+    "beschlagnahmt", "dekoriert", "adoptiert", "entführt", "bespitzelt", "bemalt", "verkauft", "kauft",
+    "besteuert", "kitzelt", "kocht", "untersucht", "repariert", "verpackt", "stapelt", "segnet", "recycelt",
+    "fotografiert", "verhört",
+];
+
+const ADVERBS: &[&str] = &[
+    "heimlich", "fröhlich", "langsam", "lautstark", "zaghaft", "grimmig", "anmutig", "ungeschickt", "stolz",
+    "träge", "diskret", "hektisch", "höflich", "brutal", "zärtlich", "selten", "feierlich", "gedankenlos",
+    "gefräßig", "täglich",
+];
+
+const OPENERS: &[&str] = &[
+    "Achtung", "Leider", "Ehrlich", "Tatsächlich", "Offenbar", "Natürlich", "Seltsamerweise",
+    "Glücklicherweise", "Unglaublich", "Vorsicht", "Donnerwetter", "Hoppla",
+];
+
+impl Grammar for German {
+    const LEXICON: &'static Lexicon = &Lexicon {
+        animals: ANIMALS,
+        professions: PROFESSIONS,
+        objects: OBJECTS,
+        adjectives: ADJECTIVES,
+        verbs: VERBS,
+        adverbs: ADVERBS,
+        openers: OPENERS,
+        versus: "gegen",
+        since: "seit",
+        beside: "neben",
+    };
+
+    // "der Pinguin frisst seit 1987 Gurken"
+    const TIME_BEFORE_OBJECT: bool = true;
+
+    fn article(article: Article, case: Case, number: Number, gender: Gender, _adjective_first: bool) -> Option<&'static str> {
+        use Gender::*;
+        match (article, number) {
+            (Article::Definite, Number::Singular) => Some(match (case, gender) {
+                (Case::Nominative, Masculine) => "der",
+                (Case::Accusative, Masculine) => "den",
+                (Case::Dative, Masculine | Neuter) => "dem",
+                (Case::Dative, Feminine) => "der",
+                (_, Feminine) => "die",
+                (_, Neuter) => "das",
+            }),
+            (Article::Definite, Number::Plural) => Some(if case == Case::Dative { "den" } else { "die" }),
+            (Article::Indefinite, Number::Singular) => Some(match (case, gender) {
+                (Case::Accusative, Masculine) => "einen",
+                (Case::Dative, Masculine | Neuter) => "einem",
+                (Case::Dative, Feminine) => "einer",
+                (_, Feminine) => "eine",
+                (_, _) => "ein",
+            }),
+            // Indefinite plurals are bare: "rostige Gurken".
+            (Article::Indefinite, Number::Plural) => None,
+        }
+    }
+
+    fn adjective(adjective: &Adjective, article: Article, case: Case, number: Number, gender: Gender) -> String {
+        use Gender::*;
+        let ending = match (article, number, case) {
+            // Weak endings after der/die/das.
+            (Article::Definite, Number::Plural, _) | (_, _, Case::Dative) => "en",
+            (Article::Definite, Number::Singular, Case::Accusative) if gender == Masculine => "en",
+            (Article::Definite, Number::Singular, _) => "e",
+            // Mixed endings after ein/eine/ein.
+            (Article::Indefinite, Number::Singular, Case::Accusative) if gender == Masculine => "en",
+            (Article::Indefinite, Number::Singular, _) => match gender {
+                Masculine => "er",
+                Feminine => "e",
+                Neuter => "es",
+            },
+            // Strong endings without an article.
+            (Article::Indefinite, Number::Plural, _) => "e",
+        };
+        format!("{}{}", adjective.forms[0], ending)
+    }
+}
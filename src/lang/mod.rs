@@ -0,0 +1,483 @@
+//! Passwords in languages other than English.
+//!
+//! English sentences are built by the templates in [`crate::templates`] from
+//! the word lists in [`crate::words`]. French, German and Spanish need more
+//! than a word list: articles and adjectives agree with the noun's gender and
+//! number, German declines them by case, and adjectives go before or after
+//! the noun. Each language keeps a small lexicon whose nouns carry their
+//! gender and plural, and a [`Grammar`] that renders articles and adjective
+//! forms. The sentence structure itself is shared.
+//!
+//! Only the declarative, versus and since templates exist in these languages.
+//! Custom word lists and themes apply to English only, and the parser and
+//! the encoding module read English passwords only.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::templates::{Builder, ChoiceSource, Template};
+
+mod french;
+mod german;
+mod spanish;
+
+use french::French;
+use german::German;
+use spanish::Spanish;
+
+/// The language passwords are written in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+/// Templates available outside English.
+const SHARED_TEMPLATES: &[Template] = &[Template::Declarative, Template::Versus, Template::Since];
+
+impl Language {
+    /// All supported languages.
+    pub const ALL: &'static [Language] = &[Language::English, Language::French, Language::German, Language::Spanish];
+
+    /// The ISO 639-1 code ("en", "fr", ...).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::French => "fr",
+            Language::German => "de",
+            Language::Spanish => "es",
+        }
+    }
+
+    /// The templates that can be written in this language.
+    pub fn templates(&self) -> &'static [Template] {
+        match self {
+            Language::English => Template::ALL,
+            _ => SHARED_TEMPLATES,
+        }
+    }
+
+    /// Whether `template` can be written in this language.
+    pub fn supports(&self, template: &Template) -> bool {
+        self.templates().contains(template)
+    }
+
+    /// Exclamations that `--opener` puts in front of a password.
+    pub fn openers(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => crate::formatting::OPENERS,
+            Language::French => French::LEXICON.openers,
+            Language::German => German::LEXICON.openers,
+            Language::Spanish => Spanish::LEXICON.openers,
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "fr" | "french" | "français" | "francais" => Ok(Language::French),
+            "de" | "german" | "deutsch" => Ok(Language::German),
+            "es" | "spanish" | "español" | "espanol" => Ok(Language::Spanish),
+            _ => Err(format!("Unknown language '{}' (expected en, fr, de or es)", s)),
+        }
+    }
+}
+
+/// Grammatical gender of a noun.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Gender {
+    Masculine,
+    Feminine,
+    Neuter,
+}
+
+/// Grammatical number of a noun phrase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Number {
+    Singular,
+    Plural,
+}
+
+/// Grammatical case of a noun phrase. Only German inflects for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Case {
+    Nominative,
+    Accusative,
+    Dative,
+}
+
+/// Which article a noun phrase takes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Article {
+    Definite,
+    Indefinite,
+}
+
+/// A noun with the metadata needed to agree with it.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Noun {
+    pub singular: &'static str,
+    pub plural: &'static str,
+    pub gender: Gender,
+}
+
+impl Noun {
+    fn form(&self, number: Number) -> &'static str {
+        match number {
+            Number::Singular => self.singular,
+            Number::Plural => self.plural,
+        }
+    }
+}
+
+pub(crate) const fn m(singular: &'static str, plural: &'static str) -> Noun {
+    Noun { singular, plural, gender: Gender::Masculine }
+}
+
+pub(crate) const fn f(singular: &'static str, plural: &'static str) -> Noun {
+    Noun { singular, plural, gender: Gender::Feminine }
+}
+
+pub(crate) const fn n(singular: &'static str, plural: &'static str) -> Noun {
+    Noun { singular, plural, gender: Gender::Neuter }
+}
+
+/// An adjective's inflected forms and where it goes.
+///
+/// French and Spanish list the masculine singular, feminine singular,
+/// masculine plural and feminine plural. German lists the stem once and
+/// derives its endings from [`Grammar::adjective`].
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Adjective {
+    pub forms: [&'static str; 4],
+    /// Whether the adjective goes before the noun.
+    pub before: bool,
+}
+
+/// An adjective that follows the noun.
+pub(crate) const fn post(ms: &'static str, fs: &'static str, mp: &'static str, fp: &'static str) -> Adjective {
+    Adjective { forms: [ms, fs, mp, fp], before: false }
+}
+
+/// An adjective that precedes the noun.
+pub(crate) const fn pre(ms: &'static str, fs: &'static str, mp: &'static str, fp: &'static str) -> Adjective {
+    Adjective { forms: [ms, fs, mp, fp], before: true }
+}
+
+impl Adjective {
+    /// The form agreeing with a Romance-language noun.
+    pub(crate) fn agree(&self, gender: Gender, number: Number) -> &'static str {
+        let feminine = gender == Gender::Feminine;
+        match number {
+            Number::Singular => self.forms[feminine as usize],
+            Number::Plural => self.forms[2 + feminine as usize],
+        }
+    }
+}
+
+/// The words a language builds its sentences from.
+pub(crate) struct Lexicon {
+    pub animals: &'static [Noun],
+    pub professions: &'static [Noun],
+    pub objects: &'static [Noun],
+    pub adjectives: &'static [Adjective],
+    /// Third person singular present.
+    pub verbs: &'static [&'static str],
+    pub adverbs: &'static [&'static str],
+    pub openers: &'static [&'static str],
+    /// "versus", taking the second noun phrase.
+    pub versus: &'static str,
+    /// "since", followed by a year.
+    pub since: &'static str,
+    /// "next to", taking a dative noun phrase.
+    pub beside: &'static str,
+}
+
+/// How a language inflects noun phrases.
+pub(crate) trait Grammar {
+    const LEXICON: &'static Lexicon;
+
+    /// Whether "since <year>" comes before the object.
+    const TIME_BEFORE_OBJECT: bool = false;
+
+    /// The article for a noun phrase, if it takes one. `adjective_first` is
+    /// set when a pre-noun adjective follows the article.
+    fn article(article: Article, case: Case, number: Number, gender: Gender, adjective_first: bool)
+        -> Option<&'static str>;
+
+    /// The adjective form agreeing with the rest of the noun phrase.
+    fn adjective(adjective: &Adjective, article: Article, case: Case, number: Number, gender: Gender) -> String;
+
+    /// Apply elisions and contractions to the finished sentence.
+    fn contract(words: Vec<String>) -> Vec<String> {
+        words
+    }
+}
+
+/// Build `template` in `language` (anything but English).
+pub(crate) fn build(language: Language, template: &Template, b: &mut Builder<impl ChoiceSource>, min_words: usize) {
+    match language {
+        Language::English => unreachable!("English is built by the templates module"),
+        Language::French => build_in::<French>(template, b, min_words),
+        Language::German => build_in::<German>(template, b, min_words),
+        Language::Spanish => build_in::<Spanish>(template, b, min_words),
+    }
+}
+
+/// Append a phrase that lengthens the password.
+pub(crate) fn extend(language: Language, b: &mut Builder<impl ChoiceSource>) {
+    match language {
+        Language::English => unreachable!("English is extended by the templates module"),
+        Language::French => beside::<French>(b),
+        Language::German => beside::<German>(b),
+        Language::Spanish => beside::<Spanish>(b),
+    };
+}
+
+/// Apply `language`'s elisions and contractions.
+pub(crate) fn contract(language: Language, words: Vec<String>) -> Vec<String> {
+    match language {
+        Language::English => unreachable!("English articles are fixed by the templates module"),
+        Language::French => French::contract(words),
+        Language::German => German::contract(words),
+        Language::Spanish => Spanish::contract(words),
+    }
+}
+
+fn build_in<G: Grammar>(template: &Template, b: &mut Builder<impl ChoiceSource>, min_words: usize) {
+    let content = match template {
+        Template::Declarative => declarative::<G>(b, min_words),
+        Template::Versus => versus::<G>(b, min_words),
+        Template::Since => since::<G>(b, min_words),
+        _ => panic!("the {:?} template is only available in English", template),
+    };
+    let mut content = content;
+    while content < min_words {
+        content += beside::<G>(b);
+    }
+}
+
+/// "le pingouin grincheux mange sournoisement des cornichons"
+fn declarative<G: Grammar>(b: &mut Builder<impl ChoiceSource>, min_words: usize) -> usize {
+    let mut content = subject::<G>(b, Case::Nominative, min_words > 3, 0.6);
+    b.pick("present verb", G::LEXICON.verbs);
+    content += 1;
+    if content + 2 < min_words || b.chance("optional adverb", 0.5) {
+        b.pick("adverb", G::LEXICON.adverbs);
+        content += 1;
+    }
+    content + object::<G>(b)
+}
+
+/// "der müde Pinguin gegen einen lächerlichen Zahnarzt"
+fn versus<G: Grammar>(b: &mut Builder<impl ChoiceSource>, min_words: usize) -> usize {
+    let mut content = subject::<G>(b, Case::Nominative, min_words > 3, 0.4);
+    push_phrase(b, G::LEXICON.versus);
+    content += subject::<G>(b, Case::Accusative, min_words > 4, 0.4);
+    content
+}
+
+/// "el pingüino come pepinillos desde 1987"
+fn since<G: Grammar>(b: &mut Builder<impl ChoiceSource>, min_words: usize) -> usize {
+    let mut content = subject::<G>(b, Case::Nominative, min_words > 3, 0.5);
+    b.pick("present verb", G::LEXICON.verbs);
+    content += 1;
+    if G::TIME_BEFORE_OBJECT {
+        push_phrase(b, G::LEXICON.since);
+        b.year();
+        content + object::<G>(b)
+    } else {
+        content += object::<G>(b);
+        push_phrase(b, G::LEXICON.since);
+        b.year();
+        content
+    }
+}
+
+/// A definite or indefinite singular animal or professional, optionally
+/// with an adjective. Returns the number of content words.
+fn subject<G: Grammar>(b: &mut Builder<impl ChoiceSource>, case: Case, force_adjective: bool, p_adjective: f64) -> usize {
+    let article = if b.branch("article", 2) == 0 { Article::Definite } else { Article::Indefinite };
+    let adjective = choose_adjective::<G>(b, force_adjective, p_adjective);
+    let (label, list) = match b.branch("subject kind", 2) {
+        0 => ("animal", G::LEXICON.animals),
+        _ => ("profession", G::LEXICON.professions),
+    };
+    let noun = &list[b.branch(label, list.len())];
+    push_noun_phrase::<G>(b, Some(article), case, Number::Singular, adjective, noun, label)
+}
+
+/// An indefinite plural object: "des cornichons", "rostige Toaster".
+fn object<G: Grammar>(b: &mut Builder<impl ChoiceSource>) -> usize {
+    let adjective = choose_adjective::<G>(b, false, 0.3);
+    let objects = G::LEXICON.objects;
+    let noun = &objects[b.branch("object", objects.len())];
+    push_noun_phrase::<G>(b, Some(Article::Indefinite), Case::Accusative, Number::Plural, adjective, noun, "object")
+}
+
+/// "à côté du lama grincheux". Always two content words.
+fn beside<G: Grammar>(b: &mut Builder<impl ChoiceSource>) -> usize {
+    push_phrase(b, G::LEXICON.beside);
+    let article = if b.branch("article", 2) == 0 { Article::Definite } else { Article::Indefinite };
+    let adjective = choose_adjective::<G>(b, true, 1.0);
+    let animals = G::LEXICON.animals;
+    let noun = &animals[b.branch("animal", animals.len())];
+    push_noun_phrase::<G>(b, Some(article), Case::Dative, Number::Singular, adjective, noun, "animal")
+}
+
+fn choose_adjective<G: Grammar>(
+    b: &mut Builder<impl ChoiceSource>,
+    force: bool,
+    p: f64,
+) -> Option<&'static Adjective> {
+    if force || b.chance("optional adjective", p) {
+        let adjectives = G::LEXICON.adjectives;
+        Some(&adjectives[b.branch("adjective", adjectives.len())])
+    } else {
+        None
+    }
+}
+
+fn push_noun_phrase<G: Grammar>(
+    b: &mut Builder<impl ChoiceSource>,
+    article: Option<Article>,
+    case: Case,
+    number: Number,
+    adjective: Option<&Adjective>,
+    noun: &Noun,
+    label: &'static str,
+) -> usize {
+    let before = adjective.is_some_and(|a| a.before);
+    if let Some(article) = article {
+        if let Some(word) = G::article(article, case, number, noun.gender, before) {
+            b.push_labeled(word, "article");
+        }
+    }
+    let adjective = adjective.map(|a| G::adjective(a, article.unwrap_or(Article::Indefinite), case, number, noun.gender));
+    if before {
+        b.push_labeled(adjective.as_deref().unwrap(), "adjective");
+    }
+    b.push_labeled(noun.form(number), label);
+    if let (false, Some(adjective)) = (before, &adjective) {
+        b.push_labeled(adjective, "adjective");
+    }
+    1 + adjective.is_some() as usize
+}
+
+/// Append a fixed, possibly multi-word phrase.
+fn push_phrase(b: &mut Builder<impl ChoiceSource>, phrase: &str) {
+    for word in phrase.split_whitespace() {
+        b.push(word);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::WordConfig;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn config(language: Language) -> WordConfig {
+        WordConfig { language, ..WordConfig::default() }
+    }
+
+    fn strings(words: &[&str]) -> Vec<String> {
+        words.iter().map(|w| w.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parses_codes_and_names() {
+        assert_eq!("fr".parse::<Language>(), Ok(Language::French));
+        assert_eq!("Deutsch".parse::<Language>(), Ok(Language::German));
+        assert_eq!("español".parse::<Language>(), Ok(Language::Spanish));
+        assert_eq!("EN".parse::<Language>(), Ok(Language::English));
+        assert!("klingon".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_each_language_generates_its_templates() {
+        let mut rng = StdRng::seed_from_u64(13);
+        for language in [Language::French, Language::German, Language::Spanish] {
+            for template in language.templates() {
+                for min_words in [0, 4, 7] {
+                    let password = template.generate(&mut rng, min_words, &config(language)).unwrap();
+                    assert!(!password.words.is_empty());
+                    assert!(password.words.iter().all(|w| !w.is_empty() && !w.contains(' ')));
+                    assert!(password.entropy() > 0.0);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_min_entropy_is_met() {
+        let mut rng = StdRng::seed_from_u64(14);
+        for language in [Language::French, Language::German, Language::Spanish] {
+            for template in language.templates() {
+                let password = template.generate_with_min_entropy(&mut rng, 60.0, &config(language)).unwrap();
+                assert!(password.entropy() >= 60.0, "{:?} {:?}", language, password.words);
+            }
+        }
+    }
+
+    #[test]
+    fn test_french_elision_and_contraction() {
+        let words = French::contract(strings(&["à", "côté", "de", "le", "escargot", "de", "les", "huîtres"]));
+        assert_eq!(words, strings(&["à", "côté", "de", "l'escargot", "des", "huîtres"]));
+        let words = French::contract(strings(&["à", "côté", "de", "le", "lama"]));
+        assert_eq!(words, strings(&["à", "côté", "du", "lama"]));
+        assert_eq!(French::article(Article::Indefinite, Case::Accusative, Number::Plural, Gender::Masculine, true), Some("de"));
+        assert_eq!(French::article(Article::Indefinite, Case::Accusative, Number::Plural, Gender::Masculine, false), Some("des"));
+    }
+
+    #[test]
+    fn test_german_declension() {
+        let adjective = Adjective { forms: ["müd", "", "", ""], before: true };
+        let decline = |article, case, gender| {
+            let word = German::article(article, case, Number::Singular, gender, true);
+            (word, German::adjective(&adjective, article, case, Number::Singular, gender))
+        };
+        assert_eq!(decline(Article::Definite, Case::Nominative, Gender::Masculine), (Some("der"), "müde".to_string()));
+        assert_eq!(decline(Article::Indefinite, Case::Accusative, Gender::Masculine), (Some("einen"), "müden".to_string()));
+        assert_eq!(decline(Article::Indefinite, Case::Nominative, Gender::Neuter), (Some("ein"), "müdes".to_string()));
+        assert_eq!(decline(Article::Definite, Case::Dative, Gender::Feminine), (Some("der"), "müden".to_string()));
+        assert_eq!(German::adjective(&adjective, Article::Indefinite, Case::Accusative, Number::Plural, Gender::Neuter), "müde");
+    }
+
+    #[test]
+    fn test_spanish_contraction() {
+        let words = Spanish::contract(strings(&["delante", "de", "el", "pulpo", "de", "la", "cabra"]));
+        assert_eq!(words, strings(&["delante", "del", "pulpo", "de", "la", "cabra"]));
+    }
+
+    #[test]
+    fn test_lexicons_have_no_duplicates() {
+        for lexicon in [French::LEXICON, German::LEXICON, Spanish::LEXICON] {
+            let nouns: Vec<_> = lexicon.animals.iter().chain(lexicon.professions).map(|n| n.singular).collect();
+            let mut seen = std::collections::HashSet::new();
+            for word in nouns.iter().chain(lexicon.objects.iter().map(|n| &n.singular)) {
+                assert!(seen.insert(*word), "{} appears twice", word);
+            }
+            let adjectives: std::collections::HashSet<_> = lexicon.adjectives.iter().map(|a| a.forms[0]).collect();
+            assert_eq!(adjectives.len(), lexicon.adjectives.len());
+            let verbs: std::collections::HashSet<_> = lexicon.verbs.iter().collect();
+            assert_eq!(verbs.len(), lexicon.verbs.len());
+            let adverbs: std::collections::HashSet<_> = lexicon.adverbs.iter().collect();
+            assert_eq!(adverbs.len(), lexicon.adverbs.len());
+        }
+    }
+}
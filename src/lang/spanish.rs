@@ -0,0 +1,188 @@
+//! Spanish: gendered articles, bare indefinite plurals, the "del"/"al"
+//! contractions, and adjectives after the noun apart from a couple that
+//! precede it.
+
+use super::{f, m, post, pre, Adjective, Article, Case, Gender, Grammar, Lexicon, Noun, Number};
+
+pub(crate) struct Spanish;
+
+const ANIMALS: &[Noun] = &[
+    m("pingüino", "pingüinos"),
+    f("llama", "llamas"),
+    m("tejón", "tejones"),
+    f("nutria", "nutrias"),
+    m("caracol", "caracoles"),
+    f("cabra", "cabras"),
+    m("hipopótamo", "hipopótamos"),
+    m("topo", "topos"),
+    f("morsa", "morsas"),
+    f("gallina", "gallinas"),
+    m("pato", "patos"),
+    m("perezoso", "perezosos"),
+    f("rana", "ranas"),
+    f("ardilla", "ardillas"),
+    f("gaviota", "gaviotas"),
+    m("mapache", "mapaches"),
+    m("camello", "camellos"),
+    m("avestruz", "avestruces"),
+    m("ornitorrinco", "ornitorrincos"),
+    f("ballena", "ballenas"),
+    m("flamenco", "flamencos"),
+    f("tortuga", "tortugas"),
+    m("koala", "koalas"),
+    f("comadreja", "comadrejas"),
+    f("foca", "focas"),
+    m("ratón", "ratones"),
+    m("sapo", "sapos"),
+    f("vaca", "vacas"),
+    m("pulpo", "pulpos"),
+    m("erizo", "erizos"),
+];
+
+const PROFESSIONS: &[Noun] = &[
+    m("dentista", "dentistas"),
+    m("contable", "contables"),
+    m("fontanero", "fontaneros"),
+    f("panadera", "panaderas"),
+    m("notario", "notarios"),
+    f("bibliotecaria", "bibliotecarias"),
+    m("cartero", "carteros"),
+    f("ministra", "ministras"),
+    m("bombero", "bomberos"),
+    f("portera", "porteras"),
+    m("jardinero", "jardineros"),
+    f("peluquera", "peluqueras"),
+    m("astronauta", "astronautas"),
+    f("bruja", "brujas"),
+    m("mago", "magos"),
+    f("cirujana", "cirujanas"),
+    m("becario", "becarios"),
+    m("aduanero", "aduaneros"),
+    f("presidenta", "presidentas"),
+    m("sumiller", "sumilleres"),
+    m("pirata", "piratas"),
+    f("detective", "detectives"),
+    m("cocinero", "cocineros"),
+    f("farmacéutica", "farmacéuticas"),
+    m("archivero", "archiveros"),
+    m("alcalde", "alcaldes"),
+];
+
+const OBJECTS: &[Noun] = &[
+    m("pepinillo", "pepinillos"),
+    m("calcetín", "calcetines"),
+    f("tostadora", "tostadoras"),
+    m("paraguas", "paraguas"),
+    f("cacerola", "cacerolas"),
+    m("queso", "quesos"),
+    f("trompeta", "trompetas"),
+    m("cruasán", "cruasanes"),
+    f("carretilla", "carretillas"),
+    m("destornillador", "destornilladores"),
+    f("grapadora", "grapadoras"),
+    m("clip", "clips"),
+    m("bigote", "bigotes"),
+    m("acordeón", "acordeones"),
+    f("zapatilla", "zapatillas"),
+    m("rábano", "rábanos"),
+    f("tortilla", "tortillas"),
+    f("vela", "velas"),
+    f("galleta", "galletas"),
+    m("tenedor", "tenedores"),
+    f("tuba", "tubas"),
+    f("silla", "sillas"),
+    m("nabo", "nabos"),
+    f("ostra", "ostras"),
+    m("globo", "globos"),
+    f("empanada", "empanadas"),
+    m("churro", "churros"),
+];
+
+const ADJECTIVES: &[Adjective] = &[
+    pre("gran", "gran", "grandes", "grandes"),
+    pre("viejo", "vieja", "viejos", "viejas"),
+    post("gruñón", "gruñona", "gruñones", "gruñonas"),
+    post("ridículo", "ridícula", "ridículos", "ridículas"),
+    post("misterioso", "misteriosa", "misteriosos", "misteriosas"),
+    post("cansado", "cansada", "cansados", "cansadas"),
+    post("parlanchín", "parlanchina", "parlanchines", "parlanchinas"),
+    post("torpe", "torpe", "torpes", "torpes"),
+    post("furioso", "furiosa", "furiosos", "furiosas"),
+    post("legendario", "legendaria", "legendarios", "legendarias"),
+    post("hambriento", "hambrienta", "hambrientos", "hambrientas"),
+    post("friolero", "friolera", "frioleros", "frioleras"),
+    post("presumido", "presumida", "presumidos", "presumidas"),
+    post("pegajoso", "pegajosa", "pegajosos", "pegajosas"),
+    post("sospechoso", "sospechosa", "sospechosos", "sospechosas"),
+    post("despistado", "despistada", "despistados", "despistadas"),
+    post("radiactivo", "radiactiva", "radiactivos", "radiactivas"),
+    post("oxidado", "oxidada", "oxidados", "oxidadas"),
+    post("astuto", "astuta", "astutos", "astutas"),
+    post("calvo", "calva", "calvos", "calvas"),
+    post("tímido", "tímida", "tímidos", "tímidas"),
+    post("dormilón", "dormilona", "dormilones", "dormilonas"),
+    post("pomposo", "pomposa", "pomposos", "pomposas"),
+    post("nervioso", "nerviosa", "nerviosos", "nerviosas"),
+    post("glotón", "glotona", "glotones", "glotonas"),
+];
+
+const VERBS: &[&str] = &[
+    "come", "roba", "esconde", "colecciona", "lame", "olfatea", "traga", "critica", "negocia", "confisca",
+    "decora", "adopta", "secuestra", "espía", "pinta", "vende", "compra", "grava", "cocina", "examina", "repara",
+    "envuelve", "apila", "bendice", "recicla", "fotografía", "interroga",
+];
+
+const ADVERBS: &[&str] = &[
+    "sigilosamente", "alegremente", "lentamente", "ruidosamente", "secretamente", "tímidamente", "furiosamente",
+    "graciosamente", "torpemente", "orgullosamente", "perezosamente", "discretamente", "nerviosamente",
+    "educadamente", "brutalmente", "tiernamente", "raramente", "solemnemente", "distraídamente", "glotonamente",
+];
+
+const OPENERS: &[&str] = &[
+    "Atención", "Cuidado", "Increíble", "Sinceramente", "Evidentemente", "Curiosamente", "Afortunadamente",
+    "Lamentablemente", "Aparentemente", "Caramba", "Ojo", "Vaya",
+];
+
+impl Grammar for Spanish {
+    const LEXICON: &'static Lexicon = &Lexicon {
+        animals: ANIMALS,
+        professions: PROFESSIONS,
+        objects: OBJECTS,
+        adjectives: ADJECTIVES,
+        verbs: VERBS,
+        adverbs: ADVERBS,
+        openers: OPENERS,
+        versus: "contra",
+        since: "desde",
+        beside: "delante de",
+    };
+
+    fn article(article: Article, _case: Case, number: Number, gender: Gender, _adjective_first: bool) -> Option<&'static str> {
+        let feminine = gender == Gender::Feminine;
+        match (article, number) {
+            (Article::Definite, Number::Singular) => Some(if feminine { "la" } else { "el" }),
+            (Article::Definite, Number::Plural) => Some(if feminine { "las" } else { "los" }),
+            (Article::Indefinite, Number::Singular) => Some(if feminine { "una" } else { "un" }),
+            // Indefinite plurals are bare: "come pepinillos".
+            (Article::Indefinite, Number::Plural) => None,
+        }
+    }
+
+    fn adjective(adjective: &Adjective, _article: Article, _case: Case, number: Number, gender: Gender) -> String {
+        adjective.agree(gender, number).to_string()
+    }
+
+    fn contract(words: Vec<String>) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+        for word in words {
+            if word == "el" {
+                if let Some(last) = result.last_mut().filter(|last| *last == "de" || *last == "a") {
+                    *last = if last == "de" { "del" } else { "al" }.to_string();
+                    continue;
+                }
+            }
+            result.push(word);
+        }
+        result
+    }
+}
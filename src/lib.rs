@@ -1,5 +1,27 @@
+pub mod article;
+mod cache;
+pub mod compound;
+pub mod denylist;
+pub mod encoding;
 pub mod entropy;
+pub mod formatting;
 pub mod generator;
+pub mod lang;
+pub mod memorability;
+pub mod mix;
+pub mod morphology;
+pub mod parser;
+pub mod password;
+pub mod pattern;
+pub mod plural;
+pub mod policy;
+pub mod rating;
+pub mod rhyme;
+pub mod selection;
 pub mod templates;
+pub mod themes;
 pub mod transitions;
+pub mod wordlists;
+pub mod wordinfo;
 pub mod words;
+pub mod wordset;
@@ -0,0 +1,225 @@
+//! Favouring familiar, vivid words over obscure ones.
+//!
+//! Every entry gets a memorability score between 0 and 1. Entries in
+//! [`COMMON`] (everyday, concrete words you can picture: "penguin",
+//! "pickles", "wobbly") score 1, entries in [`OBSCURE`] ("hornswoggle",
+//! "apoplectic") score 0, and the rest are scored from their [`WordInfo`]:
+//! long, many-syllabled and hard-to-spell words score lower. Verb forms are
+//! looked up by their lemma, so "hornswoggles" is as obscure as "hornswoggle".
+//!
+//! With a memorability of `m`, an entry with score `s` is drawn with weight
+//! `2^(SPREAD * m * s)`: at `m = 1` the most familiar words are eight times as
+//! likely as the most obscure ones, and at `m = 0` every entry is equally
+//! likely.
+//!
+//! # Entropy
+//!
+//! A weighted draw is worth less than `log2(len)` bits. Its Shannon entropy
+//! is what an attacker gets on average, and its min-entropy,
+//! `-log2(largest weight / total weight)`, is what an attacker who tries the
+//! likeliest words first is guaranteed to face. Templates record every
+//! weighted pick with the min-entropy of its list, so the reported bits stay
+//! a lower bound, and picking the strongest candidates doesn't quietly
+//! prefer obscure words.
+
+use std::sync::{Arc, Mutex};
+
+use rand::distributions::WeightedIndex;
+
+use crate::morphology::lemma;
+use crate::wordinfo::WordInfo;
+
+/// How far apart, in bits, the weights of the most and least memorable
+/// entries are at full memorability.
+pub const SPREAD: f64 = 3.0;
+
+/// Everyday words that are easy to picture.
+pub const COMMON: &[&str] = &[
+    // Adjectives
+    "grumpy", "wobbly", "lumpy", "crunchy", "squishy", "sleepy", "fluffy", "sneaky", "fuzzy", "jiggly",
+    "wrinkly", "prickly", "stubby", "pickled", "haunted", "cursed", "sunburned",
+    // Animals
+    "penguin", "goose", "pigeon", "squirrel", "walrus", "otter", "octopus", "llama", "hippo", "koala",
+    "kangaroo", "sloth", "raccoon", "badger", "beaver", "owl", "crab", "dragon", "unicorn", "hamster",
+    "parrot", "flamingo", "toad", "pug", "corgi", "panda", "dolphin", "bear", "seal", "moth",
+    // Professions
+    "wizard", "pirate", "ninja", "viking", "chef", "baker", "butcher", "plumber", "doctor", "lawyer",
+    "accountant", "librarian", "detective", "astronaut", "mechanic", "poet", "painter", "tailor", "mime",
+    "monk",
+    // Nouns and objects
+    "chaos", "gravity", "wifi", "pickles", "bagels", "waffles", "pancakes", "socks", "sandwiches",
+    "bananas", "umbrellas", "donuts", "tacos", "muffins", "noodles", "mittens", "cupcakes", "toasters",
+    // Verbs
+    "juggle", "dance", "jump", "throw", "catch", "kick", "punch", "munch", "nibble", "slurp",
+    "gobble", "waddle", "wobble", "tiptoe", "sneak", "climb", "spin", "roll", "yodel", "toss",
+    // Adverbs
+    "quietly", "happily", "sadly", "angrily",
+];
+
+/// Real words that most people would have to look up.
+pub const OBSCURE: &[&str] = &[
+    // Adjectives
+    "apoplectic", "obsequious", "sanctimonious", "pedantic", "contrarian", "gelatinous", "bulbous",
+    "necromantic", "infernal", "accursed", "rotund", "despondent", "perturbed", "scandalized",
+    // Animals
+    "hoatzin", "nudibranch", "tarsier", "geoduck", "solenodon", "binturong", "tenrec", "dhole",
+    "babirusa", "gerenuk", "gharial", "tuatara", "markhor", "colugo", "olinguito", "uakari", "muntjac",
+    "chevrotain", "caecilian", "simurgh", "jorogumo", "fenghuang", "barghest", "zorilla", "desman",
+    "hutia",
+    // Professions
+    "cordwainer", "ostler", "chandler", "milliner", "haberdasher", "thaumaturge", "artificer",
+    "farrier", "wheelwright", "fletcher", "docent", "oenologist", "etymologist", "tosher", "mudlark",
+    // Nouns
+    "eschaton", "primordium", "obsequy", "languor", "torpor", "miasma", "perdition", "augury",
+    "portent", "revenant", "phantasm", "rigmarole", "codswallop", "bunkum", "solipsism", "monism",
+    // Verbs
+    "hornswoggle", "discombobulate", "flabbergast", "gobsmack", "stupefy", "connive", "legislate",
+    "notarize", "administrate", "fabricate", "vamoose", "canoodle", "lollygag", "skedaddle",
+];
+
+/// How memorable `entry` is, from 0 (obscure) to 1 (familiar and vivid).
+pub fn score(entry: &str) -> f64 {
+    let listed = |table: &[&str]| table.contains(&entry) || lemma(entry).is_some_and(|lemma| table.contains(&lemma));
+    if listed(COMMON) {
+        return 1.0;
+    }
+    if listed(OBSCURE) {
+        return 0.0;
+    }
+
+    let info = WordInfo::of(entry);
+    let mut score = 1.0;
+    score -= 0.15 * info.syllables.saturating_sub(2) as f64;
+    score -= 0.2 * info.difficulty as usize as f64;
+    if info.len > 8 {
+        score -= 0.1;
+    }
+    if info.hyphenated {
+        score -= 0.1;
+    }
+    // Words only known from their spelling stay between the two tables
+    score.clamp(0.1, 0.9)
+}
+
+/// The draw weight of every entry of `list` at `memorability` (0 to 1).
+pub fn weights(list: &[impl AsRef<str>], memorability: f64) -> Vec<f64> {
+    list.iter().map(|entry| (SPREAD * memorability * score(entry.as_ref())).exp2()).collect()
+}
+
+/// Most lists [`Draw::of`] keeps the draw of.
+const DRAWS_KEPT: usize = 64;
+
+/// The draws of recently used lists, most recently used first.
+static DRAWS: Mutex<Vec<Arc<Draw>>> = Mutex::new(Vec::new());
+
+/// A weighted draw from one list, built once and shared by every pick from it.
+#[derive(Debug)]
+pub(crate) struct Draw {
+    list: Vec<Box<str>>,
+    memorability: f64,
+    /// Samples an index of the list by weight.
+    pub(crate) index: WeightedIndex<f64>,
+    /// The chance of the likeliest entry, which a pick is recorded at.
+    pub(crate) largest: f64,
+}
+
+impl Draw {
+    /// The draw from `list` at `memorability`, built the first time the list
+    /// is drawn from.
+    pub(crate) fn of(list: &[impl AsRef<str>], memorability: f64) -> Arc<Draw> {
+        let same = |draw: &Draw| {
+            draw.memorability == memorability
+                && draw.list.len() == list.len()
+                && draw.list.iter().zip(list).all(|(a, b)| **a == *b.as_ref())
+        };
+        let mut draws = DRAWS.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(i) = draws.iter().position(|draw| same(draw)) {
+            let draw = draws.remove(i);
+            draws.insert(0, Arc::clone(&draw));
+            return draw;
+        }
+        let weights = weights(list, memorability);
+        let largest = weights.iter().copied().fold(0.0, f64::max) / weights.iter().sum::<f64>();
+        let index = WeightedIndex::new(&weights).expect("weights are positive");
+        let list = list.iter().map(|entry| entry.as_ref().into()).collect();
+        let draw = Arc::new(Draw { list, memorability, index, largest });
+        draws.truncate(DRAWS_KEPT - 1);
+        draws.insert(0, Arc::clone(&draw));
+        draw
+    }
+}
+
+/// How many bits a draw from a list is worth, uniformly and with weights.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ListEntropy {
+    /// `log2(len)`: a uniform draw.
+    pub uniform: f64,
+    /// Shannon entropy of the weighted draw: what it's worth on average.
+    pub shannon: f64,
+    /// Min-entropy of the weighted draw: what it's worth at worst.
+    pub min: f64,
+}
+
+impl ListEntropy {
+    /// The entropy of a draw from `list` at `memorability`.
+    pub fn of(list: &[impl AsRef<str>], memorability: f64) -> ListEntropy {
+        let weights = weights(list, memorability);
+        let total: f64 = weights.iter().sum();
+        let largest = weights.iter().copied().fold(0.0, f64::max);
+        ListEntropy {
+            uniform: (list.len() as f64).log2(),
+            shannon: weights.iter().map(|w| w / total).map(|p| -p * p.log2()).sum(),
+            min: if total > 0.0 { (total / largest).log2() } else { 0.0 },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wordlists::LISTS;
+
+    #[test]
+    fn test_scores_familiar_words_above_obscure_ones() {
+        assert_eq!(score("penguin"), 1.0);
+        assert_eq!(score("hornswoggle"), 0.0);
+        assert_eq!(score("hornswoggles"), 0.0);
+        assert!(score("walrus") > score("thaumaturge"));
+        assert!(score("cat") > score("psychologist"));
+    }
+
+    #[test]
+    fn test_tables_only_hold_listed_words() {
+        for word in COMMON.iter().chain(OBSCURE) {
+            assert!(LISTS.iter().any(|list| list.words.contains(word)), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_no_memorability_is_uniform() {
+        let entropy = ListEntropy::of(&["penguin", "hornswoggle", "wizard", "apoplectic"], 0.0);
+        assert!((entropy.uniform - 2.0).abs() < 1e-9);
+        assert!((entropy.shannon - 2.0).abs() < 1e-9);
+        assert!((entropy.min - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_weighting_costs_bits() {
+        let list = &["penguin", "hornswoggle", "wizard", "apoplectic"];
+        let weights = weights(list, 1.0);
+        assert_eq!(weights[0] / weights[1], 8.0);
+        let entropy = ListEntropy::of(list, 1.0);
+        assert!(entropy.min < entropy.shannon && entropy.shannon < entropy.uniform);
+        assert!((entropy.min - (18.0_f64 / 8.0).log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_draws_are_built_once_per_list() {
+        let list = vec!["penguin", "hornswoggle", "wizard", "apoplectic"];
+        let draw = Draw::of(&list, 1.0);
+        assert!(Arc::ptr_eq(&draw, &Draw::of(&list, 1.0)));
+        assert!(!Arc::ptr_eq(&draw, &Draw::of(&list, 0.5)));
+        assert!(!Arc::ptr_eq(&draw, &Draw::of(&list[..3], 1.0)));
+        assert!((draw.largest - 8.0 / 18.0).abs() < 1e-9);
+    }
+}
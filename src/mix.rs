@@ -0,0 +1,195 @@
+//! Weighted choice between templates.
+//!
+//! A [`TemplateMix`] gives each template a weight, and a random template is
+//! drawn with a chance proportional to it. Templates with no weight are
+//! never drawn. On the command line a mix is written as a comma-separated
+//! list:
+//!
+//! ```text
+//! declarative:3,question:2     only these two, declarative half as likely again
+//! question                     only questions (a weight of 1)
+//! !versus,!since               everything but versus and since
+//! declarative:3,*,!versus      declarative three times as likely as the rest, never versus
+//! ```
+//!
+//! `*` stands for every template not named, with weight 1 unless given one.
+//! A list with nothing but `!` entries allows the rest.
+//!
+//! # Entropy
+//!
+//! Like a memorability-weighted word ([`crate::memorability`]), a weighted
+//! template choice is recorded at its min-entropy, `log2(total / largest)`:
+//! the number of equally likely templates it is worth. Picking the strongest
+//! candidates then doesn't quietly favour the rarer templates.
+
+use std::fmt;
+use std::str::FromStr;
+
+use rand::distributions::WeightedIndex;
+use rand::Rng;
+
+use crate::password::Decision;
+use crate::templates::Template;
+
+/// How likely each template is to be drawn.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemplateMix {
+    /// Every template that can be drawn, with its (positive) weight.
+    weights: Vec<(Template, f64)>,
+}
+
+impl TemplateMix {
+    /// Every one of `templates`, equally likely.
+    pub fn uniform(templates: &[Template]) -> Self {
+        Self { weights: templates.iter().map(|t| (t.clone(), 1.0)).collect() }
+    }
+
+    /// The mix with `template` drawn at `weight`. A weight of zero leaves it out.
+    pub fn with_weight(mut self, template: Template, weight: f64) -> Self {
+        self.weights.retain(|(t, _)| *t != template);
+        if weight > 0.0 {
+            self.weights.push((template, weight));
+        }
+        self
+    }
+
+    /// The mix without `template`.
+    pub fn without(self, template: Template) -> Self {
+        self.with_weight(template, 0.0)
+    }
+
+    /// The weight of `template`, zero if it is never drawn.
+    pub fn weight(&self, template: &Template) -> f64 {
+        self.weights.iter().find(|(t, _)| t == template).map_or(0.0, |&(_, weight)| weight)
+    }
+
+    /// The templates that can be drawn.
+    pub fn templates(&self) -> Vec<Template> {
+        self.weights.iter().map(|(t, _)| t.clone()).collect()
+    }
+
+    /// The bits a choice among `available` is counted at: its min-entropy.
+    pub fn bits(&self, available: &[Template]) -> f64 {
+        self.decision(available).bits()
+    }
+
+    /// The decision a choice among `available` is recorded as.
+    pub fn decision(&self, available: &[Template]) -> Decision {
+        let weights: Vec<f64> = available.iter().map(|t| self.weight(t)).collect();
+        let total: f64 = weights.iter().sum();
+        let largest = weights.iter().copied().fold(0.0, f64::max);
+        Decision::new("template", largest / total)
+    }
+
+    /// Draw one of `available` by weight, recording the choice at its
+    /// min-entropy.
+    ///
+    /// # Panics
+    ///
+    /// If none of `available` is in the mix.
+    pub fn choose(&self, rng: &mut impl Rng, available: &[Template]) -> (Template, Decision) {
+        let weights: Vec<f64> = available.iter().map(|t| self.weight(t)).collect();
+        let index = rng.sample(WeightedIndex::new(&weights).expect("a template in the mix is available"));
+        (available[index].clone(), self.decision(available))
+    }
+}
+
+impl FromStr for TemplateMix {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut named = Vec::new();
+        let mut rest = None;
+        let mut allowed = false;
+        for entry in s.split(',').map(str::trim).filter(|entry| !entry.is_empty()) {
+            if let Some(name) = entry.strip_prefix('!') {
+                named.push((template(name.trim())?, 0.0));
+                continue;
+            }
+            allowed = true;
+            let (name, weight) = match entry.split_once(':') {
+                Some((name, weight)) => (name.trim(), parse_weight(weight.trim())?),
+                None => (entry, 1.0),
+            };
+            if name == "*" {
+                rest = Some(weight);
+            } else {
+                named.push((template(name)?, weight));
+            }
+        }
+        // Only exclusions: everything else stays in
+        let rest = rest.unwrap_or(if allowed { 0.0 } else { 1.0 });
+
+        let mut mix = TemplateMix::uniform(&[]);
+        for template in Template::ALL {
+            if !named.iter().any(|(t, _)| t == template) {
+                mix = mix.with_weight(template.clone(), rest);
+            }
+        }
+        for (template, weight) in named {
+            mix = mix.with_weight(template, weight);
+        }
+        if mix.weights.is_empty() {
+            return Err(format!("The template mix '{}' leaves no template to draw", s));
+        }
+        Ok(mix)
+    }
+}
+
+impl fmt::Display for TemplateMix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let entries: Vec<String> = self.weights.iter().map(|(t, weight)| format!("{}:{}", t.name(), weight)).collect();
+        f.write_str(&entries.join(","))
+    }
+}
+
+fn template(name: &str) -> Result<Template, String> {
+    Template::from_name(name).ok_or_else(|| format!("Unknown template '{}' in the template mix", name))
+}
+
+fn parse_weight(weight: &str) -> Result<f64, String> {
+    match weight.parse::<f64>() {
+        Ok(w) if w.is_finite() && w >= 0.0 => Ok(w),
+        _ => Err(format!("Invalid template weight '{}' (expected a number of at least 0)", weight)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_weights_exclusions_and_the_rest() {
+        let mix: TemplateMix = "declarative:3,question:2".parse().unwrap();
+        assert_eq!(mix.templates(), [Template::Declarative, Template::Question]);
+        assert_eq!(mix.weight(&Template::Declarative), 3.0);
+        assert_eq!(mix.weight(&Template::Versus), 0.0);
+
+        let mix: TemplateMix = "!versus, !since".parse().unwrap();
+        assert_eq!(mix.templates().len(), Template::ALL.len() - 2);
+        assert_eq!(mix.weight(&Template::Since), 0.0);
+
+        let mix: TemplateMix = "declarative:3,*:0.5,!versus".parse().unwrap();
+        assert_eq!(mix.weight(&Template::Declarative), 3.0);
+        assert_eq!(mix.weight(&Template::Requires), 0.5);
+        assert_eq!(mix.weight(&Template::Versus), 0.0);
+    }
+
+    #[test]
+    fn test_rejects_bad_mixes() {
+        assert!("llama:2".parse::<TemplateMix>().is_err());
+        assert!("question:-1".parse::<TemplateMix>().is_err());
+        assert!("question:lots".parse::<TemplateMix>().is_err());
+        assert!("question:0".parse::<TemplateMix>().is_err());
+    }
+
+    #[test]
+    fn test_choice_is_counted_at_min_entropy() {
+        let mix: TemplateMix = "declarative:3,question:1".parse().unwrap();
+        let available = mix.templates();
+        assert!((mix.bits(&available) - (4.0_f64 / 3.0).log2()).abs() < 1e-9);
+        let (_, decision) = mix.choose(&mut rand::thread_rng(), &available);
+        assert!((decision.bits() - mix.bits(&available)).abs() < 1e-9);
+        assert!((TemplateMix::uniform(Template::ALL).bits(Template::ALL) - (Template::ALL.len() as f64).log2()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,239 @@
+//! Verb forms derived from a single lemma.
+//!
+//! The verb lists hold one lemma per verb ("juggle"). The third-person
+//! ("juggles"), gerund ("juggling"), past ("juggled") and agent-noun
+//! ("juggler") forms are derived from it by English spelling rules, with
+//! [`IRREGULAR`] covering the words the rules get wrong. Adding a verb is one
+//! entry in [`crate::words::BASE_VERBS`]; every template and name style picks
+//! up all of its forms.
+//!
+//! The final consonant is doubled before a suffix ("plotting") only for
+//! one-syllable lemmas, since stress can't be read from spelling; longer
+//! lemmas that double are listed in [`DOUBLED`].
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+use crate::words::{BASE_VERBS, BASE_VERBS_NSFW};
+
+/// A form of a verb.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Form {
+    /// The lemma itself: "juggle".
+    Base,
+    /// Third person singular present: "juggles".
+    ThirdPerson,
+    /// The "-ing" form: "juggling".
+    Gerund,
+    /// Simple past: "juggled".
+    Past,
+    /// The noun for someone who does it: "juggler".
+    Agent,
+}
+
+impl Form {
+    /// Every form, lemma first.
+    pub const ALL: &'static [Form] = &[Form::Base, Form::ThirdPerson, Form::Gerund, Form::Past, Form::Agent];
+}
+
+/// Forms the spelling rules get wrong: `(lemma, form, word)`.
+pub const IRREGULAR: &[(&str, Form, &str)] = &[
+    // Irregular pasts
+    ("broadcast", Form::Past, "broadcast"),
+    ("build", Form::Past, "built"),
+    ("catch", Form::Past, "caught"),
+    ("dive", Form::Past, "dove"),
+    ("fling", Form::Past, "flung"),
+    ("forecast", Form::Past, "forecast"),
+    ("leap", Form::Past, "leapt"),
+    ("overthink", Form::Past, "overthought"),
+    ("run", Form::Past, "ran"),
+    ("shake", Form::Past, "shook"),
+    ("slide", Form::Past, "slid"),
+    ("spin", Form::Past, "spun"),
+    ("swing", Form::Past, "swung"),
+    ("throw", Form::Past, "threw"),
+    // Agent nouns that aren't "-er"
+    ("analyze", Form::Agent, "analyst"),
+    ("archive", Form::Agent, "archivist"),
+    ("assess", Form::Agent, "assessor"),
+    ("audit", Form::Agent, "auditor"),
+    ("collect", Form::Agent, "collector"),
+    ("combust", Form::Agent, "combustor"),
+    ("conspire", Form::Agent, "conspirator"),
+    ("debate", Form::Agent, "debater"),
+    ("inspect", Form::Agent, "inspector"),
+    ("inventory", Form::Agent, "stocktaker"),
+    ("perfect", Form::Agent, "perfectionist"),
+    ("philosophize", Form::Agent, "philosopher"),
+    ("practice", Form::Agent, "practitioner"),
+    ("reflect", Form::Agent, "reflector"),
+    ("sculpt", Form::Agent, "sculptor"),
+    ("sleuth", Form::Agent, "sleuth"),
+    ("sneak", Form::Agent, "sneak"),
+    ("spy", Form::Agent, "spy"),
+    ("theorize", Form::Agent, "theorist"),
+];
+
+/// Lemmas of more than one syllable that double their final consonant.
+pub const DOUBLED: &[&str] = &["eavesdrop", "lollygag"];
+
+/// The `form` of `lemma`. For a phrasal lemma ("clash with") only the first
+/// word is inflected.
+pub fn inflect(lemma: &str, form: Form) -> String {
+    if let Some(&(_, _, word)) = IRREGULAR.iter().find(|(l, f, _)| *l == lemma && *f == form) {
+        return word.to_string();
+    }
+    if let Some((verb, rest)) = lemma.split_once(' ') {
+        return format!("{} {}", inflect(verb, form), rest);
+    }
+
+    let last = lemma.chars().last().unwrap_or_default();
+    let before_last = lemma.chars().rev().nth(1).unwrap_or_default();
+    let consonant_y = last == 'y' && !is_vowel(before_last);
+    let silent_e = last == 'e' && !"eoy".contains(before_last);
+    let doubled = || format!("{}{}", lemma, last);
+    let stem = |n: usize| &lemma[..lemma.len() - n];
+
+    match form {
+        Form::Base => lemma.to_string(),
+        Form::ThirdPerson if consonant_y => format!("{}ies", stem(1)),
+        Form::ThirdPerson if ["s", "x", "z", "ch", "sh"].iter().any(|end| lemma.ends_with(end)) => {
+            format!("{}es", lemma)
+        }
+        Form::ThirdPerson => format!("{}s", lemma),
+        Form::Gerund if lemma.ends_with("ie") => format!("{}ying", stem(2)),
+        Form::Gerund if silent_e => format!("{}ing", stem(1)),
+        Form::Gerund if doubles(lemma) => format!("{}ing", doubled()),
+        Form::Gerund => format!("{}ing", lemma),
+        Form::Past if last == 'e' => format!("{}d", lemma),
+        Form::Past if consonant_y => format!("{}ied", stem(1)),
+        Form::Past if doubles(lemma) => format!("{}ed", doubled()),
+        Form::Past => format!("{}ed", lemma),
+        Form::Agent if lemma.ends_with("ate") && vowel_groups(lemma) > 1 => format!("{}or", stem(1)),
+        Form::Agent if last == 'e' => format!("{}r", lemma),
+        Form::Agent if consonant_y => format!("{}ier", stem(1)),
+        Form::Agent if doubles(lemma) => format!("{}er", doubled()),
+        Form::Agent => format!("{}er", lemma),
+    }
+}
+
+static INTERNED: LazyLock<Mutex<HashSet<&'static str>>> = LazyLock::new(Default::default);
+
+/// `word` as a `'static` string like the lemmas. Each distinct word is
+/// allocated once and kept for the life of the process.
+fn intern(word: &str) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(&word) = interned.get(word) {
+        return word;
+    }
+    let word: &'static str = Box::leak(word.into());
+    interned.insert(word);
+    word
+}
+
+/// `form` of every lemma, in order. The words are interned, so each is
+/// kept once for the life of the process, like the lemmas they're derived
+/// from.
+pub fn conjugate(lemmas: &[&'static str], form: Form) -> Vec<&'static str> {
+    lemmas
+        .iter()
+        .map(|&lemma| match form {
+            Form::Base => lemma,
+            _ => intern(&inflect(lemma, form)),
+        })
+        .collect()
+}
+
+/// Every form of the built-in lemmas, with the lemma it comes from. The
+/// first lemma with a form wins, as a search of the lists in order would.
+static LEMMAS: LazyLock<HashMap<String, &'static str>> = LazyLock::new(|| {
+    let mut lemmas = HashMap::new();
+    for &lemma in BASE_VERBS.iter().chain(BASE_VERBS_NSFW) {
+        for &form in Form::ALL {
+            lemmas.entry(inflect(lemma, form)).or_insert(lemma);
+        }
+    }
+    lemmas
+});
+
+/// The built-in lemma that `word` is a form of, if any.
+pub fn lemma(word: &str) -> Option<&'static str> {
+    LEMMAS.get(word).copied()
+}
+
+fn is_vowel(c: char) -> bool {
+    "aeiou".contains(c)
+}
+
+fn vowel_groups(word: &str) -> usize {
+    let mut count = 0;
+    let mut previous_vowel = false;
+    for c in word.chars() {
+        let vowel = is_vowel(c);
+        if vowel && !previous_vowel {
+            count += 1;
+        }
+        previous_vowel = vowel;
+    }
+    count
+}
+
+/// Whether the final consonant doubles before a vowel suffix: one-syllable
+/// lemmas ending in a single vowel and a single consonant ("plot", "run"),
+/// plus [`DOUBLED`].
+fn doubles(lemma: &str) -> bool {
+    if DOUBLED.contains(&lemma) {
+        return true;
+    }
+    let chars: Vec<char> = lemma.chars().collect();
+    match chars[..] {
+        [.., a, b, c] => {
+            !is_vowel(a) && is_vowel(b) && !is_vowel(c) && !"wxy".contains(c) && vowel_groups(lemma) == 1
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_regular_forms() {
+        let forms = |lemma| Form::ALL.iter().map(|&form| inflect(lemma, form)).collect::<Vec<_>>();
+        assert_eq!(forms("juggle"), ["juggle", "juggles", "juggling", "juggled", "juggler"]);
+        assert_eq!(forms("plot"), ["plot", "plots", "plotting", "plotted", "plotter"]);
+        assert_eq!(forms("certify"), ["certify", "certifies", "certifying", "certified", "certifier"]);
+        assert_eq!(forms("munch"), ["munch", "munches", "munching", "munched", "muncher"]);
+        assert_eq!(forms("tiptoe"), ["tiptoe", "tiptoes", "tiptoeing", "tiptoed", "tiptoer"]);
+        assert_eq!(forms("mosey"), ["mosey", "moseys", "moseying", "moseyed", "moseyer"]);
+        assert_eq!(forms("gossip"), ["gossip", "gossips", "gossiping", "gossiped", "gossiper"]);
+    }
+
+    #[test]
+    fn test_agent_nouns_avoid_naive_suffixes() {
+        assert_eq!(inflect("hibernate", Form::Agent), "hibernator");
+        assert_eq!(inflect("combust", Form::Agent), "combustor");
+        assert_eq!(inflect("run", Form::Agent), "runner");
+        assert_eq!(inflect("eavesdrop", Form::Agent), "eavesdropper");
+    }
+
+    #[test]
+    fn test_irregular_and_phrasal_forms() {
+        assert_eq!(inflect("throw", Form::Past), "threw");
+        assert_eq!(inflect("throw", Form::Gerund), "throwing");
+        assert_eq!(inflect("clash with", Form::ThirdPerson), "clashes with");
+        for &(lemma, _, _) in IRREGULAR {
+            assert!(BASE_VERBS.contains(&lemma), "{} is not a lemma", lemma);
+        }
+    }
+
+    #[test]
+    fn test_finds_the_lemma_of_any_form() {
+        assert_eq!(lemma("plotting"), Some("plot"));
+        assert_eq!(lemma("spies"), Some("spy"));
+        assert_eq!(lemma("seducer"), Some("seduce"));
+        assert_eq!(lemma("walrus"), None);
+    }
+}
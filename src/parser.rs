@@ -0,0 +1,471 @@
+//! Parsing a finished password back into its template and word choices.
+//!
+//! The parser works by replaying the real template generators: it searches
+//! for the sequence of choices that makes one of the templates the
+//! configuration draws from ([`WordConfig::templates`], including custom
+//! patterns in its mix) produce exactly the given text. Formatting added by
+//! [`crate::formatting`] is undone first: case, separators, air quotes,
+//! commas and ending punctuation are ignored, and a leading dramatic opener
+//! or "The"/"A"/"An" article prefix is tried both ways.
+//!
+//! Since the recovered decisions are the ones the generator would have
+//! recorded, the entropy of a parsed password is exact for that reading. When
+//! several readings exist (the same word in two lists, or different word-count
+//! settings), the one with the fewest bits is listed first, which is the right
+//! figure for a strength audit. Formatting choices are not counted, so the
+//! figure is a lower bound on what `mkpass` reported.
+//!
+//! [`parse_compound`] reads sentences chained with connectives (see
+//! [`crate::compound`]) by cutting the text at every connective and reading
+//! each piece as a sentence of its own.
+
+use std::collections::HashMap;
+
+use crate::article::{fix_articles, starts_with_vowel_sound};
+use crate::compound::Connective;
+use crate::formatting::{needs_article_prefix, OPENERS};
+use crate::generator::WordConfig;
+use crate::lang::Language;
+use crate::password::Decision;
+use crate::templates::{ChoiceSource, Replay, Template, COMMA_MARKER, LINE_MARKER};
+
+/// Largest `min_words` setting tried when parsing.
+const MAX_MIN_WORDS: usize = 12;
+
+/// Most trailing prepositional phrases tried (as added by entropy targets).
+const MAX_PHRASES: usize = 8;
+
+/// A word of a parsed password and the list it came from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParsedWord {
+    /// The word as generated (lowercase).
+    pub text: String,
+    /// The decision label that produced it ("adjective", "animal", ...), or
+    /// "fixed" for words the template always adds.
+    pub list: &'static str,
+}
+
+/// One way a password could have been generated.
+#[derive(Clone, Debug)]
+pub struct ParsedPassword {
+    /// The template that produced the sentence.
+    pub template: Template,
+    /// The dramatic opener in front of the sentence, if any.
+    pub opener: Option<&'static str>,
+    /// The article added in front of a leading adjective, if any.
+    pub article_prefix: Option<&'static str>,
+    /// The sentence's words in order, with their lists.
+    pub words: Vec<ParsedWord>,
+    /// The recovered decisions, starting with the template choice.
+    pub decisions: Vec<Decision>,
+}
+
+impl ParsedPassword {
+    /// Entropy of the recovered decisions in bits (formatting not included).
+    pub fn entropy(&self) -> f64 {
+        self.decisions.iter().map(Decision::bits).sum()
+    }
+}
+
+/// One way a compound password could have been generated.
+#[derive(Clone, Debug)]
+pub struct ParsedCompound {
+    /// The reading with the fewest bits of each sentence, in order.
+    pub clauses: Vec<ParsedPassword>,
+    /// The connective in front of each sentence after the first.
+    pub connectives: Vec<Connective>,
+    /// The recovered connective choices.
+    pub decisions: Vec<Decision>,
+}
+
+impl ParsedCompound {
+    /// Entropy of every sentence's decisions plus the connective choices, in
+    /// bits (formatting not included).
+    pub fn entropy(&self) -> f64 {
+        let clauses: f64 = self.clauses.iter().map(ParsedPassword::entropy).sum();
+        clauses + self.decisions.iter().map(Decision::bits).sum::<f64>()
+    }
+}
+
+/// Every way `text` could have been generated with `config`'s word lists,
+/// fewest bits first. Empty if no template produces it, and always empty for
+/// languages other than English.
+pub fn parse_password(text: &str, config: &WordConfig) -> Vec<ParsedPassword> {
+    if config.language != Language::English {
+        return Vec::new();
+    }
+    parse_sentence(&squash(text), true, config)
+}
+
+/// Every way `text` could have been generated as two or more sentences joined
+/// by connectives, fewest bits first. Each way of cutting the text at its
+/// connectives gives one reading, made of the weakest reading of each piece.
+/// Empty if no cut reads as sentences, and always empty for languages other
+/// than English.
+pub fn parse_compound(text: &str, config: &WordConfig) -> Vec<ParsedCompound> {
+    if config.language != Language::English {
+        return Vec::new();
+    }
+    let connectives = Connective::available(config);
+    let target = squash(text);
+    // The same piece turns up in several cuts
+    let mut sentences: HashMap<(&str, bool), Option<ParsedPassword>> = HashMap::new();
+    let mut readings = Vec::new();
+
+    for (pieces, joins) in cuts(&target, &connectives) {
+        let clauses: Option<Vec<ParsedPassword>> = pieces
+            .iter()
+            .enumerate()
+            .map(|(i, &piece)| {
+                // Only the first sentence can have an opener
+                let first = i == 0;
+                let reading = sentences.entry((piece, first)).or_insert_with(|| {
+                    parse_sentence(piece, first, config).into_iter().next()
+                });
+                reading.clone()
+            })
+            .collect();
+        if let Some(clauses) = clauses {
+            let decisions = joins.iter().map(|_| Decision::uniform("connective", connectives.len())).collect();
+            readings.push(ParsedCompound { clauses, connectives: joins, decisions });
+        }
+    }
+
+    readings.sort_by(|a, b| a.entropy().total_cmp(&b.entropy()));
+    readings
+}
+
+/// Every way to cut `target` into two or more pieces at `connectives`, with
+/// the connective at each cut.
+fn cuts<'t>(target: &'t str, connectives: &[Connective]) -> Vec<(Vec<&'t str>, Vec<Connective>)> {
+    let mut found = Vec::new();
+    for (at, _) in target.char_indices().skip(1) {
+        for &connective in connectives {
+            let Some(rest) = target[at..].strip_prefix(squash(&connective.words().concat()).as_str()) else {
+                continue;
+            };
+            if rest.is_empty() {
+                continue;
+            }
+            let mut tails = cuts(rest, connectives);
+            tails.push((vec![rest], Vec::new()));
+            for (mut pieces, mut joins) in tails {
+                pieces.insert(0, &target[..at]);
+                joins.insert(0, connective);
+                found.push((pieces, joins));
+            }
+        }
+    }
+    found
+}
+
+/// Every reading of the squashed `target` as a single sentence, fewest bits
+/// first, trying a leading opener if `openers` allows one.
+fn parse_sentence(target: &str, openers: bool, config: &WordConfig) -> Vec<ParsedPassword> {
+    let mut readings: Vec<ParsedPassword> = Vec::new();
+    let variants = if openers { strip_opener(target) } else { vec![(None, target)] };
+    // Only the templates the generator would draw from, counted as it counts them
+    let templates = config.templates();
+    let template_choice = config.template_decision(&templates);
+    let words = config.words();
+
+    for (opener, rest) in variants {
+        for (article_prefix, sentence) in strip_article(rest) {
+            for template in templates.iter() {
+                for (min_words, phrases) in settings() {
+                    for replay in search(template, sentence, min_words, phrases, config) {
+                        if article_prefix.is_some() && !needs_article_prefix(&replay.words, &words) {
+                            continue;
+                        }
+                        let reading = to_parsed(template.clone(), opener, article_prefix, &template_choice, replay);
+                        let duplicate = readings.iter().any(|r| {
+                            r.template == reading.template
+                                && r.opener == reading.opener
+                                && r.article_prefix == reading.article_prefix
+                                && r.words == reading.words
+                                && r.decisions == reading.decisions
+                        });
+                        if !duplicate {
+                            readings.push(reading);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    readings.sort_by(|a, b| a.entropy().total_cmp(&b.entropy()));
+    readings
+}
+
+/// Lowercase letters and digits only: what survives every formatting option.
+fn squash(text: &str) -> String {
+    text.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+/// The text with and without each opener it could start with.
+fn strip_opener(target: &str) -> Vec<(Option<&'static str>, &str)> {
+    let mut variants = vec![(None, target)];
+    for &opener in OPENERS {
+        if let Some(rest) = target.strip_prefix(squash(opener).as_str()) {
+            variants.push((Some(opener), rest));
+        }
+    }
+    variants
+}
+
+/// The text with and without an article prefix.
+fn strip_article(target: &str) -> Vec<(Option<&'static str>, &str)> {
+    let mut variants = vec![(None, target)];
+    for article in ["The", "An", "A"] {
+        if let Some(rest) = target.strip_prefix(squash(article).as_str()) {
+            variants.push((Some(article), rest));
+        }
+    }
+    variants
+}
+
+/// The (min_words, trailing phrases) settings a password may have been made with.
+fn settings() -> impl Iterator<Item = (usize, usize)> {
+    (0..=MAX_MIN_WORDS).map(|m| (m, 0)).chain((1..=MAX_PHRASES).map(|p| (0, p)))
+}
+
+/// Squashed rendering of raw template words, with "a"/"an" fixed.
+fn render(words: &[String]) -> String {
+    let words: Vec<&String> = words.iter().filter(|w| *w != COMMA_MARKER && *w != LINE_MARKER).collect();
+    squash(&fix_articles(&words).join(" "))
+}
+
+/// What a [`Frontier`] choice has to pick from.
+enum Frontier {
+    List(Vec<String>),
+    Options(usize),
+}
+
+/// Replays a fixed prefix of choices, then zeros, and remembers what the
+/// first choice past the prefix was picking from.
+struct PrefixSource<'a> {
+    prefix: &'a [usize],
+    taken: usize,
+    frontier: Option<Frontier>,
+}
+
+impl PrefixSource<'_> {
+    fn next(&mut self, frontier: impl FnOnce() -> Frontier) -> usize {
+        let position = self.taken;
+        self.taken += 1;
+        if position == self.prefix.len() {
+            self.frontier = Some(frontier());
+        }
+        self.prefix.get(position).copied().unwrap_or(0)
+    }
+}
+
+impl ChoiceSource for PrefixSource<'_> {
+    fn index(&mut self, n: usize) -> usize {
+        self.next(|| Frontier::Options(n))
+    }
+
+    fn coin(&mut self, _p: f64) -> bool {
+        self.next(|| Frontier::Options(2)) == 1
+    }
+
+    fn choose_from(&mut self, list: &[impl AsRef<str>]) -> usize {
+        self.next(|| Frontier::List(list.iter().map(|entry| entry.as_ref().to_owned()).collect()))
+    }
+}
+
+/// All replays of `template` that render exactly to `target`.
+fn search(template: &Template, target: &str, min_words: usize, phrases: usize, config: &WordConfig) -> Vec<Replay> {
+    let mut found = Vec::new();
+    let mut stack: Vec<Vec<usize>> = vec![Vec::new()];
+
+    while let Some(prefix) = stack.pop() {
+        let mut source = PrefixSource { prefix: &prefix, taken: 0, frontier: None };
+        let replay = template.replay(&mut source, min_words, phrases, config);
+
+        let Some(frontier) = source.frontier else {
+            // No choice left open: a reading only if everything matches
+            if render(&replay.words) == target {
+                found.push(replay);
+            }
+            continue;
+        };
+
+        // Words pushed before the open choice are fixed by the prefix
+        let committed = &replay.words[..replay.marks[prefix.len()]];
+        let Some(rest) = target.strip_prefix(render(committed).as_str()) else {
+            continue;
+        };
+
+        let candidates: Vec<usize> = match frontier {
+            // Only entries that continue the text are worth exploring
+            Frontier::List(list) => {
+                let after_a = committed.last().is_some_and(|w| w == "a");
+                (0..list.len())
+                    .filter(|&i| {
+                        let entry = squash(&list[i]);
+                        // A committed "a" turns into "an" before a vowel
+                        if after_a && starts_with_vowel_sound(&entry) {
+                            rest.strip_prefix('n').is_some_and(|r| r.starts_with(&entry))
+                        } else {
+                            rest.starts_with(&entry)
+                        }
+                    })
+                    .collect()
+            }
+            Frontier::Options(n) => (0..n).collect(),
+        };
+
+        for value in candidates.into_iter().rev() {
+            let mut next = prefix.clone();
+            next.push(value);
+            stack.push(next);
+        }
+    }
+
+    found
+}
+
+fn to_parsed(
+    template: Template,
+    opener: Option<&'static str>,
+    article_prefix: Option<&'static str>,
+    template_choice: &Decision,
+    replay: Replay,
+) -> ParsedPassword {
+    let words = fix_articles(&replay.words)
+        .into_iter()
+        .zip(replay.labels)
+        .filter(|(word, _)| word != COMMA_MARKER && word != LINE_MARKER)
+        .map(|(text, list)| ParsedWord { text, list })
+        .collect();
+
+    let mut decisions = vec![template_choice.clone()];
+    decisions.extend(replay.decisions);
+
+    ParsedPassword { template, opener, article_prefix, words, decisions }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> WordConfig {
+        WordConfig::default()
+    }
+
+    #[test]
+    fn test_squash_removes_formatting() {
+        assert_eq!(squash("Behold... The \"Grumpy\" Walrus, juggles-pickles!"), "beholdthegrumpywalrusjugglespickles");
+    }
+
+    #[test]
+    fn test_parses_plain_declarative() {
+        let readings = parse_password("The grumpy walrus juggles pickles.", &config());
+        let reading = readings
+            .iter()
+            .find(|r| r.template == Template::Declarative)
+            .expect("declarative reading");
+        let lists: Vec<_> = reading.words.iter().map(|w| w.list).collect();
+        assert_eq!(lists, ["article", "adjective", "animal", "present verb", "object"]);
+    }
+
+    #[test]
+    fn test_multi_word_entries() {
+        let readings = parse_password("Do not provoke the sleepy wizard!", &config());
+        let reading = readings.iter().find(|r| r.template == Template::Imperative).expect("imperative reading");
+        let lists: Vec<_> = reading.words.iter().map(|w| w.list).collect();
+        assert_eq!(lists, ["imperative", "imperative", "imperative", "article", "adjective", "profession"]);
+    }
+
+    #[test]
+    fn test_round_trip_of_formatted_passwords() {
+        use crate::formatting::{format_password_with_rng, FormatOptions};
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(8);
+        for template in Template::ALL {
+            let generated = template.generate(&mut rng, 4, &config()).unwrap();
+            let formatted = format_password_with_rng(generated.words.clone(), &FormatOptions::default(), &mut rng);
+            let readings = parse_password(&formatted.text, &config());
+            let words: Vec<_> = generated.words.iter().filter(|w| *w != COMMA_MARKER && *w != LINE_MARKER).cloned().collect();
+            assert!(
+                readings.iter().any(|r| r.template == *template
+                    && r.words.iter().map(|w| w.text.clone()).collect::<Vec<_>>() == words),
+                "{:?}: {}",
+                template,
+                formatted.text
+            );
+        }
+    }
+
+    #[test]
+    fn test_template_choice_follows_the_mix() {
+        use crate::formatting::{format_password_with_rng, FormatOptions};
+        use crate::generator::generate_password_with_rng;
+        use crate::mix::TemplateMix;
+        use crate::pattern::Pattern;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+        use std::sync::Arc;
+
+        let pattern = Template::custom(Pattern::parse("{adjective} {animal} versus the {noun}").unwrap());
+        let mix = TemplateMix::uniform(&[Template::Question, pattern.clone()]).with_weight(Template::Declarative, 2.0);
+        let config = WordConfig { mix: Some(Arc::new(mix)), ..config() };
+        let mut rng = StdRng::seed_from_u64(9);
+        let mut patterns = 0;
+        for _ in 0..12 {
+            let generated = generate_password_with_rng(&mut rng, 4, &config).unwrap();
+            patterns += usize::from(generated.template == pattern);
+            let formatted = format_password_with_rng(generated.words.clone(), &FormatOptions::default(), &mut rng);
+            let readings = parse_password(&formatted.text, &config);
+            // Same template, same words and the template choice counted at the mix's weights
+            assert!(
+                readings.iter().any(|r| r.template == generated.template && r.decisions == generated.decisions),
+                "{}",
+                formatted.text
+            );
+        }
+        assert!(patterns > 0);
+    }
+
+    #[test]
+    fn test_unknown_text_has_no_reading() {
+        assert!(parse_password("correct horse battery staple", &config()).is_empty());
+    }
+
+    #[test]
+    fn test_round_trip_of_compound_passwords() {
+        use crate::compound::generate_compound_with_rng;
+        use crate::formatting::{format_compound_with_rng, FormatOptions};
+        use crate::generator::PasswordLength;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let shown = |words: &[String]| -> Vec<String> {
+            words.iter().filter(|w| *w != COMMA_MARKER && *w != LINE_MARKER).cloned().collect()
+        };
+        let mut rng = StdRng::seed_from_u64(25);
+        for _ in 0..3 {
+            let length = PasswordLength::MinWords(4);
+            let compound = generate_compound_with_rng(&mut rng, 3, None, length, &config()).unwrap();
+            let formatted = format_compound_with_rng(&compound, &FormatOptions::default(), &mut rng);
+            assert!(parse_password(&formatted.text, &config()).is_empty(), "{}", formatted.text);
+
+            let readings = parse_compound(&formatted.text, &config());
+            let sentences: Vec<Vec<String>> = compound.clauses.iter().map(|clause| shown(&clause.words)).collect();
+            let reading = readings
+                .iter()
+                .find(|r| {
+                    let words = r.clauses.iter().map(|c| c.words.iter().map(|w| w.text.clone()).collect::<Vec<_>>());
+                    r.connectives == compound.connectives && words.eq(sentences.clone())
+                })
+                .unwrap_or_else(|| panic!("no reading of {}", formatted.text));
+            // The weakest reading of each sentence is never stronger than the one generated
+            assert!(reading.entropy() <= compound.entropy() + 1e-9, "{}", formatted.text);
+            assert!(readings[0].entropy() <= reading.entropy());
+        }
+    }
+}
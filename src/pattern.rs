@@ -0,0 +1,323 @@
+//! User-defined sentence shapes written in a small pattern language.
+//!
+//! A pattern is a line of plain words and `{slots}`:
+//!
+//! ```text
+//! {article} {adjective?} {animal|profession} {present_verb} {prep_if_needed} {object}
+//! ```
+//!
+//! - `{adjective}` picks an entry from a word list. Slots are named after
+//!   the list in the singular: `adjective`, `animal`, `profession`, `noun`,
+//!   `object`, `adverb`, `present_verb`, `base_verb`, `gerund`, `number`,
+//!   `imperative`, `prohibition`, `demand`, `question_starter`, `warning`,
+//!   `conflict`, `location` and `time_marker`.
+//! - `{animal|profession}` picks one of the slots, each equally likely.
+//! - `{adjective?}` is left out half the time.
+//! - `{prep_if_needed}` is where the preposition an intransitive verb needs
+//!   goes ("dances with"). A verb with no such slot after it gets its
+//!   preposition right after the verb.
+//! - `{article}`, `{preposition}`, `{connector}` and `{year}` fill in the
+//!   grammar the built-in templates use.
+//! - Anything outside braces is copied as is.
+//!
+//! "a" and "an" are fixed to suit the next word, as in every template. A
+//! parsed pattern becomes a [`Template::Custom`](crate::templates::Template::Custom),
+//! and since it draws through the same recorded choices as the built-in
+//! templates, the entropy of what it generates is exact.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::generator::WordConfig;
+use crate::templates::{Builder, ChoiceSource};
+use crate::words::get_verb_preposition;
+use crate::wordset::ListKind;
+
+/// What a slot draws from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Source {
+    /// A loadable word list.
+    List(ListKind),
+    Article,
+    Preposition,
+    Connector,
+    Year,
+    /// The preposition the last verb needs, if any.
+    VerbPreposition,
+}
+
+/// A named slot: `(name, source, decision label, whether entries are
+/// phrases added word by word)`.
+type SlotInfo = (&'static str, Source, &'static str, bool);
+
+/// Every slot a pattern may use.
+const SLOTS: &[SlotInfo] = &[
+    ("article", Source::Article, "article", false),
+    ("adjective", Source::List(ListKind::Adjective), "adjective", false),
+    ("animal", Source::List(ListKind::Animal), "animal", false),
+    ("profession", Source::List(ListKind::Profession), "profession", false),
+    ("noun", Source::List(ListKind::Noun), "noun", false),
+    ("object", Source::List(ListKind::Object), "object", false),
+    ("adverb", Source::List(ListKind::Adverb), "adverb", false),
+    ("present_verb", Source::List(ListKind::PresentVerb), "present verb", false),
+    ("base_verb", Source::List(ListKind::BaseVerb), "base verb", false),
+    ("gerund", Source::List(ListKind::Gerund), "gerund", false),
+    ("number", Source::List(ListKind::Number), "number", true),
+    ("imperative", Source::List(ListKind::Imperative), "imperative", true),
+    ("prohibition", Source::List(ListKind::Prohibition), "prohibition", true),
+    ("demand", Source::List(ListKind::Demand), "demand", true),
+    ("question_starter", Source::List(ListKind::QuestionStarter), "question starter", true),
+    ("warning", Source::List(ListKind::Warning), "warning", true),
+    ("conflict", Source::List(ListKind::Conflict), "conflict", false),
+    ("location", Source::List(ListKind::Location), "location", true),
+    ("time_marker", Source::List(ListKind::TimeMarker), "time marker", true),
+    ("preposition", Source::Preposition, "preposition", false),
+    ("connector", Source::Connector, "connector", false),
+    ("year", Source::Year, "year", false),
+    ("prep_if_needed", Source::VerbPreposition, "fixed", false),
+];
+
+/// One part of a pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Element {
+    /// A word copied as is.
+    Word(String),
+    /// A slot, or a choice between slots, possibly left out.
+    Slot { options: Vec<&'static SlotInfo>, optional: bool },
+}
+
+impl Element {
+    fn has_verb(&self) -> bool {
+        matches!(self, Element::Slot { options, .. } if options.iter().any(|slot| is_verb(slot.1)))
+    }
+
+    fn is_verb_preposition(&self) -> bool {
+        matches!(self, Element::Slot { options, .. } if options.iter().any(|slot| slot.1 == Source::VerbPreposition))
+    }
+}
+
+fn is_verb(source: Source) -> bool {
+    matches!(source, Source::List(ListKind::PresentVerb | ListKind::BaseVerb | ListKind::Gerund))
+}
+
+/// A parsed sentence pattern.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    /// The pattern as written.
+    text: String,
+    elements: Vec<Element>,
+    /// The word lists the pattern draws from.
+    lists: Vec<ListKind>,
+}
+
+/// Why a pattern couldn't be read.
+#[derive(Debug)]
+pub enum PatternError {
+    /// A pattern file couldn't be read.
+    Io { path: PathBuf, error: std::io::Error },
+    /// A `{` has no matching `}`, or a `}` no matching `{`.
+    Unbalanced(String),
+    /// A slot name isn't one the pattern language knows.
+    UnknownSlot(String),
+    /// The pattern has no slots, so every password would be the same.
+    NoSlots(String),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PatternError::Io { path, error } => write!(f, "cannot read {}: {}", path.display(), error),
+            PatternError::Unbalanced(pattern) => write!(f, "unbalanced braces in pattern \"{}\"", pattern),
+            PatternError::UnknownSlot(name) => {
+                let names: Vec<_> = SLOTS.iter().map(|slot| slot.0).collect();
+                write!(f, "unknown slot {{{}}} (expected one of: {})", name, names.join(", "))
+            }
+            PatternError::NoSlots(pattern) => write!(f, "pattern \"{}\" has no slots to fill", pattern),
+        }
+    }
+}
+
+impl std::error::Error for PatternError {}
+
+impl Pattern {
+    /// Parse a pattern (see the module docs for the syntax).
+    pub fn parse(text: &str) -> Result<Pattern, PatternError> {
+        let text = text.trim();
+        let mut elements = Vec::new();
+        let mut rest = text;
+        while let Some(start) = rest.find(['{', '}']) {
+            let (before, after) = rest.split_at(start);
+            elements.extend(before.split_whitespace().map(|word| Element::Word(word.to_lowercase())));
+            let end = after.find('}').filter(|_| after.starts_with('{'));
+            let Some(end) = end.filter(|&end| !after[1..end].contains('{')) else {
+                return Err(PatternError::Unbalanced(text.to_string()));
+            };
+            elements.push(parse_slot(&after[1..end])?);
+            rest = &after[end + 1..];
+        }
+        elements.extend(rest.split_whitespace().map(|word| Element::Word(word.to_lowercase())));
+
+        if !elements.iter().any(|element| matches!(element, Element::Slot { .. })) {
+            return Err(PatternError::NoSlots(text.to_string()));
+        }
+
+        // A verb without a {prep_if_needed} before the next verb gets one right after it
+        let mut i = 0;
+        while i < elements.len() {
+            if elements[i].has_verb() {
+                let placed = elements[i + 1..]
+                    .iter()
+                    .take_while(|element| !element.has_verb())
+                    .any(Element::is_verb_preposition);
+                if !placed {
+                    let slot = SLOTS.iter().find(|slot| slot.1 == Source::VerbPreposition).unwrap();
+                    elements.insert(i + 1, Element::Slot { options: vec![slot], optional: false });
+                }
+            }
+            i += 1;
+        }
+
+        let mut lists = Vec::new();
+        for element in &elements {
+            if let Element::Slot { options, .. } = element {
+                for slot in options {
+                    if let Source::List(kind) = slot.1 {
+                        if !lists.contains(&kind) {
+                            lists.push(kind);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(Pattern { text: text.to_string(), elements, lists })
+    }
+
+    /// Read one pattern per line. Blank lines and lines starting with `#` are
+    /// ignored.
+    pub fn from_file(path: &Path) -> Result<Vec<Pattern>, PatternError> {
+        let text = fs::read_to_string(path).map_err(|error| PatternError::Io { path: path.to_path_buf(), error })?;
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(Pattern::parse)
+            .collect()
+    }
+
+    /// The pattern as written.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The word lists the pattern draws from.
+    pub fn lists(&self) -> &[ListKind] {
+        &self.lists
+    }
+
+    /// Whether every password from the pattern has a year in it, the one
+    /// source of digits.
+    pub fn draws_year(&self) -> bool {
+        self.elements.iter().any(|element| {
+            matches!(element, Element::Slot { options, optional: false } if options.iter().all(|slot| slot.1 == Source::Year))
+        })
+    }
+
+    /// Fill in the pattern, recording every choice in `b`.
+    pub(crate) fn build(&self, b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+        let words = config.words();
+        let mut verb = None;
+        for element in &self.elements {
+            let (options, optional) = match element {
+                Element::Word(word) => {
+                    b.push(word);
+                    continue;
+                }
+                Element::Slot { options, optional } => (options, *optional),
+            };
+            if optional && !b.chance("optional word", 0.5) {
+                continue;
+            }
+            let &(_, source, label, phrase) =
+                if options.len() > 1 { options[b.branch("alternative", options.len())] } else { options[0] };
+
+            let list: &[Arc<str>] = match source {
+                Source::List(kind) => words.list(kind),
+                Source::Article => &words.articles,
+                Source::Preposition => &words.prepositions,
+                Source::Connector => &words.connectors_active,
+                Source::Year => {
+                    b.year();
+                    continue;
+                }
+                Source::VerbPreposition => {
+                    if let Some(preposition) = verb.take().and_then(get_verb_preposition) {
+                        b.push(preposition);
+                    }
+                    continue;
+                }
+            };
+            let entry = if phrase { b.pick_phrase(label, list) } else { b.pick(label, list) };
+            if is_verb(source) {
+                verb = Some(entry);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// Parse the inside of `{...}`: slot names separated by `|`, optionally
+/// followed by `?`.
+fn parse_slot(inside: &str) -> Result<Element, PatternError> {
+    let inside = inside.trim();
+    let (names, optional) = match inside.strip_suffix('?') {
+        Some(names) => (names, true),
+        None => (inside, false),
+    };
+    let options = names
+        .split('|')
+        .map(|name| {
+            let name = name.trim();
+            SLOTS.iter().find(|slot| slot.0 == name).ok_or_else(|| PatternError::UnknownSlot(name.to_string()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Element::Slot { options, optional })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_slots_choices_and_words() {
+        let pattern = Pattern::parse("{article} {adjective?} {animal|profession} {present_verb} {prep_if_needed} my {object}").unwrap();
+        assert_eq!(pattern.elements.len(), 7);
+        assert_eq!(pattern.elements[5], Element::Word("my".to_string()));
+        assert_eq!(
+            pattern.lists(),
+            [ListKind::Adjective, ListKind::Animal, ListKind::Profession, ListKind::PresentVerb, ListKind::Object]
+        );
+    }
+
+    #[test]
+    fn test_inserts_missing_verb_prepositions() {
+        let pattern = Pattern::parse("{animal} {present_verb} {object}").unwrap();
+        assert!(pattern.elements[2].is_verb_preposition());
+        let explicit = Pattern::parse("{animal} {present_verb} {prep_if_needed} {object}").unwrap();
+        assert_eq!(pattern.elements, explicit.elements);
+    }
+
+    #[test]
+    fn test_rejects_bad_patterns() {
+        assert!(matches!(Pattern::parse("{animal"), Err(PatternError::Unbalanced(_))));
+        assert!(matches!(Pattern::parse("animal}"), Err(PatternError::Unbalanced(_))));
+        assert!(matches!(Pattern::parse("{llama}"), Err(PatternError::UnknownSlot(name)) if name == "llama"));
+        assert!(matches!(Pattern::parse("just words"), Err(PatternError::NoSlots(_))));
+    }
+}
@@ -0,0 +1,339 @@
+//! Plural nouns and plural verb agreement for counted subjects.
+//!
+//! The Fragment template opens with a [`crate::words::NUMBERS`] entry, and the
+//! subject and verb after it must agree: "three wizards juggle", "only one
+//! wizard juggles". [`count`] says what number an entry asks for, [`plural`]
+//! makes a plural noun and [`plural_verb`] turns a third-person verb back into
+//! its plural form.
+//!
+//! Nouns follow English spelling rules, with [`IRREGULAR`] for the ones the
+//! rules get wrong and [`UNCHANGED`] for the ones whose plural is the same
+//! word. Mass nouns ("chaos", "nihilism") have no plural and are counted in
+//! outbreaks instead: "three outbreaks of chaos juggle pickles". After an
+//! amount ("a suspicious amount of") a mass noun stays as it is and takes a
+//! singular verb, while anything else is plural and takes a plural verb: "a
+//! suspicious amount of chaos juggles", "a suspicious amount of wizards
+//! juggle". [`amount_of`] makes the noun after an amount.
+//!
+//! Plural lists keep the length and order of the lists they are made from, so
+//! a counted pick records the same decision as an uncounted one.
+
+use std::sync::Arc;
+
+use crate::cache::{Cache, Source};
+use crate::morphology::{inflect, lemma, Form};
+use crate::wordset::WordSet;
+
+/// The grammatical number a quantity asks of the subject after it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Count {
+    /// A single thing: "only one wizard juggles".
+    One,
+    /// Several things: "three wizards juggle".
+    Many,
+    /// An amount of stuff: "a suspicious amount of chaos juggles", or of
+    /// things: "a suspicious amount of wizards juggle".
+    Mass,
+}
+
+/// Quantities that count a single thing without saying "one".
+pub const SINGULAR_QUANTITIES: &[&str] = &["a record-breaking"];
+
+/// What `quantity` ("three", "a dozen", "a suspicious amount of") asks of
+/// the subject after it.
+pub fn count(quantity: &str) -> Count {
+    if quantity.ends_with("amount of") {
+        Count::Mass
+    } else if quantity.split_whitespace().last() == Some("one") || SINGULAR_QUANTITIES.contains(&quantity) {
+        Count::One
+    } else {
+        Count::Many
+    }
+}
+
+/// Plurals the spelling rules get wrong: `(singular, plural)`.
+pub const IRREGULAR: &[(&str, &str)] = &[
+    // Animals
+    ("cyclops", "cyclopes"),
+    ("dormouse", "dormice"),
+    ("goose", "geese"),
+    ("mouse", "mice"),
+    ("wolf", "wolves"),
+    ("ziz", "zizzes"),
+    // Professions
+    ("knocker-upper", "knockers-upper"),
+    ("maitre d", "maitres d"),
+    ("shaman", "shamans"),
+    // Nouns with Latin and Greek plurals
+    ("continuum", "continua"),
+    ("crisis", "crises"),
+    ("equilibrium", "equilibria"),
+    ("genesis", "geneses"),
+    ("hypothesis", "hypotheses"),
+    ("nebula", "nebulae"),
+    ("obsequy", "obsequies"),
+    ("phenomenon", "phenomena"),
+    ("primordium", "primordia"),
+    ("quantum", "quanta"),
+    ("spectrum", "spectra"),
+    ("talisman", "talismans"),
+    ("terminus", "termini"),
+    ("vortex", "vortices"),
+    // Already plural, and "algorithm" is in the same list
+    ("algorithms", "outbreaks of algorithms"),
+];
+
+/// Nouns whose plural is the same word, including ones that are already
+/// plural ("shenanigans"). Words ending in "fish" are unchanged too.
+pub const UNCHANGED: &[&str] = &[
+    "bison", "caribou", "djinn", "elk", "moose", "shrimp", "squid", "mantisshrimp",
+    "hijinks", "shenanigans",
+];
+
+/// Nouns with no plural, besides those ending in "ism", "ics", "ness" or
+/// "ology".
+pub const MASS_NOUNS: &[&str] = &[
+    // Abstract concepts
+    "chaos", "anarchy", "liberty", "equality", "justice", "karma", "sarcasm", "hypocrisy", "empathy",
+    // Feelings/states
+    "nostalgia", "paranoia", "euphoria", "melancholy", "ennui", "apathy", "angst", "serenity", "despair",
+    "bliss", "dread", "confusion", "bewilderment", "contentment",
+    // Systems
+    "gravity", "wifi", "logic", "rhetoric", "grammar", "syntax", "relativity", "chemistry", "philosophy",
+    // Funny abstracts
+    "tomfoolery", "skulduggery", "malarkey", "gobbledygook", "flimflam", "balderdash", "poppycock",
+    "codswallop", "hokum", "bunkum", "twaddle", "drivel", "humbug", "hogwash", "claptrap", "buffoonery",
+    "lunacy", "pandemonium", "mayhem",
+    // Science-y
+    "inertia", "momentum", "bandwidth", "latency", "efficiency", "redundancy", "simplicity", "complexity",
+    // Cosmic/existential
+    "oblivion", "eternity", "omniscience", "omnipotence", "transcendence", "limbo", "cosmos",
+    // Dramatic abstracts
+    "vengeance", "treachery", "redemption", "salvation", "damnation", "perdition", "absolution",
+    "retribution", "penance", "atonement", "wrath", "fury", "martyrdom", "hubris",
+    // Unusual/archaic
+    "pestilence", "famine", "blight", "miasma", "malaise", "torpor", "languor", "decadence", "decrepitude",
+    "desolation", "ruination",
+    // NSFW
+    "puberty", "alimony", "libido", "impotence", "incontinence", "flatulence", "performance-anxiety",
+    "pillow-talk",
+];
+
+/// Whether `noun` has no plural.
+pub fn is_mass(noun: &str) -> bool {
+    MASS_NOUNS.contains(&noun) || ["ism", "ics", "ness", "ology"].iter().any(|end| noun.ends_with(end))
+}
+
+/// The plural of `noun`. A mass noun is counted in outbreaks ("outbreaks
+/// of chaos"). In a noun of several words the head is pluralised: the word
+/// before " of " or "-of-" if there is one ("chiefs of staff",
+/// "walks-of-shame"), otherwise the last word ("server room mice",
+/// "space-pirates").
+pub fn plural(noun: &str) -> String {
+    if let Some(&(_, plural)) = IRREGULAR.iter().find(|(singular, _)| *singular == noun) {
+        return plural.to_string();
+    }
+    if UNCHANGED.contains(&noun) || noun.ends_with("fish") {
+        return noun.to_string();
+    }
+    if is_mass(noun) || (noun.contains(' ') && !noun.contains(" of ") && is_mass(last_word(noun))) {
+        return format!("outbreaks of {}", noun);
+    }
+    for separator in [" of ", "-of-"] {
+        if let Some((head, rest)) = noun.split_once(separator) {
+            return format!("{}{}{}", plural(head), separator, rest);
+        }
+    }
+    for separator in [' ', '-'] {
+        if let Some((rest, head)) = noun.rsplit_once(separator) {
+            return format!("{}{}{}", rest, separator, plural(head));
+        }
+    }
+
+    let before_last = noun.chars().rev().nth(1).unwrap_or_default();
+    if noun.ends_with('y') && !"aeiou".contains(before_last) {
+        format!("{}ies", &noun[..noun.len() - 1])
+    } else if ["s", "x", "z", "ch", "sh"].iter().any(|end| noun.ends_with(end)) {
+        format!("{}es", noun)
+    } else if let Some(stem) = noun.strip_suffix("man") {
+        format!("{}men", stem)
+    } else {
+        format!("{}s", noun)
+    }
+}
+
+/// The last word of a noun of several words.
+fn last_word(noun: &str) -> &str {
+    noun.rsplit(' ').next().unwrap_or(noun)
+}
+
+/// The noun after an amount ("a suspicious amount of"): a mass noun as it
+/// is, anything else in the plural.
+pub fn amount_of(noun: &str) -> String {
+    if is_mass(noun) {
+        noun.to_string()
+    } else {
+        plural(noun)
+    }
+}
+
+/// Verbs whose plural isn't their third-person form minus its ending.
+const IRREGULAR_VERBS: &[(&str, &str)] = &[("is", "are"), ("has", "have"), ("does", "do"), ("goes", "go")];
+
+/// The plural form of a third-person verb: "juggles" becomes "juggle". For
+/// a phrasal verb ("clashes with") only the first word changes.
+pub fn plural_verb(verb: &str) -> String {
+    if let Some((first, rest)) = verb.split_once(' ') {
+        return format!("{} {}", plural_verb(first), rest);
+    }
+    if let Some(&(_, plural)) = IRREGULAR_VERBS.iter().find(|(singular, _)| *singular == verb) {
+        return plural.to_string();
+    }
+    if let Some(lemma) = lemma(verb).filter(|&lemma| inflect(lemma, Form::ThirdPerson) == verb) {
+        return lemma.to_string();
+    }
+
+    if let Some(stem) = verb.strip_suffix("ies") {
+        format!("{}y", stem)
+    } else if let Some(stem) = ["sses", "xes", "zzes", "ches", "shes"]
+        .iter()
+        .find_map(|end| verb.ends_with(end).then(|| &verb[..verb.len() - 2]))
+    {
+        stem.to_string()
+    } else {
+        verb.strip_suffix('s').unwrap_or(verb).to_string()
+    }
+}
+
+/// `base` with the subjects made by `noun` and the present verbs in their
+/// plural form.
+fn apply(base: &WordSet, noun: fn(&str) -> String) -> WordSet {
+    let mut set = base.clone();
+    for list in [&mut set.animals, &mut set.professions, &mut set.nouns] {
+        *list = list.iter().map(|word| Arc::from(noun(word))).collect();
+    }
+    set.present_verbs = set.present_verbs.iter().map(|verb| Arc::from(plural_verb(verb))).collect();
+    set
+}
+
+static COUNTED: Cache<Source<WordSet>, WordSet> = Cache::new();
+static AMOUNTS: Cache<Source<WordSet>, WordSet> = Cache::new();
+
+/// `base` with plural subjects and verbs, for fragments that count many
+/// things. Built once per set and shared while it's used.
+pub fn counted(base: &Arc<WordSet>) -> Arc<WordSet> {
+    COUNTED.get(Source::of(base), || apply(base, plural))
+}
+
+/// `base` with the subjects as they read after an amount (see
+/// [`amount_of`]) and the present verbs in their plural form. Built once
+/// per set and shared while it's used.
+pub fn amounts(base: &Arc<WordSet>) -> Arc<WordSet> {
+    AMOUNTS.get(Source::of(base), || apply(base, amount_of))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rating::ContentRating;
+    use crate::themes::Theme;
+    use crate::wordset::ListKind;
+    use crate::words::NUMBERS;
+
+    #[test]
+    fn test_regular_and_irregular_plurals() {
+        for (noun, expected) in [
+            ("wizard", "wizards"),
+            ("walrus", "walruses"),
+            ("butterfly", "butterflies"),
+            ("lamprey", "lampreys"),
+            ("ombudsman", "ombudsmen"),
+            ("shaman", "shamans"),
+            ("goose", "geese"),
+            ("jellyfish", "jellyfish"),
+            ("space-pirate", "space-pirates"),
+            ("groom-of-the-stool", "grooms-of-the-stool"),
+            ("midlife-crisis", "midlife-crises"),
+            ("paradox", "paradoxes"),
+            ("chaos", "outbreaks of chaos"),
+            ("nihilism", "outbreaks of nihilism"),
+            ("hubris", "outbreaks of hubris"),
+            ("cosmos", "outbreaks of cosmos"),
+            ("obsequy", "obsequies"),
+            ("parking lot goose", "parking lot geese"),
+            ("server room mouse", "server room mice"),
+            ("dire wolf", "dire wolves"),
+            ("nebula squid", "nebula squid"),
+            ("vp of sales", "vps of sales"),
+            ("chief of staff", "chiefs of staff"),
+            ("maitre d", "maitres d"),
+            ("existential dread", "outbreaks of existential dread"),
+        ] {
+            assert_eq!(plural(noun), expected, "{}", noun);
+        }
+    }
+
+    #[test]
+    fn test_amounts_keep_mass_nouns() {
+        assert_eq!(amount_of("chaos"), "chaos");
+        assert_eq!(amount_of("nihilism"), "nihilism");
+        assert_eq!(amount_of("wizard"), "wizards");
+        assert_eq!(amount_of("squid"), "squid");
+    }
+
+    #[test]
+    fn test_plural_verbs() {
+        assert_eq!(plural_verb("juggles"), "juggle");
+        assert_eq!(plural_verb("certifies"), "certify");
+        assert_eq!(plural_verb("munches"), "munch");
+        assert_eq!(plural_verb("tiptoes"), "tiptoe");
+        assert_eq!(plural_verb("clashes with"), "clash with");
+        assert_eq!(plural_verb("is"), "are");
+    }
+
+    #[test]
+    fn test_counts_every_number() {
+        assert_eq!(count("three"), Count::Many);
+        assert_eq!(count("a dozen"), Count::Many);
+        assert_eq!(count("only one"), Count::One);
+        assert_eq!(count("a record-breaking"), Count::One);
+        assert_eq!(count("a suspicious amount of"), Count::Mass);
+        assert_eq!(NUMBERS.iter().filter(|n| count(n) == Count::Mass).count(), 1);
+    }
+
+    #[test]
+    fn test_counted_lists_stay_distinct_and_aligned() {
+        let base = &WordSet::shared(ContentRating::Explicit);
+        let set = counted(base);
+        for (plurals, singulars) in
+            [(&set.animals, &base.animals), (&set.professions, &base.professions), (&set.nouns, &base.nouns)]
+        {
+            assert_eq!(plurals.len(), singulars.len());
+            let mut unique = plurals.clone();
+            unique.sort();
+            unique.dedup();
+            assert_eq!(unique.len(), plurals.len());
+        }
+        for (plural, singular) in set.present_verbs.iter().zip(&base.present_verbs) {
+            assert_eq!(inflect(plural, Form::ThirdPerson), **singular);
+        }
+        assert!(Arc::ptr_eq(&set, &counted(base)));
+    }
+
+    #[test]
+    fn test_themed_entries_pluralise_their_head() {
+        for &theme in Theme::ALL {
+            let entries = theme.words(ListKind::Animal).iter().chain(theme.words(ListKind::Profession));
+            for &entry in entries.filter(|entry| entry.contains(' ') && !IRREGULAR.iter().any(|(s, _)| s == *entry)) {
+                let expected = match entry.split_once(" of ") {
+                    Some((head, rest)) => format!("{} of {}", plural(head), rest),
+                    None => {
+                        let (rest, head) = entry.rsplit_once(' ').unwrap();
+                        format!("{} {}", rest, plural(head))
+                    }
+                };
+                assert_eq!(plural(entry), expected, "{}", entry);
+            }
+        }
+    }
+}
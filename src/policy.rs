@@ -0,0 +1,543 @@
+//! Password composition policies ("at least one digit, 12-64 characters, no spaces").
+//!
+//! A [`PasswordPolicy`] describes the rules a site imposes. [`PolicyGenerator`]
+//! produces passwords that always satisfy them, in two steps:
+//!
+//! 1. **Steering.** Rules that can be met by construction change the inputs
+//!    instead of the output: a required digit forces the `since` template
+//!    (the only one that can draw a year), forbidden spaces replace a
+//!    whitespace separator and are dropped from the line separator of a
+//!    haiku or couplet, and a required symbol guarantees ending punctuation.
+//!    Steering only removes choices, and the recorded decisions already leave
+//!    those choices out, so it needs no extra correction.
+//! 2. **Rejection.** Whatever steering cannot guarantee (length, a `since`
+//!    password that drew a time marker instead of a year, ...) is enforced by
+//!    drawing again until a candidate passes.
+//!
+//! # Rejection penalty
+//!
+//! Rejection sampling with acceptance rate `A` makes every surviving password
+//! `1 / A` times more likely than its recorded decisions say, so its entropy
+//! drops by `log2(1 / A)` bits. `A` is only estimated, once per generator,
+//! from [`TRIAL_CANDIDATES`] draws. To avoid overstating the entropy when the
+//! estimate is lucky, the penalty uses the lower limit of a one-sided 99.9%
+//! Wilson score interval for `A` rather than the estimate itself, and is
+//! subtracted from every password the generator emits. A policy with no rules
+//! rejects nothing and costs nothing.
+//!
+//! With an entropy target ([`PasswordLength::MinEntropy`]) candidates are
+//! generated to the target plus the penalty, so the figure left after the
+//! penalty still meets it.
+
+use crate::formatting::{format_password_with_rng, FormatOptions, Punctuation};
+use crate::generator::{generate_password_of_length_with_rng, GenerateError, PasswordLength, WordConfig};
+use crate::templates::Template;
+use rand::{CryptoRng, RngCore};
+use std::fmt;
+use std::str::FromStr;
+
+/// Number of draws used to estimate how often candidates satisfy a policy.
+pub const TRIAL_CANDIDATES: usize = 512;
+
+/// Standard normal quantile of the one-sided 99.9% confidence limit used for
+/// the acceptance rate.
+const CONFIDENCE_Z: f64 = 3.09;
+
+/// Maximum draws spent on a single password before giving up.
+pub const MAX_ATTEMPTS: usize = 10_000;
+
+/// Rounds spent searching for a rejection penalty that covers itself when
+/// generating to an entropy target.
+const PENALTY_ROUNDS: usize = 4;
+
+/// Composition rules a password must satisfy.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    /// Minimum length in characters.
+    pub min_length: usize,
+    /// Maximum length in characters, if any.
+    pub max_length: Option<usize>,
+    /// Require at least one ASCII digit.
+    pub require_digit: bool,
+    /// Require at least one uppercase letter.
+    pub require_uppercase: bool,
+    /// Require at least one lowercase letter.
+    pub require_lowercase: bool,
+    /// Require at least one symbol (anything that is not a letter, digit or whitespace).
+    pub require_symbol: bool,
+    /// Whether whitespace may appear in the password.
+    pub allow_spaces: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 0,
+            max_length: None,
+            require_digit: false,
+            require_uppercase: false,
+            require_lowercase: false,
+            require_symbol: false,
+            allow_spaces: true,
+        }
+    }
+}
+
+/// A single rule a password failed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PolicyViolation {
+    TooShort { length: usize, min: usize },
+    TooLong { length: usize, max: usize },
+    MissingDigit,
+    MissingUppercase,
+    MissingLowercase,
+    MissingSymbol,
+    ContainsSpace,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyViolation::TooShort { length, min } => {
+                write!(f, "{} characters is shorter than the minimum of {}", length, min)
+            }
+            PolicyViolation::TooLong { length, max } => {
+                write!(f, "{} characters is longer than the maximum of {}", length, max)
+            }
+            PolicyViolation::MissingDigit => write!(f, "no digit"),
+            PolicyViolation::MissingUppercase => write!(f, "no uppercase letter"),
+            PolicyViolation::MissingLowercase => write!(f, "no lowercase letter"),
+            PolicyViolation::MissingSymbol => write!(f, "no symbol"),
+            PolicyViolation::ContainsSpace => write!(f, "contains whitespace"),
+        }
+    }
+}
+
+/// Why no compliant password could be produced.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PolicyError {
+    /// The policy contradicts itself or the requested template.
+    Unsatisfiable(String),
+    /// Candidates pass too rarely to be generated in reasonable time.
+    TooRestrictive { attempts: usize },
+    /// Candidates could not be generated at all.
+    Generate(GenerateError),
+}
+
+impl fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyError::Unsatisfiable(reason) => write!(f, "policy cannot be satisfied: {}", reason),
+            PolicyError::TooRestrictive { attempts } => write!(
+                f,
+                "no candidate satisfied the policy in {} attempts; try relaxing the length limits or the word count",
+                attempts
+            ),
+            PolicyError::Generate(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+impl PasswordPolicy {
+    /// Every rule `password` breaks (empty if it complies).
+    pub fn violations(&self, password: &str) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        let length = password.chars().count();
+
+        if length < self.min_length {
+            violations.push(PolicyViolation::TooShort { length, min: self.min_length });
+        }
+        if let Some(max) = self.max_length {
+            if length > max {
+                violations.push(PolicyViolation::TooLong { length, max });
+            }
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            violations.push(PolicyViolation::MissingDigit);
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            violations.push(PolicyViolation::MissingUppercase);
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            violations.push(PolicyViolation::MissingLowercase);
+        }
+        if self.require_symbol && !password.chars().any(is_symbol) {
+            violations.push(PolicyViolation::MissingSymbol);
+        }
+        if !self.allow_spaces && password.chars().any(char::is_whitespace) {
+            violations.push(PolicyViolation::ContainsSpace);
+        }
+
+        violations
+    }
+
+    /// Check whether `password` satisfies every rule.
+    pub fn is_satisfied_by(&self, password: &str) -> bool {
+        self.violations(password).is_empty()
+    }
+
+    /// Adjust the template and formatting so that as many rules as possible hold
+    /// by construction. Fails if the rules contradict each other or the template.
+    pub fn steer(
+        &self,
+        template: Option<Template>,
+        options: &FormatOptions,
+    ) -> Result<(Option<Template>, FormatOptions), PolicyError> {
+        if let Some(max) = self.max_length {
+            if max < self.min_length {
+                return Err(PolicyError::Unsatisfiable(format!(
+                    "maximum length {} is below minimum length {}",
+                    max, self.min_length
+                )));
+            }
+        }
+
+        // Only the since template and patterns with a {year} draw a year, the one source of digits
+        let template = if self.require_digit {
+            match template {
+                None | Some(Template::Since) => Some(Template::Since),
+                Some(Template::Custom(pattern)) if pattern.draws_year() => Some(Template::Custom(pattern)),
+                Some(other) => {
+                    return Err(PolicyError::Unsatisfiable(format!(
+                        "the '{}' template never contains digits",
+                        other.name()
+                    )))
+                }
+            }
+        } else {
+            template
+        };
+
+        let mut options = options.clone();
+        if !self.allow_spaces && options.separator.chars().any(char::is_whitespace) {
+            options.separator = if self.require_symbol { "-".to_string() } else { String::new() };
+        }
+        if !self.allow_spaces {
+            options.line_separator.retain(|c| !c.is_whitespace());
+        }
+        if self.require_symbol && options.punctuation == Punctuation::None {
+            options.punctuation = Punctuation::Period;
+        }
+
+        Ok((template, options))
+    }
+}
+
+/// Characters that count as symbols: anything that is not a letter, digit or whitespace.
+fn is_symbol(c: char) -> bool {
+    !c.is_alphanumeric() && !c.is_whitespace()
+}
+
+impl FromStr for PasswordPolicy {
+    type Err = String;
+
+    /// Parse a comma-separated rule list such as `digit,upper,symbol,length=12-64,nospace`.
+    ///
+    /// Rules: `digit`, `upper`, `lower`, `symbol`, `nospace`, `min=N`, `max=N`
+    /// and `length=MIN-MAX`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut policy = PasswordPolicy::default();
+        let parse_len = |value: &str| {
+            value.trim().parse::<usize>().map_err(|_| format!("Invalid length in policy: '{}'", value))
+        };
+
+        for rule in s.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+            match rule.split_once('=') {
+                Some(("min", value)) => policy.min_length = parse_len(value)?,
+                Some(("max", value)) => policy.max_length = Some(parse_len(value)?),
+                Some(("length", value)) => {
+                    let (min, max) = value
+                        .split_once('-')
+                        .ok_or_else(|| format!("Expected length=MIN-MAX, got '{}'", rule))?;
+                    policy.min_length = parse_len(min)?;
+                    policy.max_length = Some(parse_len(max)?);
+                }
+                Some(_) => return Err(format!("Unknown policy rule: '{}'", rule)),
+                None => match rule.to_lowercase().as_str() {
+                    "digit" | "digits" => policy.require_digit = true,
+                    "upper" | "uppercase" => policy.require_uppercase = true,
+                    "lower" | "lowercase" => policy.require_lowercase = true,
+                    "symbol" | "symbols" => policy.require_symbol = true,
+                    "nospace" | "nospaces" => policy.allow_spaces = false,
+                    _ => return Err(format!("Unknown policy rule: '{}'", rule)),
+                },
+            }
+        }
+
+        Ok(policy)
+    }
+}
+
+/// A password that satisfies a policy, with its entropy after all corrections.
+#[derive(Clone, Debug)]
+pub struct CompliantPassword {
+    /// The final formatted password.
+    pub text: String,
+    /// Entropy in bits: recorded word and formatting decisions minus the rejection penalty.
+    pub entropy: f64,
+}
+
+/// Generates formatted passwords that always satisfy a [`PasswordPolicy`].
+#[derive(Clone, Debug)]
+pub struct PolicyGenerator {
+    policy: PasswordPolicy,
+    template: Option<Template>,
+    length: PasswordLength,
+    config: WordConfig,
+    options: FormatOptions,
+    acceptance_rate: f64,
+    acceptance_bound: f64,
+}
+
+impl PolicyGenerator {
+    /// Steer the inputs for `policy` and estimate how often candidates pass.
+    pub fn new(
+        policy: PasswordPolicy,
+        template: Option<Template>,
+        length: PasswordLength,
+        config: &WordConfig,
+        options: &FormatOptions,
+    ) -> Result<Self, PolicyError> {
+        Self::new_with_rng(policy, template, length, config, options, &mut rand::thread_rng())
+    }
+
+    /// Steer the inputs for `policy` and estimate how often candidates pass using the given RNG
+    pub fn new_with_rng<R: RngCore + CryptoRng>(
+        policy: PasswordPolicy,
+        template: Option<Template>,
+        length: PasswordLength,
+        config: &WordConfig,
+        options: &FormatOptions,
+        rng: &mut R,
+    ) -> Result<Self, PolicyError> {
+        let (template, options) = policy.steer(template, options)?;
+        if let Some(t) = template.as_ref().filter(|t| config.mix.as_ref().is_some_and(|mix| mix.weight(t) == 0.0)) {
+            return Err(PolicyError::Unsatisfiable(format!("the template mix never draws the '{}' template", t.name())));
+        }
+        let mut generator = Self {
+            policy,
+            template,
+            length,
+            config: config.clone(),
+            options,
+            acceptance_rate: 1.0,
+            acceptance_bound: 1.0,
+        };
+
+        let PasswordLength::MinEntropy(target) = length else {
+            generator.estimate_acceptance(rng)?;
+            return Ok(generator);
+        };
+
+        // Longer candidates pass at a different rate, so look for a penalty that
+        // is at least the one measured when generating to target + penalty
+        let mut penalty = 0.0;
+        for _ in 0..PENALTY_ROUNDS {
+            generator.length = PasswordLength::MinEntropy(target + penalty);
+            generator.estimate_acceptance(rng)?;
+            if generator.rejection_penalty() <= penalty {
+                return Ok(generator);
+            }
+            penalty = generator.rejection_penalty() + 0.5;
+        }
+        generator.length = PasswordLength::MinEntropy(target + penalty);
+        generator.estimate_acceptance(rng)?;
+        Ok(generator)
+    }
+
+    /// Estimate the acceptance rate, and a lower confidence limit for it, from
+    /// [`TRIAL_CANDIDATES`] draws.
+    fn estimate_acceptance<R: RngCore + CryptoRng>(&mut self, rng: &mut R) -> Result<(), PolicyError> {
+        if self.policy == PasswordPolicy::default() {
+            return Ok(());
+        }
+        let mut accepted = 0;
+        for _ in 0..TRIAL_CANDIDATES {
+            if self.policy.is_satisfied_by(&self.candidate(rng)?.0) {
+                accepted += 1;
+            }
+        }
+        if accepted == 0 {
+            return Err(PolicyError::TooRestrictive { attempts: TRIAL_CANDIDATES });
+        }
+        self.acceptance_rate = accepted as f64 / TRIAL_CANDIDATES as f64;
+        self.acceptance_bound = wilson_lower_bound(accepted, TRIAL_CANDIDATES);
+        Ok(())
+    }
+
+    /// Estimated fraction of candidates that satisfy the policy.
+    pub fn acceptance_rate(&self) -> f64 {
+        self.acceptance_rate
+    }
+
+    /// Bits lost to rejection sampling: log2(1 / A), with `A` the lower
+    /// confidence limit of the acceptance rate rather than the estimate, so
+    /// the penalty errs on the side of too many bits.
+    pub fn rejection_penalty(&self) -> f64 {
+        -self.acceptance_bound.log2()
+    }
+
+    /// The formatting options after steering.
+    pub fn format_options(&self) -> &FormatOptions {
+        &self.options
+    }
+
+    /// Generate one compliant password.
+    pub fn generate(&self) -> Result<CompliantPassword, PolicyError> {
+        self.generate_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generate one compliant password using the given RNG
+    pub fn generate_with_rng<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<CompliantPassword, PolicyError> {
+        for _ in 0..MAX_ATTEMPTS {
+            let (text, entropy) = self.candidate(rng)?;
+            if self.policy.is_satisfied_by(&text) {
+                return Ok(CompliantPassword {
+                    text,
+                    entropy: entropy - self.rejection_penalty(),
+                });
+            }
+        }
+        Err(PolicyError::TooRestrictive { attempts: MAX_ATTEMPTS })
+    }
+
+    /// Draw one formatted candidate and its recorded entropy, without checking the policy.
+    fn candidate<R: RngCore + CryptoRng>(&self, rng: &mut R) -> Result<(String, f64), PolicyError> {
+        let generated = generate_password_of_length_with_rng(rng, self.template.clone(), self.length, &self.config)
+            .map_err(PolicyError::Generate)?;
+        let word_entropy = generated.entropy();
+        let formatted = format_password_with_rng(generated.words, &self.options, rng);
+        let entropy = word_entropy + formatted.entropy();
+        Ok((formatted.text, entropy))
+    }
+}
+
+/// Lower limit of the one-sided Wilson score interval for a proportion of
+/// `successes` out of `trials`, at [`CONFIDENCE_Z`].
+fn wilson_lower_bound(successes: usize, trials: usize) -> f64 {
+    let n = trials as f64;
+    let p = successes as f64 / n;
+    let z2 = CONFIDENCE_Z * CONFIDENCE_Z;
+    let centre = p + z2 / (2.0 * n);
+    let spread = CONFIDENCE_Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+    (centre - spread) / (1.0 + z2 / n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn site_policy() -> PasswordPolicy {
+        "digit,upper,symbol,length=12-64,nospace".parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_policy() {
+        let policy = site_policy();
+        assert!(policy.require_digit && policy.require_uppercase && policy.require_symbol);
+        assert!(!policy.require_lowercase);
+        assert!(!policy.allow_spaces);
+        assert_eq!((policy.min_length, policy.max_length), (12, Some(64)));
+        assert!("digit,sparkles".parse::<PasswordPolicy>().is_err());
+        assert!("length=12".parse::<PasswordPolicy>().is_err());
+    }
+
+    #[test]
+    fn test_violations() {
+        let policy = site_policy();
+        assert!(policy.is_satisfied_by("Penguin-since-1987."));
+        assert_eq!(
+            policy.violations("penguin sings"),
+            vec![
+                PolicyViolation::MissingDigit,
+                PolicyViolation::MissingUppercase,
+                PolicyViolation::MissingSymbol,
+                PolicyViolation::ContainsSpace,
+            ]
+        );
+        assert_eq!(policy.violations("A1!"), vec![PolicyViolation::TooShort { length: 3, min: 12 }]);
+    }
+
+    #[test]
+    fn test_steering() {
+        let policy = site_policy();
+        let (template, options) = policy.steer(None, &FormatOptions::default()).unwrap();
+        assert_eq!(template, Some(Template::Since));
+        assert_eq!(options.separator, "-");
+        assert_eq!(options.line_separator, "/");
+
+        assert!(policy.steer(Some(Template::Question), &FormatOptions::default()).is_err());
+        let contradictory = PasswordPolicy { min_length: 10, max_length: Some(5), ..PasswordPolicy::default() };
+        assert!(contradictory.steer(None, &FormatOptions::default()).is_err());
+    }
+
+    #[test]
+    fn test_generated_passwords_comply() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let policy = site_policy();
+        let generator = PolicyGenerator::new_with_rng(
+            policy.clone(), None, PasswordLength::MinWords(3), &WordConfig::default(), &FormatOptions::default(), &mut rng,
+        )
+        .unwrap();
+        // Roughly half of since passwords draw a year instead of a time marker
+        assert!(generator.rejection_penalty() > 0.0);
+        for _ in 0..50 {
+            let password = generator.generate_with_rng(&mut rng).unwrap();
+            assert!(policy.is_satisfied_by(&password.text), "{}", password.text);
+            assert!(password.entropy > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_entropy_target_survives_penalty() {
+        let mut rng = StdRng::seed_from_u64(9);
+        let policy: PasswordPolicy = "digit,nospace".parse().unwrap();
+        let generator = PolicyGenerator::new_with_rng(
+            policy.clone(), None, PasswordLength::MinEntropy(60.0), &WordConfig::default(), &FormatOptions::default(), &mut rng,
+        )
+        .unwrap();
+        for _ in 0..30 {
+            let password = generator.generate_with_rng(&mut rng).unwrap();
+            assert!(policy.is_satisfied_by(&password.text), "{}", password.text);
+            assert!(password.entropy >= 60.0, "{} has {} bits", password.text, password.entropy);
+        }
+    }
+
+    #[test]
+    fn test_penalty_is_conservative() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let generator = PolicyGenerator::new_with_rng(
+            site_policy(), None, PasswordLength::MinWords(3), &WordConfig::default(), &FormatOptions::default(), &mut rng,
+        )
+        .unwrap();
+        assert!(generator.rejection_penalty() > -generator.acceptance_rate().log2());
+        // Every trial passing still leaves room for a rare rejection
+        let bound = wilson_lower_bound(TRIAL_CANDIDATES, TRIAL_CANDIDATES);
+        assert!(bound < 1.0 && bound > 0.95, "{}", bound);
+        assert!(wilson_lower_bound(256, 512) < 0.5);
+    }
+
+    #[test]
+    fn test_no_rules_no_penalty() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let generator = PolicyGenerator::new_with_rng(
+            PasswordPolicy::default(), None, PasswordLength::MinWords(5), &WordConfig::default(), &FormatOptions::default(), &mut rng,
+        )
+        .unwrap();
+        assert_eq!(generator.rejection_penalty(), 0.0);
+    }
+
+    #[test]
+    fn test_impossible_length_is_reported() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let policy = PasswordPolicy { max_length: Some(4), ..PasswordPolicy::default() };
+        let result = PolicyGenerator::new_with_rng(
+            policy, None, PasswordLength::MinWords(5), &WordConfig::default(), &FormatOptions::default(), &mut rng,
+        );
+        assert!(matches!(result, Err(PolicyError::TooRestrictive { .. })));
+    }
+}
@@ -0,0 +1,228 @@
+//! Content ratings for the built-in words.
+//!
+//! Every word is tagged with the lowest [`ContentRating`] it may appear at.
+//! The tag comes from the list a word is in, adjusted by two exception
+//! tables:
+//!
+//! - words in the ordinary lists and the themes are rated
+//!   [`ContentRating::Kids`], unless they are in [`NOT_FOR_KIDS`] (death,
+//!   the occult, weapons, injury and drink), which makes them
+//!   [`ContentRating::Workplace`];
+//! - words in the `*_NSFW` lists are rated [`ContentRating::Explicit`],
+//!   unless they are in [`CHEEKY`] (embarrassing rather than sexual), which
+//!   makes them [`ContentRating::Cheeky`].
+//!
+//! The word getters in [`crate::words`] return every word rated at or below
+//! the rating they're given.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::wordlists::LISTS;
+
+/// How much grown-up content the word lists may contain. Each rating
+/// includes the words of the ratings below it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ContentRating {
+    /// Suitable for children: no death, occult, weapons, injury, drink or
+    /// innuendo.
+    Kids,
+    /// The ordinary lists: dark humour, but nothing about sex.
+    #[default]
+    Workplace,
+    /// Embarrassing and mildly suggestive words ("midlife-crisis", "drunkenly").
+    Cheeky,
+    /// Everything, including explicit sexual terms.
+    Explicit,
+}
+
+impl ContentRating {
+    /// Every rating, from mildest to strongest.
+    pub const ALL: &'static [ContentRating] =
+        &[ContentRating::Kids, ContentRating::Workplace, ContentRating::Cheeky, ContentRating::Explicit];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ContentRating::Kids => "kids",
+            ContentRating::Workplace => "workplace",
+            ContentRating::Cheeky => "cheeky",
+            ContentRating::Explicit => "explicit",
+        }
+    }
+
+    /// The rating the old all-or-nothing NSFW switch stood for.
+    pub fn from_nsfw(nsfw: bool) -> ContentRating {
+        if nsfw {
+            ContentRating::Explicit
+        } else {
+            ContentRating::Workplace
+        }
+    }
+}
+
+impl fmt::Display for ContentRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for ContentRating {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "kids" | "kid" | "children" => Ok(ContentRating::Kids),
+            "workplace" | "work" | "sfw" => Ok(ContentRating::Workplace),
+            "cheeky" => Ok(ContentRating::Cheeky),
+            "explicit" | "nsfw" => Ok(ContentRating::Explicit),
+            _ => Err(format!("Unknown content rating '{}' (expected kids, workplace, cheeky or explicit)", s)),
+        }
+    }
+}
+
+/// Words from the ordinary lists and the themes that children shouldn't see,
+/// grouped by what rules them out. Every safe-for-work word was reviewed
+/// against these groups; a new word needs the same review.
+pub const NOT_FOR_KIDS: &[&str] = &[
+    // Death, funerals and the undead
+    "death-touched", "soul-rending", "mummified", "vampiric", "lycanthropic", "doomed", "bereft", "smote",
+    "wendigo", "skinwalker", "ghoul", "lamia", "banshee", "redcap", "jorogumo", "barghest", "hellhound",
+    "sin-eater", "embalmer", "coroner", "mortician", "undertaker", "resurrectionist", "gravedigger",
+    "taxidermist", "necromancer", "martyrdom", "martyr", "revenant", "wraith", "obsequy", "apocalypse",
+    "armageddon", "nihilism", "coffins", "sarcophagi", "funeral urns", "death masks", "tombstones",
+    "bone thrones", "mourning veils", "taxidermy", "in davy jones' locker",
+    // Curses, demons and the occult
+    "possessed", "damned", "demonic", "infernal", "necromantic", "tormented", "condemned", "accursed",
+    "wretched", "void-spawned", "eldritch", "cursed", "hexed", "forsaken", "malevolent", "nefarious",
+    "insidious", "sinister", "baleful", "exorcist", "heretic", "inquisitor", "crusader", "damnation",
+    "perdition", "blasphemy", "sacrilege", "exorcism", "heresy", "curse", "hex", "cursed dolls",
+    "cursed rings", "in the cursed swamp",
+    // Violence, weapons and crime
+    "blood-soaked", "ravaged", "assassin", "executioner", "torturer", "court-poisoner", "butcher",
+    "mercenary", "bounty", "whipping-boy", "gunner", "cryptobro", "shackles", "guillotines", "gallows",
+    "iron maidens", "torture racks", "executioner hoods", "pulse grenades", "plasma rifles", "laser pistols",
+    "ray guns", "blasters", "phasers", "ion cannons", "photon torpedoes", "lightsabers", "laser swords",
+    "swords", "enchanted swords", "daggers", "crossbows", "longbows", "flails", "halberds", "maces",
+    "scimitars", "battle axes", "war hammers", "morning stars", "katanas", "claymores", "rapiers",
+    "tridents", "throwing stars", "cutlasses", "muskets", "cannonballs", "stalk", "stalks", "stalking",
+    "embezzle", "embezzles", "embezzling", "violently", "maniacally", "annihilates", "humiliates",
+    // Injury and disease
+    "traumatized", "gangrenous", "malignant", "contaminated", "swollen", "frostbitten", "blighted",
+    "stricken", "afflicted", "plague-doctor", "leech-collector", "pestilence", "plague", "famine",
+    "contagion", "blight", "scourge", "miasma",
+    // Drink
+    "hungover", "tavern-wench", "bartender", "sommelier", "oenologist", "cellarmaster", "rum runner",
+    "hedonism", "rum barrels", "dwarven ale", "guzzle", "guzzles", "guzzling",
+];
+
+/// Words from the `*_NSFW` lists that are embarrassing rather than explicit.
+pub const CHEEKY: &[&str] = &[
+    // Adjectives
+    "freshly-divorced", "emotionally-unavailable", "heavily-medicated", "chronically-single", "overcompensating",
+    "tax-evading", "recently-incarcerated", "questionably-legal", "aggressively-single",
+    // Adverbs
+    "inappropriately", "drunkenly", "shamelessly", "guiltily", "flirtatiously",
+    // Professions
+    "masseuse", "poolboy", "proctologist", "divorce-lawyer", "telemarketer", "debt-collector", "yoga-instructor",
+    "personal-trainer",
+    // Nouns
+    "puberty", "midlife-crisis", "alimony", "incontinence", "flatulence", "walk-of-shame", "innuendo",
+    // Objects
+    "leather-pants", "hemorrhoid-cream", "adult-diapers", "back-massagers", "cucumbers", "bananas", "melons",
+    "feather-dusters", "silk-scarves",
+    // Verbs
+    "twerk", "twerks", "twerking",
+    // Prohibitions
+    "awkwardly fumbles", "chronically disappoints",
+];
+
+/// The rating of a word from one of the ordinary lists (`nsfw == false`) or
+/// one of the `*_NSFW` lists.
+fn tag(word: &str, nsfw: bool) -> ContentRating {
+    match (nsfw, nsfw && CHEEKY.contains(&word), NOT_FOR_KIDS.contains(&word)) {
+        (true, true, _) => ContentRating::Cheeky,
+        (true, false, _) => ContentRating::Explicit,
+        (false, _, true) => ContentRating::Workplace,
+        (false, _, false) => ContentRating::Kids,
+    }
+}
+
+/// The rating of a built-in word, or `None` if it's in no list.
+pub fn rating(word: &str) -> Option<ContentRating> {
+    LISTS.iter().find(|list| list.words.contains(&word)).map(|list| tag(word, list.nsfw))
+}
+
+/// The words of an ordinary list and its `*_NSFW` counterpart that are rated
+/// at or below `rating`.
+pub(crate) fn rated(sfw: &[&'static str], nsfw: &[&'static str], rating: ContentRating) -> Vec<&'static str> {
+    let sfw = sfw.iter().filter(|word| tag(word, false) <= rating);
+    let nsfw = nsfw.iter().filter(|word| tag(word, true) <= rating);
+    sfw.chain(nsfw).copied().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::themes::{apply, Theme, ThemeMode};
+    use crate::wordset::{ListKind, WordSet};
+
+    #[test]
+    fn test_exceptions_are_in_the_lists_they_adjust() {
+        let themed = |word: &&str| {
+            Theme::ALL.iter().any(|theme| ListKind::ALL.iter().any(|&kind| theme.words(kind).contains(word)))
+        };
+        for word in NOT_FOR_KIDS {
+            assert!(rating(word) == Some(ContentRating::Workplace) || themed(word), "{}", word);
+        }
+        for word in CHEEKY {
+            assert_eq!(rating(word), Some(ContentRating::Cheeky), "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_tags_every_word_by_list_and_exception() {
+        assert_eq!(rating("walrus"), Some(ContentRating::Kids));
+        assert_eq!(rating("gangrenous"), Some(ContentRating::Workplace));
+        for word in ["cursed", "vampiric", "ghoul", "plague-doctor", "tombstones", "battle axes", "war hammers"] {
+            assert_eq!(rating(word), Some(ContentRating::Workplace), "{}", word);
+        }
+        assert_eq!(rating("midlife-crisis"), Some(ContentRating::Cheeky));
+        assert_eq!(rating("dominatrix"), Some(ContentRating::Explicit));
+        assert_eq!(rating("zzyzx"), None);
+    }
+
+    #[test]
+    fn test_kids_words_avoid_dark_stems() {
+        const STEMS: &[&str] = &[
+            "blood", "death", "corpse", "grave", "tomb", "coffin", "funeral", "bone", "skull", "undead",
+            "zombie", "vampir", "ghoul", "curse", "hex", "demon", "damn", "hell", "doom", "kill", "murder",
+            "assassin", "execution", "tortur", "poison", "butcher", "sword", "dagger", "axe",
+            "rifle", "pistol", "gun", "grenade", "cannon", "musket", "plague", "wound", "drunk", "hungover",
+            "ale", "rum", "wine", "beer", "whisk",
+        ];
+        let ok = ["doomscroller", "swordfish", "whisks"];
+        let base = WordSet::builtin(ContentRating::Kids);
+        let sets: Vec<WordSet> =
+            Theme::ALL.iter().map(|&theme| apply(base, &[theme], ThemeMode::Augment, ContentRating::Kids)).collect();
+        let mut dark: Vec<&str> = sets
+            .iter()
+            .flat_map(|set| ListKind::ALL.iter().flat_map(|&kind| set.list(kind).iter().map(|word| &**word)))
+            .filter(|word| {
+                let dark = |part: &str| !ok.contains(&part) && STEMS.iter().any(|stem| part.starts_with(stem));
+                word.split([' ', '-']).any(dark)
+            })
+            .collect();
+        dark.sort();
+        dark.dedup();
+        assert!(dark.is_empty(), "rated for kids: {:?}", dark);
+    }
+
+    #[test]
+    fn test_ratings_include_the_words_below_them() {
+        let lists = |rating| rated(&["walrus", "gangrenous"], &["midlife-crisis", "dominatrix"], rating);
+        assert_eq!(lists(ContentRating::Kids), vec!["walrus"]);
+        assert_eq!(lists(ContentRating::Workplace), vec!["walrus", "gangrenous"]);
+        assert_eq!(lists(ContentRating::Cheeky), vec!["walrus", "gangrenous", "midlife-crisis"]);
+        assert_eq!(lists(ContentRating::Explicit), vec!["walrus", "gangrenous", "midlife-crisis", "dominatrix"]);
+    }
+}
@@ -0,0 +1,340 @@
+//! Rhymes between word list entries, for the couplet template.
+//!
+//! Two entries rhyme when their last words sound alike from the stressed
+//! vowel on: "pickles" and "nickels", "socks" and "fox", "kangaroo" and
+//! "shrew". [`rhyme`] reads that sound from the spelling: the stress is found
+//! from the ending ("-le", "-er" and "-y" are never stressed, "-tion" and
+//! "-ity" stress the syllable before them), and spellings that sound the same
+//! are merged ("ck" and "k", "ai" and "a_e", "oo" and "ew"). Words the rules
+//! would misread are spelled the way they sound in [`RESPELLINGS`], the
+//! bundled pronunciation data.
+//!
+//! A [`RhymeIndex`] groups the animals, objects and nouns of a word set by
+//! their rhyme and keeps the entries that rhyme with at least one other.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::cache::{Cache, Source};
+use crate::wordinfo::{is_vowel, said_apart};
+use crate::wordset::{ListKind, WordSet};
+
+/// Words whose spelling misleads the rules, spelled the way they sound. An
+/// entry is looked up by its last word.
+pub const RESPELLINGS: &[(&str, &str)] = &[
+    // Animals
+    ("bigfoot", "bigfut"),
+    ("chameleon", "kameelyun"),
+    ("ghoul", "gool"),
+    ("hoopoe", "hoopoo"),
+    ("jerboa", "jerbowa"),
+    ("kitsune", "kitsoonay"),
+    ("macaque", "makak"),
+    ("magpie", "magpigh"),
+    ("mokele", "mokelay"),
+    ("penguin", "pengwin"),
+    ("pigeon", "pidjun"),
+    ("sloth", "slowth"),
+    ("squid", "skwid"),
+    ("tarsier", "tarseeyer"),
+    ("wolverine", "wolvereen"),
+    // Objects
+    ("biscuits", "biskits"),
+    ("cushions", "kushuns"),
+    ("massagers", "massahzhers"),
+    ("rapiers", "raypeeyers"),
+    ("ravioli", "raveeohly"),
+    ("scimitars", "scimiters"),
+    ("typewriters", "typewryters"),
+    ("visors", "vyzors"),
+    // Nouns
+    ("dread", "dred"),
+    ("ennui", "onwee"),
+    ("famine", "fammin"),
+    ("hogwash", "hogwosh"),
+    ("omniscience", "omnishence"),
+    ("plague", "playg"),
+    ("wifi", "wyfy"),
+    ("word", "werd"),
+];
+
+/// Endings that put the stress on the syllable before them: "gravity",
+/// "salvation", "biology".
+const PRE_STRESSED: &[&str] = &[
+    "tion", "sion", "cion", "gion", "cian", "tian", "sian", "ity", "ety", "logy", "graphy", "metry", "ical",
+    "ic", "ian", "ial", "ious", "eous", "ium", "ia", "acy", "ery", "ary", "ory", "ify", "ible", "able",
+];
+
+/// Endings that are never stressed, so the stress falls on the syllable
+/// before: "pickle", "badger", "pony".
+const UNSTRESSED: &[&str] = &[
+    "le", "el", "er", "or", "ar", "ur", "y", "ey", "ie", "i", "ow", "et", "en", "on", "in", "ing", "a", "o",
+    "us", "is", "ism", "ist", "al", "an", "um", "ish", "ard", "ent", "ant", "ence", "ance", "ure", "age", "ock",
+    "em", "il", "ess", "ness", "ment", "ful", "less", "dom", "ship", "hood", "ling", "let", "kin", "ton", "man",
+    "ster", "ous", "un",
+];
+
+/// The sound an entry rhymes on: its last word from the stressed vowel on,
+/// spelled as it sounds. "pickles" and "nickels" both give "ikels".
+pub fn rhyme(entry: &str) -> String {
+    let word = last_word(entry).to_lowercase();
+    let word = RESPELLINGS.iter().find(|(w, _)| *w == word).map_or(word.as_str(), |(_, sound)| sound);
+    let (stem, ending) = singular(word);
+    let chars: Vec<char> = stem.chars().collect();
+    let stressed: String = chars[stressed(&chars)..].iter().collect();
+    sound(&stressed) + ending
+}
+
+/// Whether `a` and `b` rhyme: the same sound, but not the same word.
+pub fn rhymes(a: &str, b: &str) -> bool {
+    last_word(a) != last_word(b) && rhyme(a) == rhyme(b)
+}
+
+fn last_word(entry: &str) -> &str {
+    entry.rsplit(|c: char| c.is_whitespace() || c == '-').next().unwrap_or(entry)
+}
+
+/// A plural's singular and the sound of its ending: "socks" is "sock" and
+/// "s", "vases" is "vase" and "is".
+fn singular(word: &str) -> (String, &'static str) {
+    if let Some(stem) = word.strip_suffix("ies").filter(|stem| stem.len() > 1) {
+        return (format!("{}y", stem), "s");
+    }
+    if ["sses", "xes", "ches", "shes"].iter().any(|end| word.ends_with(end)) {
+        return (word[..word.len() - 2].to_string(), "is");
+    }
+    if ["ces", "ges", "ses", "zes"].iter().any(|end| word.ends_with(end)) {
+        return (word[..word.len() - 1].to_string(), "is");
+    }
+    match word.strip_suffix('s') {
+        Some(stem) if stem.len() > 2 && !["s", "u", "i"].iter().any(|end| stem.ends_with(end)) => {
+            (stem.to_string(), "s")
+        }
+        _ => (word.to_string(), ""),
+    }
+}
+
+/// Where the stressed vowel of `word` starts.
+fn stressed(word: &[char]) -> usize {
+    // Vowel groups, with pairs said apart ("biology") split in two
+    let mut groups: Vec<(usize, usize)> = Vec::new();
+    for i in 0..word.len() {
+        if !is_vowel(word, i) {
+            continue;
+        }
+        match groups.last_mut() {
+            Some(group) if group.1 == i && !said_apart(word, i) => group.1 = i + 1,
+            _ => groups.push((i, i + 1)),
+        }
+    }
+    // A silent final "e" is no syllable, but "-le" after a consonant is
+    let n = word.len();
+    if groups.len() > 1 && groups.last() == Some(&(n - 1, n)) && word[n - 1] == 'e' {
+        let syllabic_le = n >= 3 && word[n - 2] == 'l' && !is_vowel(word, n - 3);
+        if !is_vowel(word, n - 2) && !syllabic_le {
+            groups.pop();
+        }
+    }
+    let Some(&(last, _)) = groups.last() else {
+        return 0;
+    };
+
+    let text: String = word.iter().collect();
+    let ending = |endings: &[&str]| endings.iter().filter(|end| text.ends_with(*end)).map(|end| n - end.len()).max_by_key(|&at| n - at);
+    if let Some(at) = ending(PRE_STRESSED) {
+        if let Some(&(start, _)) = groups.iter().rev().find(|&&(start, _)| start < at) {
+            return start;
+        }
+    }
+    if groups.len() > 1 {
+        let (before, before_end) = groups[groups.len() - 2];
+        let unstressed = UNSTRESSED.iter().any(|end| text.ends_with(end) && (before_end..=last).contains(&(n - end.len())));
+        if unstressed {
+            return before;
+        }
+    }
+    last
+}
+
+/// `part` spelled the way it sounds, so that spellings of the same sound
+/// compare equal.
+fn sound(part: &str) -> String {
+    let mut s = part.to_string();
+    for (from, to) in [("tion", "shun"), ("sion", "shun"), ("cian", "shun"), ("tian", "shun"), ("sian", "shun"), ("ture", "cher")] {
+        s = s.replace(from, to);
+    }
+    // "rogue", "macaque"
+    for (from, to) in [("gue", "g"), ("que", "k")] {
+        if let Some(stem) = s.strip_suffix(from).filter(|stem| stem.ends_with(|c: char| "aeiou".contains(c))) {
+            s = format!("{}{}", stem, to);
+        }
+    }
+    for (from, to) in [("igh", "I"), ("ck", "k"), ("ph", "f"), ("qu", "kw"), ("wh", "w"), ("x", "ks"), ("dg", "j")] {
+        s = s.replace(from, to);
+    }
+    if let Some(stem) = s.strip_suffix("ge") {
+        s = format!("{}je", stem);
+    }
+
+    let source: Vec<char> = s.chars().collect();
+    let mut chars: Vec<char> = source
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| match c {
+            'c' if source.get(i + 1).is_some_and(|&next| "eiy".contains(next)) => 's',
+            'c' => 'k',
+            c => c,
+        })
+        .collect();
+
+    // A silent "e" lengthens the vowel before it: "cake", "bone"
+    let n = chars.len();
+    let lone_vowel = |i: usize| "aeiou".contains(chars[i]) && (i == 0 || !"aeiou".contains(chars[i - 1]));
+    if n >= 3 && chars[n - 1] == 'e' && !"aeiouy".contains(chars[n - 2]) && lone_vowel(n - 3) {
+        chars[n - 3] = chars[n - 3].to_ascii_uppercase();
+        chars.remove(n - 1);
+    }
+    // Doubled consonants sound single
+    chars.dedup_by(|c, before| c == before && !"aeiouy".contains(*c));
+    let mut s: String = chars.into_iter().collect();
+
+    if let Some(stem) = s.strip_suffix("le").filter(|stem| stem.ends_with(|c: char| !"aeiouy".contains(c))) {
+        s = format!("{}el", stem);
+    }
+    if let Some(stem) = s.strip_suffix("ead") {
+        s = format!("{}ed", stem);
+    }
+
+    // A stressed final "y" or "ie" is "eye", an unstressed one "ee"
+    for end in ["y", "ie", "ey", "i"] {
+        if let Some(stem) = s.strip_suffix(end) {
+            let stressed = stem.chars().all(|c| !"aeiouyAEIOU".contains(c));
+            if end != "ey" && end != "i" && stressed {
+                s = format!("{}I", stem);
+            } else if !stem.ends_with(|c: char| "aeo".contains(c)) {
+                s = format!("{}E", stem);
+            }
+            break;
+        }
+    }
+    if let Some(stem) = s.strip_suffix("ow") {
+        s = format!("{}O", stem);
+    }
+    for end in ["ou", "u"] {
+        if let Some(stem) = s.strip_suffix(end) {
+            s = format!("{}U", stem);
+            break;
+        }
+    }
+    for (from, to) in [
+        ("ai", "A"), ("ay", "A"), ("ei", "A"), ("ey", "A"), ("ee", "E"), ("ea", "E"), ("oa", "O"), ("oe", "O"),
+        ("oo", "U"), ("ew", "U"), ("ue", "U"), ("ui", "U"), ("au", "aw"), ("oy", "oi"), ("ou", "ow"),
+        ("ir", "er"), ("ur", "er"),
+    ] {
+        s = s.replace(from, to);
+    }
+    s
+}
+
+/// The entries of some lists grouped by [`rhyme`].
+#[derive(Debug)]
+pub struct RhymeIndex {
+    /// For each of [`RhymeIndex::KINDS`], its entries that rhyme with another
+    /// entry of any of the lists.
+    rhyming: [Vec<Arc<str>>; 3],
+    /// Every rhyme sound with entries of each kind.
+    classes: HashMap<String, [Vec<Arc<str>>; 3]>,
+    /// The rhyme sound of every entry, so couplets don't work it out again.
+    sounds: HashMap<Arc<str>, String>,
+}
+
+impl RhymeIndex {
+    /// The lists a couplet ends its lines with.
+    pub const KINDS: [ListKind; 3] = [ListKind::Object, ListKind::Noun, ListKind::Animal];
+
+    /// Group the animals, objects and nouns of `words` by rhyme.
+    pub fn new(words: &WordSet) -> RhymeIndex {
+        let mut classes: HashMap<String, [Vec<Arc<str>>; 3]> = HashMap::new();
+        let mut sounds = HashMap::new();
+        for (i, &kind) in Self::KINDS.iter().enumerate() {
+            for entry in words.list(kind) {
+                let sound = sounds.entry(Arc::clone(entry)).or_insert_with(|| rhyme(entry));
+                classes.entry(sound.clone()).or_default()[i].push(Arc::clone(entry));
+            }
+        }
+        let mut rhyming: [Vec<Arc<str>>; 3] = Default::default();
+        for (i, &kind) in Self::KINDS.iter().enumerate() {
+            let rhymes =
+                |entry: &&Arc<str>| partners(&classes, &sounds[*entry], entry).iter().any(|list| !list.is_empty());
+            rhyming[i] = words.list(kind).iter().filter(rhymes).cloned().collect();
+        }
+        RhymeIndex { rhyming, classes, sounds }
+    }
+
+    /// The entries of `Self::KINDS[kind]` that something rhymes with.
+    pub fn rhyming(&self, kind: usize) -> &[Arc<str>] {
+        &self.rhyming[kind]
+    }
+
+    /// For each of [`RhymeIndex::KINDS`], the entries that rhyme with `entry`.
+    pub fn partners(&self, entry: &str) -> [Vec<Arc<str>>; 3] {
+        match self.sounds.get(entry) {
+            Some(sound) => partners(&self.classes, sound, entry),
+            None => partners(&self.classes, &rhyme(entry), entry),
+        }
+    }
+}
+
+/// The entries of `classes` with the rhyme `sound` of `entry`, except those
+/// ending in the same word.
+fn partners(classes: &HashMap<String, [Vec<Arc<str>>; 3]>, sound: &str, entry: &str) -> [Vec<Arc<str>>; 3] {
+    let Some(class) = classes.get(sound) else {
+        return Default::default();
+    };
+    let rest =
+        |list: &Vec<Arc<str>>| list.iter().filter(|other| last_word(other) != last_word(entry)).cloned().collect();
+    [rest(&class[0]), rest(&class[1]), rest(&class[2])]
+}
+
+static INDEXES: Cache<Source<WordSet>, RhymeIndex> = Cache::new();
+
+/// The rhyme index of `words`, built once per set and shared while it's used.
+pub fn index(words: &Arc<WordSet>) -> Arc<RhymeIndex> {
+    INDEXES.get(Source::of(words), || RhymeIndex::new(words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rating::ContentRating;
+
+    #[test]
+    fn test_rhymes() {
+        assert!(rhymes("pickles", "nickels"));
+        assert!(rhymes("socks", "fox"));
+        assert!(rhymes("kangaroo", "shrew"));
+        assert!(rhymes("headphones", "tombstones"));
+        assert!(rhymes("frustration", "salvation"));
+        assert!(rhymes("rune", "raccoon"));
+        assert!(rhymes("safe-word", "hummingbird"));
+        assert!(!rhymes("cassettes", "spreadsheets"));
+        assert!(!rhymes("pigeon", "chameleon"));
+        assert!(!rhymes("socks", "sock"));
+        assert!(!rhymes("pickles", "pickle"));
+    }
+
+    #[test]
+    fn test_partners_rhyme_and_are_other_words() {
+        let index = index(&WordSet::shared(ContentRating::Explicit));
+        for (kind, _) in RhymeIndex::KINDS.iter().enumerate() {
+            assert!(index.rhyming(kind).len() > 10);
+            for entry in index.rhyming(kind) {
+                let partners = index.partners(entry);
+                assert!(partners.iter().any(|list| !list.is_empty()), "{}", entry);
+                for partner in partners.iter().flatten() {
+                    assert!(rhymes(entry, partner), "{} / {}", entry, partner);
+                }
+            }
+        }
+    }
+}
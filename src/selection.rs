@@ -0,0 +1,127 @@
+//! Best-of-N candidate selection and its entropy cost.
+//!
+//! `mkpass` has always generated more candidates than requested and shown only
+//! the strongest ones. Filtering on entropy narrows the set of passwords that
+//! can actually be emitted, so the bits of each survivor overstate what an
+//! attacker faces. This module makes the selection explicit and reports how
+//! much entropy it costs.
+//!
+//! # Penalty
+//!
+//! With `n` candidates drawn independently, the `k` strongest kept, and each
+//! output picked uniformly from those `k`, any particular password `x` comes
+//! out with probability at most `n * P(x) / k`: it can occupy at most all `n`
+//! draws, and each emitted slot picks one of the `k` survivors. Its surprisal
+//! therefore drops by at most `log2(n / k)` bits, roughly `log2(1 / 0.3)` ≈
+//! 1.74 bits for the default strategy. That bound is what gets subtracted.
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// How many candidates to generate per password and what share of them to keep.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SelectionStrategy {
+    /// Candidates generated per requested password.
+    pub oversample: usize,
+    /// Fraction of candidates (strongest first) that survive selection.
+    pub keep_fraction: f64,
+}
+
+impl SelectionStrategy {
+    /// Keep the strongest 30% of ten candidates per password (mkpass default).
+    pub const STRONGEST: Self = Self { oversample: 10, keep_fraction: 0.3 };
+
+    /// No selection: every generated candidate is emitted, unbiased.
+    pub const UNIFORM: Self = Self { oversample: 1, keep_fraction: 1.0 };
+
+    /// Number of candidates to generate for `count` passwords.
+    pub fn candidates_for(&self, count: usize) -> usize {
+        count * self.oversample.max(1)
+    }
+
+    /// Number of candidates that survive selection out of `candidates`.
+    pub fn kept_of(&self, candidates: usize) -> usize {
+        let kept = (candidates as f64 * self.keep_fraction).ceil() as usize;
+        kept.clamp(candidates.min(1), candidates)
+    }
+
+    /// Upper bound on the entropy lost to selection, in bits: log2(n / k).
+    pub fn entropy_penalty(&self, count: usize) -> f64 {
+        let candidates = self.candidates_for(count);
+        if candidates == 0 {
+            return 0.0;
+        }
+        (candidates as f64 / self.kept_of(candidates) as f64).log2()
+    }
+}
+
+impl Default for SelectionStrategy {
+    fn default() -> Self {
+        Self::STRONGEST
+    }
+}
+
+/// Keep the strongest candidates according to `strategy` and return `count` of them
+/// in random order.
+///
+/// Candidates are ranked by `entropy` (highest first), the top
+/// [`keep_fraction`](SelectionStrategy::keep_fraction) survive, and `count`
+/// of those are drawn uniformly. Subtract
+/// [`SelectionStrategy::entropy_penalty`] from any entropy shown for the results.
+pub fn select_strongest<T>(
+    mut candidates: Vec<T>,
+    count: usize,
+    strategy: &SelectionStrategy,
+    entropy: impl Fn(&T) -> f64,
+    rng: &mut impl Rng,
+) -> Vec<T> {
+    // Sort by entropy (descending) - keep the most secure
+    candidates.sort_by(|a, b| entropy(b).total_cmp(&entropy(a)));
+
+    let kept = strategy.kept_of(candidates.len());
+    candidates.truncate(kept);
+
+    // Shuffle the survivors (adds unpredictability while maintaining quality)
+    candidates.shuffle(rng);
+    candidates.truncate(count);
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_default_penalty_matches_keep_fraction() {
+        let penalty = SelectionStrategy::STRONGEST.entropy_penalty(3);
+        // 30 candidates, 9 kept
+        assert!((penalty - (30.0_f64 / 9.0).log2()).abs() < 1e-9);
+        assert!(penalty <= (1.0 / 0.3_f64).log2() + 1e-9);
+    }
+
+    #[test]
+    fn test_uniform_has_no_penalty() {
+        assert_eq!(SelectionStrategy::UNIFORM.entropy_penalty(5), 0.0);
+        assert_eq!(SelectionStrategy::UNIFORM.candidates_for(5), 5);
+    }
+
+    #[test]
+    fn test_select_strongest_keeps_top_candidates() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let candidates: Vec<u32> = (0..30).collect();
+        let picked = select_strongest(candidates, 3, &SelectionStrategy::STRONGEST, |c| *c as f64, &mut rng);
+        assert_eq!(picked.len(), 3);
+        // Only the 9 strongest (21..=29) can survive
+        assert!(picked.iter().all(|c| *c >= 21), "{:?}", picked);
+    }
+
+    #[test]
+    fn test_uniform_selection_returns_everything() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut picked = select_strongest(vec![3, 1, 2], 3, &SelectionStrategy::UNIFORM, |c| *c as f64, &mut rng);
+        picked.sort();
+        assert_eq!(picked, vec![1, 2, 3]);
+    }
+}
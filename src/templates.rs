@@ -1,17 +1,33 @@
 //! Template system for generating diverse password structures.
 //!
-//! This module provides 10 different sentence templates that create
+//! This module provides 12 different sentence templates that create
 //! varied and memorable passwords while maintaining grammatical correctness.
 
+use std::sync::Arc;
+
 use rand::Rng;
 
-use crate::generator::WordConfig;
+use crate::article::fix_articles;
+use crate::cache::{Cache, Source};
+use crate::generator::{GenerateError, WordConfig};
+use crate::lang::{self, Language};
+use crate::memorability::Draw;
+use crate::password::{Decision, GeneratedPassword};
+use crate::pattern::Pattern;
+use crate::plural::{self, Count};
+use crate::rhyme::{self, RhymeIndex};
+use crate::wordinfo::{phrase_syllables, syllable_counts};
 use crate::words::*;
+use crate::wordset::{ListKind, WordSet};
 
 /// Marker token indicating a comma should follow the previous word.
 /// Used for introductory phrases like "In the shadows, the penguin..."
 pub const COMMA_MARKER: &str = "__COMMA__";
 
+/// Marker token between the lines of a haiku or couplet. Formatting joins lines with
+/// [`FormatOptions::line_separator`](crate::formatting::FormatOptions::line_separator).
+pub const LINE_MARKER: &str = "__LINE__";
+
 /// Convert comma markers into actual commas attached to the preceding word.
 /// ["In", "the", "shadows", "__COMMA__", "the"] becomes ["In", "the", "shadows,", "the"]
 pub fn render_commas(words: Vec<String>) -> Vec<String> {
@@ -32,7 +48,7 @@ pub fn render_commas(words: Vec<String>) -> Vec<String> {
 }
 
 /// The available password generation templates.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Template {
     /// "the grumpy walrus juggles pickles"
     Declarative,
@@ -54,6 +70,12 @@ pub enum Template {
     ShouldNot,
     /// "chaos requires more coffee"
     Requires,
+    /// "the caffeinated / narwhal smuggles rubber ducks / near a sleepy owl"
+    Haiku,
+    /// "the grumpy walrus juggles pickles / the sleepy wizard owes nickels"
+    Couplet,
+    /// A sentence shape written in the pattern language (see [`crate::pattern`]).
+    Custom(Arc<Pattern>),
 }
 
 impl Template {
@@ -69,12 +91,40 @@ impl Template {
         Template::Somewhere,
         Template::ShouldNot,
         Template::Requires,
+        Template::Haiku,
+        Template::Couplet,
     ];
 
     /// Select a random template.
     pub fn random(rng: &mut impl Rng) -> Self {
         let idx = rng.gen_range(0..Self::ALL.len());
-        Self::ALL[idx]
+        Self::ALL[idx].clone()
+    }
+
+    /// A template for `pattern`.
+    pub fn custom(pattern: Pattern) -> Self {
+        Template::Custom(Arc::new(pattern))
+    }
+
+    /// The word lists the template may draw from, including those used to
+    /// extend it.
+    pub fn lists(&self) -> &[ListKind] {
+        use ListKind::*;
+        match self {
+            Template::Declarative => &[Adjective, Animal, Profession, Noun, PresentVerb, Object, Adverb],
+            Template::Imperative => &[Imperative, Adjective, Animal, Profession, Noun],
+            Template::Question => &[QuestionStarter, Adjective, Animal, Profession, Noun, BaseVerb, Object],
+            Template::Warning => &[Warning, BaseVerb, Adjective, Animal, Profession, Noun, Object],
+            Template::Fragment => &[Number, Adverb, Adjective, Animal, Profession, Noun, PresentVerb, Object],
+            Template::Versus => &[Adjective, Animal, Profession, Noun, Conflict],
+            Template::Since => &[Adverb, Gerund, Object, Noun, TimeMarker, Adjective, Animal, Profession],
+            Template::Somewhere => &[Location, Adjective, Animal, Profession, Noun, Gerund, Object],
+            Template::ShouldNot => &[Adjective, Animal, Profession, Noun, Prohibition, BaseVerb, Object],
+            Template::Requires => &[Adjective, Noun, Animal, Profession, Demand, Object],
+            Template::Haiku => &[Adjective, Animal, Profession, Noun, PresentVerb, Object],
+            Template::Couplet => &[Adjective, Animal, Profession, Noun, PresentVerb, Object],
+            Template::Custom(pattern) => pattern.lists(),
+        }
     }
 
     /// Parse a template name from a string.
@@ -90,6 +140,8 @@ impl Template {
             "somewhere" => Some(Template::Somewhere),
             "shouldnot" | "should-not" | "should_not" => Some(Template::ShouldNot),
             "requires" => Some(Template::Requires),
+            "haiku" => Some(Template::Haiku),
+            "couplet" => Some(Template::Couplet),
             _ => None,
         }
     }
@@ -107,6 +159,9 @@ impl Template {
             Template::Somewhere => "somewhere",
             Template::ShouldNot => "shouldnot",
             Template::Requires => "requires",
+            Template::Haiku => "haiku",
+            Template::Couplet => "couplet",
+            Template::Custom(_) => "custom",
         }
     }
 
@@ -114,124 +169,390 @@ impl Template {
     ///
     /// The `min_words` parameter is a hint for how many content words to include.
     /// Some templates have fixed structures and may not honor this exactly.
-    pub fn generate(&self, rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<String> {
-        // Since template returns Vec<String> directly due to dynamic year generation
-        if matches!(self, Template::Since) {
-            return generate_since(rng, min_words, config);
+    ///
+    /// The returned password records every random decision taken, so its
+    /// entropy is exact rather than estimated from the finished words.
+    ///
+    /// # Errors
+    ///
+    /// If the template can't be generated with `config`: it isn't written in
+    /// the configured language, or draws from a list with no words left (see
+    /// [`WordConfig::can_generate`]).
+    pub fn generate(
+        &self,
+        rng: &mut impl Rng,
+        min_words: usize,
+        config: &WordConfig,
+    ) -> Result<GeneratedPassword, GenerateError> {
+        if !config.can_generate(self) {
+            return Err(GenerateError::Unavailable(self.clone()));
+        }
+        Ok(self.generate_available(rng, min_words, config))
+    }
+
+    /// [`generate`](Self::generate) a template already known to be available.
+    pub(crate) fn generate_available(
+        &self,
+        rng: &mut impl Rng,
+        min_words: usize,
+        config: &WordConfig,
+    ) -> GeneratedPassword {
+        let mut b = Builder::new(RngSource(rng), config.memorability);
+        self.build(&mut b, min_words, config);
+        self.finish(b, config)
+    }
+
+    /// Generate a password with at least `min_bits` of exact entropy.
+    ///
+    /// The template is built in its shortest form, then prepositional phrases
+    /// ("beneath a soggy walrus") are appended until the recorded decisions
+    /// reach the target. A haiku or couplet gets whole extra stanzas instead.
+    /// Whether to extend depends only on the choices made so far, so the
+    /// recorded entropy stays exact.
+    ///
+    /// # Errors
+    ///
+    /// If the template can't be generated with `config`, `min_bits` isn't
+    /// finite, or an extension adds no entropy (every list it draws from has a
+    /// single entry), so the target is never reached.
+    pub fn generate_with_min_entropy(
+        &self,
+        rng: &mut impl Rng,
+        min_bits: f64,
+        config: &WordConfig,
+    ) -> Result<GeneratedPassword, GenerateError> {
+        if !config.can_generate(self) {
+            return Err(GenerateError::Unavailable(self.clone()));
+        }
+        if !min_bits.is_finite() {
+            return Err(GenerateError::InvalidTarget(min_bits));
+        }
+        let mut b = Builder::new(RngSource(rng), config.memorability);
+        self.build(&mut b, 0, config);
+        while b.entropy() < min_bits {
+            let reached = b.entropy();
+            self.extend(&mut b, config);
+            // An extension draws the same way every time, so one that adds
+            // nothing never will
+            if b.entropy() <= reached {
+                return Err(GenerateError::Stalled { reached, target: min_bits });
+            }
+        }
+        Ok(self.finish(b, config))
+    }
+
+    fn build(&self, b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+        if config.language != Language::English {
+            return lang::build(config.language, self, b, min_words);
+        }
+        match self {
+            Template::Declarative => generate_declarative(b, min_words, config),
+            Template::Imperative => generate_imperative(b, min_words, config),
+            Template::Question => generate_question(b, min_words, config),
+            Template::Warning => generate_warning(b, min_words, config),
+            Template::Fragment => generate_fragment(b, min_words, config),
+            Template::Versus => generate_versus(b, min_words, config),
+            Template::Since => generate_since(b, min_words, config),
+            Template::Somewhere => generate_somewhere(b, min_words, config),
+            Template::ShouldNot => generate_should_not(b, min_words, config),
+            Template::Requires => generate_requires(b, min_words, config),
+            Template::Haiku | Template::Couplet => {
+                self.push_stanza(b, config);
+                while b.content_words() < min_words {
+                    self.extend(b, config);
+                }
+            }
+            Template::Custom(pattern) => {
+                pattern.build(b, config);
+                while b.content_words() < min_words {
+                    push_extension(b, config);
+                }
+            }
+        }
+    }
+
+    /// Lengthen a finished password: a prepositional phrase, or for a haiku
+    /// or couplet another stanza, which keeps every line at 5-7-5 or rhyming.
+    fn extend(&self, b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+        if matches!(self, Template::Haiku | Template::Couplet) {
+            b.push(LINE_MARKER);
+            self.push_stanza(b, config);
+        } else {
+            push_extension(b, config);
+        }
+    }
+
+    /// Append one stanza of a haiku or couplet.
+    fn push_stanza(&self, b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+        if *self == Template::Couplet {
+            push_couplet(b, config);
+        } else {
+            push_haiku(b, config);
         }
+    }
 
-        let words = match self {
-            Template::Declarative => generate_declarative(rng, min_words, config),
-            Template::Imperative => generate_imperative(rng, min_words, config),
-            Template::Question => generate_question(rng, min_words, config),
-            Template::Warning => generate_warning(rng, min_words, config),
-            Template::Fragment => generate_fragment(rng, min_words, config),
-            Template::Versus => generate_versus(rng, min_words, config),
-            Template::Since => unreachable!(), // Handled above
-            Template::Somewhere => generate_somewhere(rng, min_words, config),
-            Template::ShouldNot => generate_should_not(rng, min_words, config),
-            Template::Requires => generate_requires(rng, min_words, config),
+    /// Run this template with choices taken from `source`, keeping the raw words
+    /// and where each choice was made.
+    ///
+    /// `phrases` prepositional phrases (or haiku and couplet stanzas) are appended
+    /// afterwards, as [`generate_with_min_entropy`](Self::generate_with_min_entropy) does.
+    pub(crate) fn replay(
+        &self,
+        source: impl ChoiceSource,
+        min_words: usize,
+        phrases: usize,
+        config: &WordConfig,
+    ) -> Replay {
+        let mut b = Builder::new(source, config.memorability);
+        self.build(&mut b, min_words, config);
+        for _ in 0..phrases {
+            self.extend(&mut b, config);
+        }
+        Replay { words: b.words, labels: b.labels, marks: b.marks, decisions: b.decisions }
+    }
+
+    fn finish(&self, b: Builder<impl ChoiceSource>, config: &WordConfig) -> GeneratedPassword {
+        let words = match config.language {
+            Language::English => fix_articles(&b.words),
+            language => lang::contract(language, b.words),
         };
-        fix_articles(&words)
+        GeneratedPassword { template: self.clone(), words, decisions: b.decisions }
     }
 }
 
-/// Helper to pick a random word from a slice.
-fn pick<'a>(rng: &mut impl Rng, words: &[&'a str]) -> &'a str {
-    words[rng.gen_range(0..words.len())]
-}
+/// Where a template's choices come from.
+///
+/// Generation normally draws from an RNG, but the same template code can be
+/// driven by any other source of choices, such as a recorded sequence being
+/// replayed (see [`crate::parser`]).
+pub(crate) trait ChoiceSource {
+    /// Choose one of `n` equally likely options.
+    fn index(&mut self, n: usize) -> usize;
+    /// Come up true with probability `p`.
+    fn coin(&mut self, p: f64) -> bool;
+
+    /// Choose one entry of `list` (uniformly, unless the source knows better).
+    fn choose_from(&mut self, list: &[impl AsRef<str>]) -> usize {
+        self.index(list.len())
+    }
 
-/// Helper to pick a random word from a Vec.
-fn pick_vec<'a>(rng: &mut impl Rng, words: &'a [&'static str]) -> &'static str {
-    words[rng.gen_range(0..words.len())]
+    /// Choose one entry of `list`, each with a chance proportional to its
+    /// weight in `draw`. Sources that don't draw at random choose as
+    /// [`choose_from`](Self::choose_from) does.
+    fn choose_weighted(&mut self, list: &[impl AsRef<str>], _draw: &Draw) -> usize {
+        self.choose_from(list)
+    }
 }
 
-/// Fix "a" to "an" when followed by a word starting with a vowel.
-fn fix_articles(words: &[&str]) -> Vec<String> {
-    let mut result = Vec::new();
+impl<S: ChoiceSource + ?Sized> ChoiceSource for &mut S {
+    fn index(&mut self, n: usize) -> usize {
+        (**self).index(n)
+    }
 
-    for (i, word) in words.iter().enumerate() {
-        if *word == "a" {
-            if let Some(next_word) = words.get(i + 1) {
-                if starts_with_vowel_sound(next_word) {
-                    result.push("an".to_string());
-                    continue;
-                }
-            }
-        }
-        result.push(word.to_string());
+    fn coin(&mut self, p: f64) -> bool {
+        (**self).coin(p)
     }
 
-    result
+    fn choose_from(&mut self, list: &[impl AsRef<str>]) -> usize {
+        (**self).choose_from(list)
+    }
+
+    fn choose_weighted(&mut self, list: &[impl AsRef<str>], draw: &Draw) -> usize {
+        (**self).choose_weighted(list, draw)
+    }
 }
 
-/// Fix "a" to "an" when followed by a word starting with a vowel (for owned strings).
-fn fix_articles_owned(words: &[String]) -> Vec<String> {
-    let mut result = Vec::new();
+/// Draws choices from an RNG.
+struct RngSource<'r, R: Rng>(&'r mut R);
 
-    for (i, word) in words.iter().enumerate() {
-        if word == "a" {
-            if let Some(next_word) = words.get(i + 1) {
-                if starts_with_vowel_sound(next_word) {
-                    result.push("an".to_string());
-                    continue;
-                }
-            }
-        }
-        result.push(word.clone());
+impl<R: Rng> ChoiceSource for RngSource<'_, R> {
+    fn index(&mut self, n: usize) -> usize {
+        self.0.gen_range(0..n)
     }
 
-    result
+    fn coin(&mut self, p: f64) -> bool {
+        self.0.gen_bool(p)
+    }
+
+    fn choose_weighted(&mut self, _list: &[impl AsRef<str>], draw: &Draw) -> usize {
+        self.0.sample(&draw.index)
+    }
 }
 
-fn starts_with_vowel_sound(word: &str) -> bool {
-    word.chars()
-        .next()
-        .map(|c| matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u'))
-        .unwrap_or(false)
+/// Words produced by driving a template with an arbitrary [`ChoiceSource`].
+pub(crate) struct Replay {
+    /// The raw words, before "a"/"an" fixing.
+    pub words: Vec<String>,
+    /// For each word, the label of the decision that produced it ("fixed" if none).
+    pub labels: Vec<&'static str>,
+    /// For each choice taken, how many words had been pushed before it.
+    pub marks: Vec<usize>,
+    /// Every decision taken, in order.
+    pub decisions: Vec<Decision>,
 }
 
-/// Pick a subject (animal, profession, or noun).
-fn pick_subject(rng: &mut impl Rng, config: &WordConfig) -> &'static str {
-    let choice = rng.gen_range(0..3);
-    match choice {
-        0 => pick(rng, ANIMALS),
-        1 => {
-            let professions = get_professions(config.nsfw);
-            pick_vec(rng, &professions)
+/// Accumulates the words of a password and records every random decision.
+///
+/// Templates never touch the choice source directly: each pick, coin flip and
+/// range draw goes through here so its probability ends up in the password's
+/// decision record.
+pub(crate) struct Builder<S: ChoiceSource> {
+    source: S,
+    words: Vec<String>,
+    labels: Vec<&'static str>,
+    decisions: Vec<Decision>,
+    marks: Vec<usize>,
+    /// How strongly word picks favour memorable words (see [`crate::memorability`]).
+    memorability: f64,
+}
+
+impl<S: ChoiceSource> Builder<S> {
+    pub(crate) fn new(source: S, memorability: f64) -> Self {
+        Self { source, words: Vec::new(), labels: Vec::new(), decisions: Vec::new(), marks: Vec::new(), memorability }
+    }
+
+    /// Record a decision taken at the current position.
+    fn record(&mut self, decision: Decision) {
+        self.marks.push(self.words.len());
+        self.decisions.push(decision);
+    }
+
+    /// Append a fixed word (no randomness involved).
+    pub(crate) fn push(&mut self, word: &str) {
+        self.push_labeled(word, "fixed");
+    }
+
+    /// Append a word produced by the decision labelled `label`.
+    pub(crate) fn push_labeled(&mut self, word: &str, label: &'static str) {
+        self.words.push(word.to_string());
+        self.labels.push(label);
+    }
+
+    /// Pick the index of a random entry of `list` without appending it.
+    ///
+    /// Picks are uniform unless memorable words are favoured, in which case the
+    /// pick is recorded with the min-entropy of the weighted list.
+    fn choose(&mut self, label: &'static str, list: &[impl AsRef<str>]) -> usize {
+        if self.memorability == 0.0 {
+            self.record(Decision::uniform(label, list.len()));
+            return self.source.choose_from(list);
+        }
+        let draw = Draw::of(list, self.memorability);
+        self.record(Decision::new(label, draw.largest));
+        self.source.choose_weighted(list, &draw)
+    }
+
+    /// Pick a random entry from `list` and append it as one word.
+    pub(crate) fn pick<'l>(&mut self, label: &'static str, list: &'l [impl AsRef<str>]) -> &'l str {
+        self.pick_index(label, list).1
+    }
+
+    /// Pick a random entry from `list`, append it as one word and return its
+    /// index along with it.
+    fn pick_index<'l>(&mut self, label: &'static str, list: &'l [impl AsRef<str>]) -> (usize, &'l str) {
+        let index = self.choose(label, list);
+        let entry = list[index].as_ref();
+        self.push_labeled(entry, label);
+        (index, entry)
+    }
+
+    /// Pick a random phrase from `list` and append it word by word.
+    pub(crate) fn pick_phrase<'l>(&mut self, label: &'static str, list: &'l [impl AsRef<str>]) -> &'l str {
+        let phrase = list[self.choose(label, list)].as_ref();
+        for w in phrase.split_whitespace() {
+            self.push_labeled(w, label);
         }
-        _ => {
-            let nouns = get_nouns(config.nsfw);
-            pick_vec(rng, &nouns)
+        phrase
+    }
+
+    /// Flip a biased coin that comes up true with probability `p`.
+    pub(crate) fn chance(&mut self, label: &'static str, p: f64) -> bool {
+        self.record(Decision::new(label, p));
+        let hit = self.source.coin(p);
+        if !hit {
+            self.decisions.last_mut().unwrap().probability = 1.0 - p;
         }
+        hit
+    }
+
+    /// Uniformly choose one of `n` branches.
+    pub(crate) fn branch(&mut self, label: &'static str, n: usize) -> usize {
+        self.record(Decision::uniform(label, n));
+        self.source.index(n)
+    }
+
+    /// Append a random year between 1950 and 2030.
+    pub(crate) fn year(&mut self) {
+        const YEARS: std::ops::RangeInclusive<usize> = 1950..=2030;
+        self.record(Decision::uniform("year", YEARS.count()));
+        let year = YEARS.start() + self.source.index(YEARS.count());
+        self.push_labeled(&year.to_string(), "year");
+    }
+
+    /// Entropy of the decisions recorded so far, in bits.
+    fn entropy(&self) -> f64 {
+        self.decisions.iter().map(Decision::bits).sum()
+    }
+
+    /// Number of content words (non-function words) appended so far.
+    fn content_words(&self) -> usize {
+        self.words.iter().filter(|w| !is_function_word(w)).count()
+    }
+}
+
+/// Pick a subject (animal, profession, or noun).
+fn pick_subject(b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+    pick_subject_from(b, &config.words());
+}
+
+/// Pick a subject from the lists of `words`.
+fn pick_subject_from<'w>(b: &mut Builder<impl ChoiceSource>, words: &'w WordSet) -> &'w str {
+    match b.branch("subject kind", 3) {
+        0 => b.pick("animal", &words.animals),
+        1 => b.pick("profession", &words.professions),
+        _ => b.pick("noun", &words.nouns),
     }
 }
 
 /// Pick an object (from OBJECTS or NOUNS).
-fn pick_object(rng: &mut impl Rng, config: &WordConfig) -> &'static str {
-    if rng.gen_bool(0.7) {
-        let objects = get_objects(config.nsfw);
-        pick_vec(rng, &objects)
+fn pick_object(b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+    let words = config.words();
+    if b.chance("object kind", 0.7) {
+        b.pick("object", &words.objects);
     } else {
-        let nouns = get_nouns(config.nsfw);
-        pick_vec(rng, &nouns)
+        b.pick("noun", &words.nouns);
     }
 }
 
-/// Generate a random year between 1950 and 2030.
-fn random_year(rng: &mut impl Rng) -> String {
-    rng.gen_range(1950..=2030).to_string()
+/// Append the phrase used to lengthen a password in the configured language.
+fn push_extension(b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+    match config.language {
+        Language::English => push_prepositional_phrase(b, config),
+        language => lang::extend(language, b),
+    }
+}
+
+/// Append a prepositional phrase: "beneath a soggy walrus".
+fn push_prepositional_phrase(b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+    let words = config.words();
+    b.pick("preposition", &words.prepositions);
+    b.pick("article", &words.articles);
+    // Always add adjective to maximize content words
+    b.pick("adjective", &words.adjectives);
+    pick_subject(b, config);
+}
+
+/// Append the preposition an intransitive verb needs before its object, if any.
+fn push_verb_preposition(b: &mut Builder<impl ChoiceSource>, verb: &str) {
+    if let Some(prep) = get_verb_preposition(verb) {
+        b.push(prep);
+    }
 }
 
 // =============================================================================
 // Template Generators
 // =============================================================================
 
-/// Count content words (non-function words)
-fn count_content_words(words: &[&str]) -> usize {
-    words.iter().filter(|w| !is_function_word(w)).count()
-}
-
 /// Check if a word is a function word (article, preposition, connector)
 fn is_function_word(word: &str) -> bool {
     ARTICLES.contains(&word)
@@ -241,408 +562,522 @@ fn is_function_word(word: &str) -> bool {
 
 /// Declarative: "the grumpy walrus juggles pickles"
 /// Structure: [article] [adjective?] [subject] [verb] [prep?] [object] [adverb?]
-fn generate_declarative(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adjectives = get_adjectives(config.nsfw);
-    let present_verbs = get_present_verbs(config.nsfw);
-    let adverbs = get_adverbs(config.nsfw);
+fn generate_declarative(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Article
-    words.push(pick(rng, ARTICLES));
+    b.pick("article", &words.articles);
 
     // Optional adjective (more likely if we need more words)
-    if min_words > 3 || rng.gen_bool(0.6) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 3 || b.chance("optional adjective", 0.6) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
-    // Verb (present tense)
-    let verb = pick_vec(rng, &present_verbs);
-    words.push(verb);
-
-    // Add preposition if verb is intransitive
-    if let Some(prep) = get_verb_preposition(verb) {
-        words.push(prep);
-    }
+    // Verb (present tense), with a preposition if it is intransitive
+    let verb = b.pick("present verb", &words.present_verbs);
+    push_verb_preposition(b, verb);
 
     // Object
-    words.push(pick_object(rng, config));
+    pick_object(b, config);
 
     // Optional adverb for extra length
-    if count_content_words(&words) < min_words && rng.gen_bool(0.5) {
-        words.push(pick_vec(rng, &adverbs));
+    if b.content_words() < min_words && b.chance("optional adverb", 0.5) {
+        b.pick("adverb", &words.adverbs);
     }
 
     // Extend with prepositional phrase if still too short
-    while count_content_words(&words) < min_words {
-        words.push(pick(rng, PREPOSITIONS));
-        words.push(pick(rng, ARTICLES));
-        // Always add adjective to maximize content words
-        words.push(pick_vec(rng, &adjectives));
-        words.push(pick_subject(rng, config));
+    while b.content_words() < min_words {
+        push_prepositional_phrase(b, config);
     }
-
-    words
 }
 
 /// Imperative: "never trust a caffeinated accountant"
 /// Structure: [imperative phrase] [article?] [adjective?] [subject]
-fn generate_imperative(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let imperatives = get_imperatives(config.nsfw);
-    let adjectives = get_adjectives(config.nsfw);
+fn generate_imperative(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Imperative starter (counts as multiple words for display but single for logic)
-    let imperative = pick_vec(rng, &imperatives);
-    let imperative_words: Vec<&str> = imperative.split_whitespace().collect();
-    let ends_with_article = imperative_words.last()
-        .map(|w| ARTICLES.contains(w))
+    let imperative = b.pick_phrase("imperative", &words.imperatives);
+    let ends_with_article = imperative.split_whitespace().last()
+        .map(|w| ARTICLES.contains(&w))
         .unwrap_or(false);
 
-    for w in imperative_words {
-        words.push(w);
-    }
-
     // Only add article if the imperative phrase doesn't already end with one
     if !ends_with_article {
-        words.push(pick(rng, ARTICLES));
+        b.pick("article", &words.articles);
     }
 
     // Adjective (more likely if we need more words)
-    if min_words > 4 || rng.gen_bool(0.7) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 4 || b.chance("optional adjective", 0.7) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
     // Extend with prepositional phrase if needed
-    while words.len() < min_words {
-        words.push(pick(rng, PREPOSITIONS));
-        words.push(pick_vec(rng, &adjectives));
-        words.push(pick_subject(rng, config));
+    while b.words.len() < min_words {
+        b.pick("preposition", &words.prepositions);
+        b.pick("adjective", &words.adjectives);
+        pick_subject(b, config);
     }
-
-    words
 }
 
 /// Question: "why does the walrus overthink"
 /// Structure: [question starter] [article] [subject] [base verb]
-fn generate_question(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adjectives = get_adjectives(config.nsfw);
-    let base_verbs = get_base_verbs(config.nsfw);
+fn generate_question(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Question starter (multiple words)
-    let starter = pick(rng, QUESTION_STARTERS);
-    for w in starter.split_whitespace() {
-        words.push(w);
-    }
+    b.pick_phrase("question starter", &words.question_starters);
 
     // Article
-    words.push(pick(rng, ARTICLES));
+    b.pick("article", &words.articles);
 
     // Optional adjective
-    if min_words > 5 || rng.gen_bool(0.5) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 5 || b.chance("optional adjective", 0.5) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
     // Base verb
-    let verb = pick_vec(rng, &base_verbs);
-    words.push(verb);
+    let verb = b.pick("base verb", &words.base_verbs);
 
     // Optional object for length (with preposition if verb is intransitive)
-    if words.len() < min_words {
-        if let Some(prep) = get_verb_preposition(verb) {
-            words.push(prep);
-        }
-        words.push(pick_object(rng, config));
+    if b.words.len() < min_words {
+        push_verb_preposition(b, verb);
+        pick_object(b, config);
     }
-
-    words
 }
 
 /// Warning: "do not provoke the sleepy wizard"
 /// This is similar to imperative but specifically uses warning-style phrases
 /// Structure: [warning phrase] [verb] [article] [adjective] [subject]
-fn generate_warning(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let base_verbs = get_base_verbs(config.nsfw);
-    let adjectives = get_adjectives(config.nsfw);
+fn generate_warning(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Use warning starters from pool
-    let warning = pick(rng, WARNINGS);
-    for w in warning.split_whitespace() {
-        words.push(w);
-    }
+    b.pick_phrase("warning", &words.warnings);
 
     // Add a base verb after the warning phrase
-    words.push(pick_vec(rng, &base_verbs));
+    b.pick("base verb", &words.base_verbs);
 
     // Article
-    words.push(pick(rng, ARTICLES));
+    b.pick("article", &words.articles);
 
     // Adjective (almost always for warnings)
-    if min_words > 5 || rng.gen_bool(0.8) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 5 || b.chance("optional adjective", 0.8) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
     // Extend if needed
-    while words.len() < min_words {
-        words.push(pick(rng, PREPOSITIONS));
-        words.push(pick_vec(rng, &adjectives));
-        words.push(pick_object(rng, config));
+    while b.words.len() < min_words {
+        b.pick("preposition", &words.prepositions);
+        b.pick("adjective", &words.adjectives);
+        pick_object(b, config);
     }
-
-    words
 }
 
-/// Fragment: "three wizards juggle entropy"
+/// Fragment: "three wizards juggle pickles"
 /// Structure: [number/adverb] [adjective?] [subject(s)] [present verb] [object]
-fn generate_fragment(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adverbs = get_adverbs(config.nsfw);
-    let adjectives = get_adjectives(config.nsfw);
-    let present_verbs = get_present_verbs(config.nsfw);
-
-    // Start with number or adverb
-    if rng.gen_bool(0.5) {
-        let number = pick(rng, NUMBERS);
-        for w in number.split_whitespace() {
-            words.push(w);
-        }
+fn generate_fragment(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
+
+    // Start with number or adverb; a number decides how many subjects there are
+    let count = if b.chance("number or adverb", 0.5) {
+        plural::count(b.pick_phrase("number", &words.numbers))
     } else {
-        words.push(pick_vec(rng, &adverbs));
-    }
+        b.pick("adverb", &words.adverbs);
+        Count::One
+    };
 
     // Optional adjective
-    if min_words > 4 || rng.gen_bool(0.5) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 4 || b.chance("optional adjective", 0.5) {
+        b.pick("adjective", &words.adjectives);
     }
 
-    // Subject (could be plural conceptually)
-    words.push(pick_subject(rng, config));
-
-    // Present verb (singular form for grammatical consistency)
-    let verb = pick_vec(rng, &present_verbs);
-    words.push(verb);
-
-    // Add preposition if verb is intransitive
-    if let Some(prep) = get_verb_preposition(verb) {
-        words.push(prep);
-    }
+    // Subject and present verb, plural when many are counted ("three wizards
+    // juggle") and after an amount of anything but a mass noun ("a suspicious
+    // amount of wizards juggle"), with a preposition if the verb is intransitive
+    let subjects = match count {
+        Count::One => Arc::clone(&words),
+        Count::Many => plural::counted(&words),
+        Count::Mass => plural::amounts(&words),
+    };
+    let subject = pick_subject_from(b, &subjects);
+    let mass = count == Count::Mass
+        && [&words.animals, &words.professions, &words.nouns].iter().any(|list| list.iter().any(|w| **w == *subject));
+    let verbs = if count == Count::One || mass { &words.present_verbs } else { &subjects.present_verbs };
+    let verb = b.pick("present verb", verbs);
+    push_verb_preposition(b, verb);
 
     // Object
-    words.push(pick_object(rng, config));
-
-    words
+    pick_object(b, config);
 }
 
 /// Versus: "penguin versus bureaucracy"
 /// Structure: [subject] [conflict word] [subject/noun]
-fn generate_versus(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adjectives = get_adjectives(config.nsfw);
-    let nouns = get_nouns(config.nsfw);
+fn generate_versus(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Optional adjective for first subject
-    if min_words > 3 || rng.gen_bool(0.4) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 3 || b.chance("optional adjective", 0.4) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // First subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
     // Conflict word (randomized from pool)
-    words.push(pick(rng, CONFLICTS));
+    b.pick("conflict", &words.conflicts);
 
     // Optional adjective for second subject
-    if min_words > 4 || rng.gen_bool(0.4) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 4 || b.chance("optional adjective", 0.4) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Second subject (often abstract noun for humor)
-    if rng.gen_bool(0.6) {
-        words.push(pick_vec(rng, &nouns));
+    if b.chance("abstract opponent", 0.6) {
+        b.pick("noun", &words.nouns);
     } else {
-        words.push(pick_subject(rng, config));
+        pick_subject(b, config);
     }
-
-    words
 }
 
 /// Since: "desperately hoarding pickles since 1987"
 /// Structure: [adverb?] [gerund] [prep?] [object] [time marker or "since" + dynamic year]
-fn generate_since(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<String> {
-    let mut words: Vec<String> = Vec::new();
-    let adverbs = get_adverbs(config.nsfw);
-    let gerunds = get_gerunds(config.nsfw);
-    let objects = get_objects(config.nsfw);
-    let nouns = get_nouns(config.nsfw);
+fn generate_since(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Optional adverb
-    if min_words > 4 || rng.gen_bool(0.6) {
-        words.push(pick_vec(rng, &adverbs).to_string());
+    if min_words > 4 || b.chance("optional adverb", 0.6) {
+        b.pick("adverb", &words.adverbs);
     }
 
-    // Gerund
-    let gerund = pick_vec(rng, &gerunds);
-    words.push(gerund.to_string());
-
-    // Add preposition if verb is intransitive
-    if let Some(prep) = get_verb_preposition(gerund) {
-        words.push(prep.to_string());
-    }
+    // Gerund, with a preposition if it is intransitive
+    let gerund = b.pick("gerund", &words.gerunds);
+    push_verb_preposition(b, gerund);
 
-    // Object (inline pick_object logic for owned strings)
-    if rng.gen_bool(0.7) {
-        words.push(pick_vec(rng, &objects).to_string());
-    } else {
-        words.push(pick_vec(rng, &nouns).to_string());
-    }
+    // Object
+    pick_object(b, config);
 
     // 50% chance to use dynamic year, 50% chance to use TIME_MARKERS pool
-    if rng.gen_bool(0.5) {
-        // Use dynamic year generation
-        words.push("since".to_string());
-        words.push(random_year(rng));
+    if b.chance("year or time marker", 0.5) {
+        b.push("since");
+        b.year();
     } else {
-        // Use time marker from pool
-        let time_marker = pick(rng, TIME_MARKERS);
-        for w in time_marker.split_whitespace() {
-            words.push(w.to_string());
-        }
+        b.pick_phrase("time marker", &words.time_markers);
     }
-
-    // Apply article fixing (a -> an before vowels)
-    fix_articles_owned(&words)
 }
 
 /// Somewhere: "Somewhere, a penguin is plotting"
 /// Structure: [location], [article] [adjective?] [subject] [connector] [gerund]
-fn generate_somewhere(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adjectives = get_adjectives(config.nsfw);
-    let gerunds = get_gerunds(config.nsfw);
+fn generate_somewhere(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Location (randomized from pool)
-    let location = pick(rng, LOCATIONS);
-    for w in location.split_whitespace() {
-        words.push(w);
-    }
+    b.pick_phrase("location", &words.locations);
 
     // Comma after introductory location phrase
-    words.push(COMMA_MARKER);
+    b.push(COMMA_MARKER);
 
     // Article
-    words.push(pick(rng, ARTICLES));
+    b.pick("article", &words.articles);
 
     // Optional adjective
-    if min_words > 5 || rng.gen_bool(0.5) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 5 || b.chance("optional adjective", 0.5) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
     // Connector (randomized from pool)
-    words.push(pick(rng, CONNECTORS_ACTIVE));
+    b.pick("connector", &words.connectors_active);
 
     // Gerund
-    let gerund = pick_vec(rng, &gerunds);
-    words.push(gerund);
+    let gerund = b.pick("gerund", &words.gerunds);
 
     // Optional object for length (with preposition if verb is intransitive)
-    if words.len() < min_words {
-        if let Some(prep) = get_verb_preposition(gerund) {
-            words.push(prep);
-        }
-        words.push(pick_object(rng, config));
+    if b.words.len() < min_words {
+        push_verb_preposition(b, gerund);
+        pick_object(b, config);
     }
-
-    words
 }
 
 /// ShouldNot: "llamas should not negotiate"
 /// Structure: [subject] [prohibition] [base verb]
-fn generate_should_not(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adjectives = get_adjectives(config.nsfw);
-    let prohibitions = get_prohibitions(config.nsfw);
-    let base_verbs = get_base_verbs(config.nsfw);
+fn generate_should_not(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Optional adjective
-    if min_words > 4 || rng.gen_bool(0.4) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 4 || b.chance("optional adjective", 0.4) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject
-    words.push(pick_subject(rng, config));
+    pick_subject(b, config);
 
     // Prohibition (randomized from pool)
-    let prohibition = pick_vec(rng, &prohibitions);
-    for w in prohibition.split_whitespace() {
-        words.push(w);
-    }
+    b.pick_phrase("prohibition", &words.prohibitions);
 
     // Base verb
-    let verb = pick_vec(rng, &base_verbs);
-    words.push(verb);
+    let verb = b.pick("base verb", &words.base_verbs);
 
     // Optional object (with preposition if verb is intransitive)
-    if words.len() < min_words || rng.gen_bool(0.5) {
-        if let Some(prep) = get_verb_preposition(verb) {
-            words.push(prep);
-        }
-        words.push(pick_object(rng, config));
+    if b.words.len() < min_words || b.chance("optional object", 0.5) {
+        push_verb_preposition(b, verb);
+        pick_object(b, config);
     }
-
-    words
 }
 
 /// Requires: "chaos requires more coffee"
 /// Structure: [subject/noun] [demand phrase] [object]
-fn generate_requires(rng: &mut impl Rng, min_words: usize, config: &WordConfig) -> Vec<&'static str> {
-    let mut words: Vec<&str> = Vec::new();
-    let adjectives = get_adjectives(config.nsfw);
-    let nouns = get_nouns(config.nsfw);
-    let demands = get_demands(config.nsfw);
+fn generate_requires(b: &mut Builder<impl ChoiceSource>, min_words: usize, config: &WordConfig) {
+    let words = config.words();
 
     // Optional adjective
-    if min_words > 4 || rng.gen_bool(0.3) {
-        words.push(pick_vec(rng, &adjectives));
+    if min_words > 4 || b.chance("optional adjective", 0.3) {
+        b.pick("adjective", &words.adjectives);
     }
 
     // Subject (often abstract noun)
-    if rng.gen_bool(0.6) {
-        words.push(pick_vec(rng, &nouns));
+    if b.chance("abstract subject", 0.6) {
+        b.pick("noun", &words.nouns);
     } else {
-        words.push(pick_subject(rng, config));
+        pick_subject(b, config);
     }
 
     // Demand phrase (randomized from pool)
-    let demand = pick_vec(rng, &demands);
-    for w in demand.split_whitespace() {
-        words.push(w);
-    }
+    b.pick_phrase("demand", &words.demands);
 
     // Object
-    words.push(pick_object(rng, config));
+    pick_object(b, config);
+}
+
+/// Haiku: "the caffeinated / narwhal smuggles rubber ducks / near a sleepy owl"
+/// Structure: [article] [adjective] [subject] [present verb] [prep?] [object]
+/// [preposition] [article] [adjective] [subject], broken into lines of 5, 7
+/// and 5 syllables
+///
+/// Every pick is made among the entries that still let the stanza be
+/// finished on the syllable grid, and recorded as a choice among exactly
+/// those, so the entropy of the constrained choice is exact.
+fn push_haiku(b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+    let haiku = haiku(&config.words());
+    let mut spoken = 0;
+    for (slot, choices) in haiku.slots.iter().zip(&haiku.choices) {
+        let choices = &choices[spoken];
+        assert!(!choices.is_empty(), "the word lists can fill a haiku");
+        let choice = if slot.kinds.len() > 1 { &choices[b.branch(slot.label, choices.len())] } else { &choices[0] };
+
+        let kind = &slot.kinds[choice.kind];
+        let (index, entry) = b.pick_index(kind.label, &choice.entries);
+        if kind.verb {
+            push_verb_preposition(b, entry);
+        }
+        spoken += choice.costs[index];
+        if HAIKU_BREAKS[..HAIKU_BREAKS.len() - 1].contains(&spoken) {
+            b.push(LINE_MARKER);
+        }
+    }
+}
+
+/// Syllables spoken by the end of each line of a haiku.
+const HAIKU_BREAKS: [usize; 3] = [5, 12, 17];
+
+/// One word of a haiku and the lists it may come from.
+struct HaikuSlot {
+    /// The label of the choice between kinds, when there is more than one.
+    label: &'static str,
+    kinds: Vec<HaikuKind>,
+    /// Whether a line may end after this word. Articles stay with their noun.
+    ends_line: bool,
+}
+
+/// One list a haiku word may come from, with the syllables of each entry.
+struct HaikuKind {
+    label: &'static str,
+    list: Vec<Arc<str>>,
+    /// Syllables of each entry, counting the preposition a verb brings along.
+    costs: Vec<usize>,
+    verb: bool,
+}
+
+fn haiku_slots(words: &WordSet) -> Vec<HaikuSlot> {
+    let kind =
+        |label, list: &[Arc<str>]| HaikuKind { label, list: list.to_vec(), costs: syllable_counts(list), verb: false };
+    let slot = |label, kinds, ends_line| HaikuSlot { label, kinds, ends_line };
+    let subject = || {
+        let kinds = vec![kind("animal", &words.animals), kind("profession", &words.professions), kind("noun", &words.nouns)];
+        slot("subject kind", kinds, true)
+    };
+    let mut verb = kind("present verb", &words.present_verbs);
+    for (cost, entry) in verb.costs.iter_mut().zip(&verb.list) {
+        *cost += get_verb_preposition(entry).map_or(0, phrase_syllables);
+    }
+    verb.verb = true;
+    vec![
+        slot("article", vec![kind("article", &words.articles)], false),
+        slot("adjective", vec![kind("adjective", &words.adjectives)], true),
+        subject(),
+        slot("present verb", vec![verb], true),
+        slot("object kind", vec![kind("object", &words.objects), kind("noun", &words.nouns)], true),
+        slot("preposition", vec![kind("preposition", &words.prepositions)], true),
+        slot("article", vec![kind("article", &words.articles)], false),
+        slot("adjective", vec![kind("adjective", &words.adjectives)], true),
+        subject(),
+    ]
+}
 
-    words
+/// Whether a word of `cost` syllables fits after `spoken` syllables, leaving
+/// a stanza that can be finished from the syllable counts in `next` (see
+/// [`haiku_finishable`]).
+fn fits_haiku(slot: &HaikuSlot, spoken: usize, cost: usize, next: u32) -> bool {
+    let end = spoken + cost;
+    end <= HAIKU_BREAKS[2]
+        && cost > 0
+        && !HAIKU_BREAKS.iter().any(|&line| spoken < line && line < end)
+        && (slot.ends_line || !HAIKU_BREAKS.contains(&end))
+        && next >> end & 1 == 1
+}
+
+/// For each slot, the syllable counts spoken before it (as bits of a mask)
+/// from which the rest of the stanza can still land on every line break.
+fn haiku_finishable(slots: &[HaikuSlot]) -> Vec<u32> {
+    let mut finishable = vec![0; slots.len() + 1];
+    finishable[slots.len()] = 1 << HAIKU_BREAKS[2];
+    for (i, slot) in slots.iter().enumerate().rev() {
+        let mut costs = 0u32;
+        for kind in &slot.kinds {
+            for &cost in kind.costs.iter().filter(|&&cost| cost <= HAIKU_BREAKS[2]) {
+                costs |= 1 << cost;
+            }
+        }
+        for spoken in 0..=HAIKU_BREAKS[2] {
+            let fits = (0..=HAIKU_BREAKS[2]).any(|cost| costs >> cost & 1 == 1 && fits_haiku(slot, spoken, cost, finishable[i + 1]));
+            if fits {
+                finishable[i] |= 1 << spoken;
+            }
+        }
+    }
+    finishable
+}
+
+/// The slots of a haiku over one word set, and what can fill each.
+struct Haiku {
+    slots: Vec<HaikuSlot>,
+    /// For each slot and number of syllables spoken before it, the kinds
+    /// with entries that fit.
+    choices: Vec<Vec<Vec<HaikuChoice>>>,
+}
+
+/// The entries of one kind that fit a haiku slot, with their syllables.
+struct HaikuChoice {
+    /// Index into the slot's kinds.
+    kind: usize,
+    entries: Vec<Arc<str>>,
+    costs: Vec<usize>,
+}
+
+static HAIKUS: Cache<Source<WordSet>, Haiku> = Cache::new();
+
+/// The haiku slots for `words`, with syllables counted once per set and
+/// shared while it's used.
+fn haiku(words: &Arc<WordSet>) -> Arc<Haiku> {
+    HAIKUS.get(Source::of(words), || build_haiku(words))
+}
+
+fn build_haiku(words: &WordSet) -> Haiku {
+    let slots = haiku_slots(words);
+    let finishable = haiku_finishable(&slots);
+    let choices = slots
+        .iter()
+        .enumerate()
+        .map(|(i, slot)| {
+            (0..=HAIKU_BREAKS[2])
+                .map(|spoken| {
+                    let fits = |cost: usize| fits_haiku(slot, spoken, cost, finishable[i + 1]);
+                    let choices = slot.kinds.iter().enumerate().map(|(kind, HaikuKind { list, costs, .. })| {
+                        let fitting = list.iter().zip(costs).filter(|&(_, &cost)| fits(cost));
+                        let (entries, costs) = fitting.map(|(entry, &cost)| (Arc::clone(entry), cost)).unzip();
+                        HaikuChoice { kind, entries, costs }
+                    });
+                    choices.filter(|choice| !choice.entries.is_empty()).collect()
+                })
+                .collect()
+        })
+        .collect();
+    Haiku { slots, choices }
+}
+
+/// Whether the lists of `words` can fill a haiku.
+pub(crate) fn haiku_fits(words: &Arc<WordSet>) -> bool {
+    !haiku(words).choices[0][0].is_empty()
+}
+
+/// Couplet: "the grumpy walrus juggles pickles / the sleepy wizard owes nickels"
+/// Structure: two lines of [article] [adjective] [subject] [present verb]
+/// [prep?] [ending], where the endings rhyme
+///
+/// The first ending is picked among the objects, nouns and animals that
+/// rhyme with something, the second among the entries that rhyme with the
+/// first. Each is recorded as a choice among exactly those entries, so the
+/// entropy lost to the rhyme is counted.
+fn push_couplet(b: &mut Builder<impl ChoiceSource>, config: &WordConfig) {
+    let index = rhyme::index(&config.words());
+    let rhyming: Vec<&[Arc<str>]> = (0..RhymeIndex::KINDS.len()).map(|kind| index.rhyming(kind)).collect();
+    let first = push_couplet_line(b, config, "ending kind", &rhyming);
+    b.push(LINE_MARKER);
+    let partners = index.partners(first);
+    push_couplet_line(b, config, "rhyme kind", &partners.each_ref().map(Vec::as_slice));
+}
+
+/// Append one line of a couplet, ending with an entry of `endings` (one list
+/// for each of [`RhymeIndex::KINDS`]), and return that entry.
+fn push_couplet_line<'e>(
+    b: &mut Builder<impl ChoiceSource>,
+    config: &WordConfig,
+    kind_label: &'static str,
+    endings: &[&'e [Arc<str>]],
+) -> &'e str {
+    let words = config.words();
+    b.pick("article", &words.articles);
+    b.pick("adjective", &words.adjectives);
+    pick_subject(b, config);
+    let verb = b.pick("present verb", &words.present_verbs);
+    push_verb_preposition(b, verb);
+
+    let kinds: Vec<usize> = (0..endings.len()).filter(|&kind| !endings[kind].is_empty()).collect();
+    assert!(!kinds.is_empty(), "the word lists have rhymes");
+    let kind = if kinds.len() > 1 { kinds[b.branch(kind_label, kinds.len())] } else { kinds[0] };
+    match RhymeIndex::KINDS[kind] {
+        ListKind::Animal => {
+            b.pick("article", &words.articles);
+            b.pick("animal", endings[kind])
+        }
+        ListKind::Noun => b.pick("noun", endings[kind]),
+        _ => b.pick("object", endings[kind]),
+    }
+}
+
+/// Whether the lists of `words` have entries that rhyme.
+pub(crate) fn couplet_fits(words: &Arc<WordSet>) -> bool {
+    (0..RhymeIndex::KINDS.len()).any(|kind| !rhyme::index(words).rhyming(kind).is_empty())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::rating::ContentRating;
     use rand::SeedableRng;
     use rand::rngs::StdRng;
 
@@ -651,7 +1086,7 @@ mod tests {
     }
 
     fn get_default_config() -> WordConfig {
-        WordConfig { nsfw: false }
+        WordConfig::default()
     }
 
     #[test]
@@ -674,7 +1109,7 @@ mod tests {
     fn test_declarative_generates_words() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Declarative.generate(&mut rng, 4, &config);
+        let words = Template::Declarative.generate(&mut rng, 4, &config).unwrap().words;
         assert!(words.len() >= 4, "Expected at least 4 words, got {}", words.len());
     }
 
@@ -682,7 +1117,7 @@ mod tests {
     fn test_imperative_generates_words() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Imperative.generate(&mut rng, 4, &config);
+        let words = Template::Imperative.generate(&mut rng, 4, &config).unwrap().words;
         assert!(!words.is_empty());
     }
 
@@ -690,7 +1125,7 @@ mod tests {
     fn test_question_generates_words() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Question.generate(&mut rng, 4, &config);
+        let words = Template::Question.generate(&mut rng, 4, &config).unwrap().words;
         assert!(!words.is_empty());
         // Should start with a question word
         let first_words: Vec<_> = QUESTION_STARTERS.iter()
@@ -703,7 +1138,7 @@ mod tests {
     fn test_since_contains_time_marker() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Since.generate(&mut rng, 4, &config);
+        let words = Template::Since.generate(&mut rng, 4, &config).unwrap().words;
         // Should contain words from TIME_MARKERS
         let has_time_marker = words.iter().any(|w| {
             TIME_MARKERS.iter().any(|marker| marker.split_whitespace().any(|mw| mw == w))
@@ -715,7 +1150,7 @@ mod tests {
     fn test_somewhere_starts_with_location() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Somewhere.generate(&mut rng, 4, &config);
+        let words = Template::Somewhere.generate(&mut rng, 4, &config).unwrap().words;
         // Should start with a word from LOCATIONS
         let starts_with_location = LOCATIONS.iter().any(|loc| {
             loc.split_whitespace().next().map(|w| w == words[0]).unwrap_or(false)
@@ -727,7 +1162,7 @@ mod tests {
     fn test_should_not_contains_prohibition() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::ShouldNot.generate(&mut rng, 4, &config);
+        let words = Template::ShouldNot.generate(&mut rng, 4, &config).unwrap().words;
         // Should contain words from PROHIBITIONS
         let has_prohibition = words.iter().any(|w| {
             PROHIBITIONS.iter().any(|p| p.split_whitespace().any(|pw| pw == w))
@@ -739,7 +1174,7 @@ mod tests {
     fn test_requires_contains_demand() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Requires.generate(&mut rng, 4, &config);
+        let words = Template::Requires.generate(&mut rng, 4, &config).unwrap().words;
         // Should contain words from DEMANDS
         let has_demand = words.iter().any(|w| {
             DEMANDS.iter().any(|d| d.split_whitespace().any(|dw| dw == w))
@@ -751,11 +1186,73 @@ mod tests {
     fn test_versus_contains_conflict() {
         let mut rng = get_test_rng();
         let config = get_default_config();
-        let words = Template::Versus.generate(&mut rng, 3, &config);
+        let words = Template::Versus.generate(&mut rng, 3, &config).unwrap().words;
         let has_conflict = words.iter().any(|w| CONFLICTS.contains(&w.as_str()));
         assert!(has_conflict, "Versus template should contain a conflict word, got: {:?}", words);
     }
 
+    #[test]
+    fn test_every_template_records_decisions() {
+        let mut rng = get_test_rng();
+        let config = get_default_config();
+        for template in Template::ALL {
+            let password = template.generate(&mut rng, 4, &config).unwrap();
+            assert_eq!(password.template, *template);
+            assert!(!password.decisions.is_empty());
+            for decision in &password.decisions {
+                assert!(
+                    decision.probability > 0.0 && decision.probability <= 1.0,
+                    "{:?} has invalid probability in {:?}",
+                    decision,
+                    password.words
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_forced_choices_add_no_entropy() {
+        // With min_words > 4 both adjectives are forced, so no coin flip is
+        // recorded for them and only the word picks contribute.
+        let mut rng = get_test_rng();
+        let config = get_default_config();
+        let password = Template::Versus.generate(&mut rng, 5, &config).unwrap();
+
+        assert!(!password.decisions.iter().any(|d| d.label == "optional adjective"));
+        assert_eq!(password.decisions.iter().filter(|d| d.label == "adjective").count(), 2);
+
+        let conflict = password.decisions.iter().find(|d| d.label == "conflict").unwrap();
+        assert!((conflict.bits() - (CONFLICTS.len() as f64).log2()).abs() < 1e-9);
+
+        let adjectives = (get_adjectives(ContentRating::Workplace).len() as f64).log2();
+        let minimum = 2.0 * adjectives + conflict.bits() + 3.0_f64.log2();
+        assert!(password.entropy() > minimum, "{} <= {}", password.entropy(), minimum);
+    }
+
+    #[test]
+    fn test_since_year_is_recorded() {
+        let config = get_default_config();
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let password = Template::Since.generate(&mut rng, 4, &config).unwrap();
+            let has_year = password.words.iter().any(|w| w.parse::<u32>().is_ok());
+            let year_decisions = password.decisions.iter().filter(|d| d.label == "year").count();
+            assert_eq!(has_year, year_decisions == 1, "{:?}", password.words);
+        }
+    }
+
+    #[test]
+    fn test_min_entropy_extends_every_template() {
+        let mut rng = get_test_rng();
+        let config = get_default_config();
+        for template in Template::ALL {
+            let short = template.generate_with_min_entropy(&mut rng, 0.0, &config).unwrap();
+            let long = template.generate_with_min_entropy(&mut rng, 90.0, &config).unwrap();
+            assert!(long.entropy() >= 90.0, "{:?}: {}", template, long.entropy());
+            assert!(long.words.len() > short.words.len(), "{:?}", template);
+        }
+    }
+
     #[test]
     fn test_article_fixing() {
         let words = vec!["a", "elegant", "octopus"];
@@ -0,0 +1,335 @@
+//! Curated vocabulary packs that give passwords a theme.
+//!
+//! A [`Theme`] supplies its own animals, professions, objects, locations and
+//! bearer titles. Templates keep their grammar and simply draw from the themed
+//! lists, so every entropy figure follows the themed list sizes: a narrow
+//! theme in [`ThemeMode::Swap`] visibly costs bits, while
+//! [`ThemeMode::Augment`] only adds words.
+//!
+//! Themed sets are built on top of a base [`WordSet`];
+//! [`crate::generator::WordConfig::words`] keeps the ones recently used.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::rating::{rated, ContentRating};
+use crate::wordset::{ListKind, WordSet, SLOTS};
+
+/// A vocabulary pack.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Theme {
+    Pirates,
+    SciFi,
+    Office,
+    Fantasy,
+    Kitchen,
+}
+
+/// How themed words combine with the base lists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ThemeMode {
+    /// Themed lists replace the base lists they cover.
+    #[default]
+    Swap,
+    /// Themed words are added to the base lists.
+    Augment,
+}
+
+/// The lists a theme covers.
+const THEMED: &[ListKind] = &[
+    ListKind::Animal,
+    ListKind::Profession,
+    ListKind::Object,
+    ListKind::Location,
+    ListKind::BearerTitle,
+];
+
+impl Theme {
+    /// Every theme.
+    pub const ALL: &'static [Theme] = &[Theme::Pirates, Theme::SciFi, Theme::Office, Theme::Fantasy, Theme::Kitchen];
+
+    /// The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Pirates => "pirates",
+            Theme::SciFi => "scifi",
+            Theme::Office => "office",
+            Theme::Fantasy => "fantasy",
+            Theme::Kitchen => "kitchen",
+        }
+    }
+
+    /// The theme's entries for one list (empty if it doesn't cover it).
+    pub fn words(self, kind: ListKind) -> &'static [&'static str] {
+        match (self, kind) {
+            (Theme::Pirates, ListKind::Animal) => &[
+                "parrot", "seagull", "sea turtle", "barracuda", "hammerhead", "moray eel", "ship rat",
+                "albatross", "octopus", "manta ray", "hermit crab", "pufferfish", "swordfish", "cormorant",
+                "sea serpent", "monkey", "lobster", "jellyfish",
+            ],
+            (Theme::Pirates, ListKind::Profession) => &[
+                "buccaneer", "corsair", "privateer", "quartermaster", "boatswain", "deckhand", "powder monkey",
+                "cabin boy", "first mate", "helmsman", "lookout", "ship's cook", "cartographer", "gunner",
+                "harbormaster", "rum runner", "treasure hunter", "castaway", "stowaway", "shipwright",
+            ],
+            (Theme::Pirates, ListKind::Object) => &[
+                "doubloons", "cutlasses", "treasure maps", "spyglasses", "eyepatches", "peg legs", "rum barrels",
+                "cannonballs", "anchors", "hooks", "tricorn hats", "sea chests", "compasses", "jolly rogers",
+                "gold coins", "planks", "ship's biscuits", "message bottles", "grappling hooks", "sails",
+                "hammocks", "lanterns", "muskets", "pieces of eight",
+            ],
+            (Theme::Pirates, ListKind::Location) => &[
+                "on the high seas", "below deck", "in the crow's nest", "on treasure island",
+                "in davy jones' locker", "at the tavern", "off the coast", "in the brig", "on the poop deck",
+                "in a hidden cove",
+            ],
+            (Theme::Pirates, ListKind::BearerTitle) => &[
+                "captain", "admiral", "commodore", "scourge", "terror", "plunderer", "raider", "marauder",
+                "sea lord", "rover",
+            ],
+
+            (Theme::SciFi, ListKind::Animal) => &[
+                "space slug", "robo-dog", "cyber-cat", "star whale", "void eel", "moon moth", "plasma jelly",
+                "quantum ferret", "nebula squid", "laser shark", "mutant tardigrade", "astro-hamster",
+                "crystal beetle", "gravity goat", "warp weasel", "photon pigeon",
+            ],
+            (Theme::SciFi, ListKind::Profession) => &[
+                "starship captain", "space pirate", "asteroid miner", "terraformer", "xenobiologist",
+                "android", "cyborg", "warp engineer", "alien diplomat", "clone wrangler", "hologram",
+                "navigator", "astrophysicist", "mech pilot", "space marine", "moon farmer", "time cop",
+                "robot butler", "orbital janitor", "galactic senator",
+            ],
+            (Theme::SciFi, ListKind::Object) => &[
+                "laser pistols", "warp cores", "tractor beams", "hyperdrives", "ray guns", "space suits",
+                "star charts", "escape pods", "plasma rifles", "force fields", "hover boards", "data crystals",
+                "jetpacks", "teleporters", "nanobots", "fusion cells", "cryo pods", "antimatter",
+                "robot arms", "photon torpedoes", "space helmets", "airlocks", "tricorders", "moon rocks",
+            ],
+            (Theme::SciFi, ListKind::Location) => &[
+                "in orbit", "on the mothership", "in hyperspace", "on a distant moon", "in the airlock",
+                "at the space station", "beyond the asteroid belt", "in zero gravity", "on mars",
+                "in cryosleep",
+            ],
+            (Theme::SciFi, ListKind::BearerTitle) => &[
+                "commander", "overlord", "supreme leader", "archon", "grand admiral", "emperor", "prime",
+                "custodian", "vanguard", "sentinel",
+            ],
+
+            (Theme::Office, ListKind::Animal) => &[
+                "office cat", "break room pigeon", "desk hamster", "lobby goldfish", "parking lot goose",
+                "server room mouse", "cubicle ferret", "printer gremlin", "water cooler frog",
+                "conference room moth", "vending machine raccoon", "elevator spider",
+            ],
+            (Theme::Office, ListKind::Profession) => &[
+                "middle manager", "intern", "receptionist", "project manager", "scrum master",
+                "product owner", "team lead", "hr representative", "it guy", "office manager", "vp of sales",
+                "account executive", "data entry clerk", "temp", "payroll clerk", "compliance officer",
+                "facilities manager", "executive assistant", "regional manager", "chief of staff",
+            ],
+            (Theme::Office, ListKind::Object) => &[
+                "staplers", "paperclips", "spreadsheets", "tps reports", "sticky notes", "lanyards",
+                "coffee mugs", "ergonomic chairs", "standing desks", "slide decks", "meeting invites",
+                "expense reports", "name badges", "printer jams", "whiteboard markers", "status updates",
+                "action items", "org charts", "performance reviews", "timesheets", "email threads",
+                "team building exercises", "desk plants", "fax machines",
+            ],
+            (Theme::Office, ListKind::Location) => &[
+                "in the break room", "in the all-hands", "on the third floor", "by the water cooler",
+                "in a meeting that could have been an email", "in the supply closet", "on a conference call",
+                "after the reorg", "before the deadline", "in the parking garage",
+            ],
+            (Theme::Office, ListKind::BearerTitle) => &[
+                "manager", "director", "head", "chief", "owner", "champion", "facilitator", "coordinator",
+                "liaison", "stakeholder",
+            ],
+
+            (Theme::Fantasy, ListKind::Animal) => &[
+                "dragon", "griffin", "unicorn", "phoenix", "wyvern", "basilisk", "pegasus", "hippogriff",
+                "owlbear", "dire wolf", "giant spider", "warg", "drake", "cockatrice", "kraken",
+                "hellhound", "salamander", "displacer beast",
+            ],
+            (Theme::Fantasy, ListKind::Profession) => &[
+                "wizard", "knight", "paladin", "ranger", "druid", "bard", "rogue", "necromancer",
+                "sorceress", "warlock", "cleric", "barbarian", "alchemist", "blacksmith", "innkeeper",
+                "squire", "court jester", "dragon slayer", "hedge witch", "dungeon master",
+            ],
+            (Theme::Fantasy, ListKind::Object) => &[
+                "enchanted swords", "magic scrolls", "healing potions", "spell books", "crystal balls",
+                "wands", "amulets", "dragon eggs", "cursed rings", "runestones", "battle axes", "war hammers",
+                "longbows", "shields", "gauntlets", "goblets", "treasure chests", "magic beans",
+                "mana crystals", "elven cloaks", "dwarven ale", "phoenix feathers", "troll teeth",
+                "ancient relics",
+            ],
+            (Theme::Fantasy, ListKind::Location) => &[
+                "in the enchanted forest", "deep in the dungeon", "atop the wizard's tower",
+                "in the dragon's lair", "beyond the misty mountains", "at the tavern", "in the castle",
+                "under the troll bridge", "in the elven kingdom", "in the cursed swamp",
+            ],
+            (Theme::Fantasy, ListKind::BearerTitle) => &[
+                "guardian", "keeper", "bearer", "warden", "lord", "lady", "high priest", "archmage",
+                "champion", "protector",
+            ],
+
+            (Theme::Kitchen, ListKind::Animal) => &[
+                "lobster", "crab", "chicken", "turkey", "salmon", "tuna", "anchovy", "sardine", "shrimp",
+                "cow", "pig", "lamb", "duck", "quail", "clam", "oyster", "mussel", "squid",
+            ],
+            (Theme::Kitchen, ListKind::Profession) => &[
+                "chef", "sous chef", "line cook", "pastry chef", "saucier", "dishwasher", "baker",
+                "butcher", "sommelier", "waiter", "busboy", "maitre d", "food critic", "barista",
+                "fry cook", "short order cook", "caterer", "grill master", "prep cook", "head chef",
+            ],
+            (Theme::Kitchen, ListKind::Object) => &[
+                "spatulas", "whisks", "ladles", "colanders", "rolling pins", "saucepans", "frying pans",
+                "cutting boards", "oven mitts", "measuring cups", "mixing bowls", "cheese graters",
+                "pepper mills", "salad spinners", "egg timers", "toasters", "blenders", "muffin tins",
+                "potato mashers", "garlic presses", "soup pots", "sourdough starters", "baking sheets",
+                "cookie cutters",
+            ],
+            (Theme::Kitchen, ListKind::Location) => &[
+                "in the walk-in fridge", "behind the stove", "in the pantry", "at the dinner rush",
+                "on the back burner", "under the sink", "in the oven", "at the farmers market",
+                "in the spice rack", "on the chopping block",
+            ],
+            (Theme::Kitchen, ListKind::BearerTitle) => &[
+                "master", "keeper", "chef", "guardian", "duke", "baron", "champion", "connoisseur",
+                "devourer", "taster",
+            ],
+
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for Theme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.to_lowercase().replace('-', "");
+        Theme::ALL.iter().copied().find(|theme| theme.name() == name).ok_or_else(|| {
+            let names: Vec<_> = Theme::ALL.iter().map(|theme| theme.name()).collect();
+            format!("Unknown theme: '{}' (available: {})", s, names.join(", "))
+        })
+    }
+}
+
+impl FromStr for ThemeMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "swap" => Ok(ThemeMode::Swap),
+            "augment" => Ok(ThemeMode::Augment),
+            _ => Err(format!("Unknown theme mode: '{}' (expected swap or augment)", s)),
+        }
+    }
+}
+
+/// `base` with `themes` applied, whatever order they're given in and however
+/// often each is repeated.
+pub fn themed(base: &WordSet, themes: &[Theme], mode: ThemeMode, rating: ContentRating) -> WordSet {
+    let mut themes = themes.to_vec();
+    themes.sort();
+    themes.dedup();
+    apply(base, &themes, mode, rating)
+}
+
+/// Build `base` with `themes` applied.
+///
+/// Themed words already in a list that fills the same template slot are
+/// dropped, so each list keeps distinct outcomes and the entropy computed
+/// from its size stays honest. Themed words rated above `rating` are left
+/// out.
+pub fn apply(base: &WordSet, themes: &[Theme], mode: ThemeMode, rating: ContentRating) -> WordSet {
+    let mut set = base.clone();
+
+    for &kind in THEMED {
+        let list = set.list_mut(kind);
+        if mode == ThemeMode::Swap {
+            list.clear();
+        }
+        for word in themes.iter().flat_map(|theme| rated(theme.words(kind), &[], rating)) {
+            if !list.iter().any(|entry| **entry == *word) {
+                list.push(Arc::from(word));
+            }
+        }
+    }
+
+    for &kind in THEMED {
+        let others: Vec<Arc<str>> = SLOTS
+            .iter()
+            .filter(|slot| slot.contains(&kind))
+            .flat_map(|slot| slot.iter().filter(|&&other| other != kind))
+            .flat_map(|&other| set.list(other).to_vec())
+            .collect();
+        set.list_mut(kind).retain(|word| !others.contains(word));
+    }
+
+    set
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_theme_is_valid_in_both_modes() {
+        for &rating in ContentRating::ALL {
+            for &theme in Theme::ALL {
+                for mode in [ThemeMode::Swap, ThemeMode::Augment] {
+                    let set = apply(WordSet::builtin(rating), &[theme], mode, rating);
+                    set.validate().unwrap_or_else(|e| panic!("{} ({:?}): {}", theme, mode, e));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_theme_lists_are_lowercase_and_unique() {
+        for &theme in Theme::ALL {
+            for &kind in THEMED {
+                let words = theme.words(kind);
+                assert!(words.len() >= 10, "{} has only {} {}", theme, words.len(), kind);
+                for (i, word) in words.iter().enumerate() {
+                    assert!(!words[..i].contains(word), "{} repeats \"{}\" in {}", theme, word, kind);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_swap_replaces_and_augment_extends() {
+        let base = WordSet::builtin(ContentRating::Workplace);
+        let swapped = apply(base, &[Theme::Pirates], ThemeMode::Swap, ContentRating::Workplace);
+        assert!(swapped.animals.len() <= Theme::Pirates.words(ListKind::Animal).len());
+        assert!(swapped.animals.contains(&"parrot".into()));
+        assert_eq!(swapped.nouns, base.nouns);
+
+        let augmented = apply(base, &[Theme::Pirates], ThemeMode::Augment, ContentRating::Workplace);
+        assert!(augmented.animals.len() > base.animals.len());
+        assert!(augmented.animals.starts_with(&base.animals));
+    }
+
+    #[test]
+    fn test_theme_order_does_not_matter() {
+        let base = WordSet::builtin(ContentRating::Workplace);
+        let first = themed(base, &[Theme::Kitchen, Theme::Office], ThemeMode::Swap, ContentRating::Workplace);
+        let themes = [Theme::Office, Theme::Kitchen, Theme::Office];
+        let second = themed(base, &themes, ThemeMode::Swap, ContentRating::Workplace);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("Sci-Fi".parse::<Theme>(), Ok(Theme::SciFi));
+        assert!("western".parse::<Theme>().is_err());
+        assert_eq!("augment".parse::<ThemeMode>(), Ok(ThemeMode::Augment));
+    }
+}
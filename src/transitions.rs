@@ -23,8 +23,8 @@ impl Category {
             Category::Animal => ANIMALS,
             Category::Profession => PROFESSIONS,
             Category::Noun => NOUNS,
-            Category::Gerund => GERUNDS,
-            Category::PresentVerb => PRESENT_VERBS,
+            Category::Gerund => &GERUNDS,
+            Category::PresentVerb => &PRESENT_VERBS,
             Category::Object => OBJECTS,
             Category::Adverb => ADVERBS,
             Category::Article => ARTICLES,
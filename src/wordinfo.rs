@@ -0,0 +1,408 @@
+//! Per-word metadata, and filters that keep only words that are short, easy
+//! to say or easy to spell.
+//!
+//! Every entry of a [`WordSet`] can be described by a [`WordInfo`]: its
+//! length, syllable count, spelling difficulty and whether it is hyphenated.
+//! Phrases such as "beware of" are measured word by word, so a list of short
+//! phrases passes a length limit even though the phrase as a whole is long.
+//!
+//! A [`WordFilter`] drops the entries that don't fit. Templates compute
+//! entropy from the size of the list they pick from, so a filtered set
+//! reports the smaller figure by itself. Articles are never filtered, since
+//! "a"/"an" agreement depends on them.
+
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::wordset::{ListKind, WordSet};
+
+/// How hard a word is to spell from hearing it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    /// Spelled the way it sounds: "walrus", "soggy".
+    #[default]
+    Easy,
+    /// One tricky spot: a silent letter, an unusual vowel pair, or a long word.
+    Medium,
+    /// Several tricky spots: "psychologist", "bureaucrat".
+    Hard,
+}
+
+impl Difficulty {
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Medium => "medium",
+            Difficulty::Hard => "hard",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "medium" => Ok(Difficulty::Medium),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!("Unknown difficulty '{}' (expected easy, medium or hard)", s)),
+        }
+    }
+}
+
+/// Metadata about one word list entry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WordInfo {
+    /// Characters in the entry's longest word.
+    pub len: usize,
+    /// Syllables in the entry's longest-sounding word.
+    pub syllables: usize,
+    /// Spelling difficulty of the entry's hardest word.
+    pub difficulty: Difficulty,
+    /// Whether the entry contains a hyphen ("forty-two").
+    pub hyphenated: bool,
+}
+
+impl WordInfo {
+    /// Describe `entry`.
+    pub fn of(entry: &str) -> WordInfo {
+        let words = || entry.split_whitespace();
+        WordInfo {
+            len: words().map(|w| w.chars().count()).max().unwrap_or(0),
+            syllables: words().map(syllables).max().unwrap_or(0),
+            difficulty: words().map(difficulty).max().unwrap_or_default(),
+            hyphenated: entry.contains('-'),
+        }
+    }
+}
+
+/// Words the rules in [`syllables`] count wrong, with their syllables.
+pub const SYLLABLE_EXCEPTIONS: &[(&str, usize)] = &[
+    ("abalone", 4), ("aches", 1), ("alien", 3), ("ambiguity", 5), ("asmr", 4), ("coati", 3), ("coital", 3),
+    ("concierge", 3), ("continuum", 4), ("coyote", 3), ("crier", 2), ("crooked", 2), ("druid", 2),
+    ("eavesdrop", 2), ("ethereal", 4), ("fenghuang", 2), ("forecast", 2), ("genealogist", 5), ("hierarchy", 4),
+    ("holier", 3), ("hr", 2), ("hutia", 3), ("jerboa", 3), ("kitsune", 3), ("koala", 3), ("meander", 3),
+    ("mokele", 3), ("naive", 2), ("naked", 2), ("nft", 3), ("notepads", 2), ("pierogis", 4), ("placemats", 2),
+    ("poet", 2), ("radioactive", 5), ("rapiers", 3), ("rearrange", 3), ("righteous", 2), ("scientist", 3),
+    ("somewhere", 2), ("stoically", 4), ("stoicism", 4), ("tarsier", 3), ("theatrical", 4), ("theatrically", 5),
+    ("timeline", 2), ("typewriters", 3), ("uakari", 3), ("warehouse", 2), ("wavelength", 2), ("wretched", 2),
+];
+
+/// Suffixes and second halves of compounds that follow a silent "e":
+/// "fiercely", "baleful", "whiteboards".
+const AFTER_SILENT_E: &[&str] =
+    &["ly", "ful", "ment", "less", "ness", "board", "keep", "dig", "walk", "plate", "fly", "monger", "bod"];
+
+/// Count the syllables of one English word by counting vowel groups.
+///
+/// Some vowel pairs are said apart ("theory", "media", "ritual"), a final
+/// "e" and the "-ed"/"-es" endings are usually silent ("confused",
+/// "snakes") unless they follow a consonant and "l" or "r" ("wrinkles",
+/// "ogre"), and an "e" before a suffix is silent too ("fiercely"). Words the
+/// rules get wrong are in [`SYLLABLE_EXCEPTIONS`].
+pub fn syllables(word: &str) -> usize {
+    let word = word.to_lowercase();
+    if let Some(&(_, count)) = SYLLABLE_EXCEPTIONS.iter().find(|(exception, _)| *exception == word) {
+        return count;
+    }
+    let chars: Vec<char> = word.chars().collect();
+    let n = chars.len();
+    let vowel = |i: usize| is_vowel(&chars, i);
+
+    let mut count = 0;
+    for i in 0..n {
+        if vowel(i) && (i == 0 || !vowel(i - 1) || said_apart(&chars, i)) {
+            count += 1;
+        }
+    }
+
+    let silent_ending = |suffix: &str, sounded: &str| {
+        let Some(stem) = n.checked_sub(suffix.chars().count() + 1) else {
+            return false;
+        };
+        let c = chars[stem];
+        if !word.ends_with(suffix) || vowel(stem) || sounded.contains(c) {
+            return false;
+        }
+        // "wrinkles", "ogre", "baffled"
+        let syllabic = "lr".contains(c) && stem > 0 && !vowel(stem - 1) && chars[stem - 1] != c;
+        // "-ches" and "-shes"
+        let sibilant = suffix == "es" && c == 'h' && stem > 0 && "cs".contains(chars[stem - 1]);
+        !syllabic && !sibilant
+    };
+    let ending = if word.ends_with('e') {
+        silent_ending("e", "")
+    } else if word.ends_with("ed") {
+        silent_ending("ed", "td")
+    } else {
+        silent_ending("es", "sxzcg")
+    };
+    if ending && count > 1 {
+        count -= 1;
+    }
+
+    // "rogue", "antiques"
+    let stem = word.strip_suffix(['s', 'd']).unwrap_or(&word);
+    let stem_len = stem.chars().count();
+    if (stem.ends_with("gue") || stem.ends_with("que")) && stem_len > 3 && vowel(stem_len - 4) && count > 1 {
+        count -= 1;
+    }
+
+    for i in 2..n {
+        let rest: String = chars[i + 1..].iter().collect();
+        let syllabic_l = chars[i - 1] == 'l' && !vowel(i - 2);
+        if chars[i] == 'e'
+            && !vowel(i - 1)
+            && !syllabic_l
+            && AFTER_SILENT_E.iter().any(|part| rest.starts_with(part))
+            && count > 1
+        {
+            count -= 1;
+        }
+    }
+
+    // "sarcasm", "nihilism"
+    let stem = word.strip_suffix('s').unwrap_or(&word);
+    if stem.ends_with("sm") && stem.chars().rev().nth(2).is_some_and(|c| "aeiouy".contains(c)) {
+        count += 1;
+    }
+    // "couldn't"
+    if word.ends_with("dn't") {
+        count += 1;
+    }
+    count.max(1)
+}
+
+/// Whether `chars[i]` is a vowel. "y" before a vowel is a consonant at the
+/// start of a word, after a vowel or after a single consonant ("yodel",
+/// "lawyer", "lanyard"), and a vowel otherwise ("hyena").
+pub(crate) fn is_vowel(chars: &[char], i: usize) -> bool {
+    let c = chars[i];
+    if !"aeiouy".contains(c) {
+        return false;
+    }
+    if c == 'y' && chars.get(i + 1).is_some_and(|&next| "aeiou".contains(next)) {
+        if i == 0 || "aeiouw".contains(chars[i - 1]) {
+            return false;
+        }
+        if i >= 2 && chars[i - 1] != 'h' && "aeiou".contains(chars[i - 2]) {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether the vowels at `i - 1` and `i` are two syllables.
+pub(crate) fn said_apart(chars: &[char], i: usize) -> bool {
+    let before = i.checked_sub(2).map(|j| chars[j]);
+    let after = chars.get(i + 1).copied();
+    let before_in = |set: &str| before.is_some_and(|c| set.contains(c));
+    match (chars[i - 1], chars[i]) {
+        // "media", "scorpion", "medium", but not "-tion", "-cial" or "-gious"
+        ('i', 'a' | 'o' | 'u') => !before_in("ctsgxh"),
+        // "chaos", "dryad", "hyena"
+        ('a', 'o') | ('y', 'a' | 'e' | 'o') => true,
+        // "theory", but not "pigeon" or "gorgeous"
+        ('e', 'o') => !(before == Some('g') && matches!(after, Some('n' | 'u'))),
+        // "ritual", but not "quagga" or "iguana"
+        ('u', 'a' | 'o') => !before_in("qg"),
+        // "influencer", "cruel"
+        ('u', 'e') => matches!(after, Some('l' | 'n')) && !before_in("qg"),
+        // "quiet", "anxiety"
+        ('i', 'e') => after == Some('t'),
+        _ => false,
+    }
+}
+
+/// Syllables of a whole entry: "rubber ducks" has three, "space-pirate"
+/// three.
+pub fn phrase_syllables(entry: &str) -> usize {
+    entry.split(|c: char| c.is_whitespace() || c == '-').filter(|w| !w.is_empty()).map(syllables).sum()
+}
+
+/// The syllables of every entry of `list`.
+pub fn syllable_counts(list: &[impl AsRef<str>]) -> Vec<usize> {
+    list.iter().map(|entry| phrase_syllables(entry.as_ref())).collect()
+}
+
+/// Letter groups that aren't spelled the way they sound.
+const TRICKY: &[&str] = &[
+    "ph", "gh", "ps", "pn", "kn", "wr", "mn", "rh", "gn", "ough", "augh", "eau", "ae", "ei", "ieu", "sch", "cq",
+    "que", "'",
+];
+
+/// Estimate how hard one word is to spell.
+pub fn difficulty(word: &str) -> Difficulty {
+    let word = word.to_lowercase();
+    let mut score = TRICKY.iter().filter(|group| word.contains(*group)).count();
+
+    let chars: Vec<char> = word.chars().collect();
+    let mut doubled: Vec<char> = chars.windows(2).filter(|pair| pair[0] == pair[1]).map(|pair| pair[0]).collect();
+    doubled.dedup();
+    if doubled.len() >= 2 {
+        score += 1;
+    }
+    if chars.len() > 9 {
+        score += 1;
+    }
+    if !word.is_ascii() {
+        score += 1;
+    }
+
+    match score {
+        0 => Difficulty::Easy,
+        1 => Difficulty::Medium,
+        _ => Difficulty::Hard,
+    }
+}
+
+/// Limits on the entries a [`WordSet`] keeps.
+///
+/// When no entry of a list meets the limits, the list keeps the entries that
+/// come closest instead of becoming empty; [`WordFilter::unmet`] names those
+/// lists.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WordFilter {
+    /// Longest word allowed, in characters.
+    pub max_word_len: Option<usize>,
+    /// Most syllables allowed in one word.
+    pub max_syllables: Option<usize>,
+    /// Hardest spelling allowed.
+    pub max_difficulty: Option<Difficulty>,
+    /// Drop hyphenated entries.
+    pub no_hyphens: bool,
+}
+
+impl WordFilter {
+    /// True if the filter keeps every word.
+    pub fn is_empty(&self) -> bool {
+        *self == WordFilter::default()
+    }
+
+    /// Whether `entry` meets every limit.
+    pub fn allows(&self, entry: &str) -> bool {
+        self.excess(&WordInfo::of(entry)) == 0
+    }
+
+    /// How far an entry is over the limits, summed across them.
+    fn excess(&self, info: &WordInfo) -> usize {
+        let over = |value: usize, limit: Option<usize>| limit.map_or(0, |limit| value.saturating_sub(limit));
+        over(info.len, self.max_word_len)
+            + over(info.syllables, self.max_syllables)
+            + over(info.difficulty as usize, self.max_difficulty.map(|d| d as usize))
+            + (self.no_hyphens && info.hyphenated) as usize
+    }
+
+    /// Lists of `base` that have entries but none that meet the limits.
+    pub fn unmet(&self, base: &WordSet) -> Vec<ListKind> {
+        ListKind::ALL
+            .iter()
+            .copied()
+            .filter(|&kind| {
+                let list = base.list(kind);
+                !list.is_empty() && !list.iter().any(|entry| self.allows(entry))
+            })
+            .collect()
+    }
+
+    /// `base` with every list but the articles filtered.
+    pub fn apply(&self, base: &WordSet) -> WordSet {
+        let mut set = base.clone();
+        for &kind in ListKind::ALL {
+            self.retain_closest(set.list_mut(kind));
+        }
+        self.retain_closest(&mut set.prepositions);
+        self.retain_closest(&mut set.connectors_active);
+        set
+    }
+
+    /// Keep the entries of `list` that are least over the limits.
+    fn retain_closest(&self, list: &mut Vec<Arc<str>>) {
+        let excess: Vec<usize> = list.iter().map(|entry| self.excess(&WordInfo::of(entry))).collect();
+        let Some(&closest) = excess.iter().min() else { return };
+        let mut excess = excess.into_iter();
+        list.retain(|_| excess.next() == Some(closest));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::WordConfig;
+    use crate::rating::ContentRating;
+
+    #[test]
+    fn test_counts_syllables() {
+        for (word, expected) in [
+            ("cat", 1),
+            ("walrus", 2),
+            ("snakes", 1),
+            ("confused", 2),
+            ("wrinkles", 2),
+            ("accountant", 3),
+            ("hippopotamus", 5),
+            ("the", 1),
+            ("biology", 4),
+            ("theory", 3),
+            ("gargoyles", 2),
+            ("finished", 2),
+            ("baffled", 2),
+            ("fiercely", 2),
+            ("rogue", 1),
+            ("sarcasm", 3),
+            ("couldn't", 2),
+            ("lanyards", 2),
+            ("naive", 2),
+        ] {
+            assert_eq!(syllables(word), expected, "{}", word);
+        }
+    }
+
+    #[test]
+    fn test_rates_spelling_difficulty() {
+        assert_eq!(difficulty("walrus"), Difficulty::Easy);
+        assert_eq!(difficulty("gnome"), Difficulty::Medium);
+        assert_eq!(difficulty("psychologist"), Difficulty::Hard);
+    }
+
+    #[test]
+    fn test_measures_phrases_word_by_word() {
+        let info = WordInfo::of("beware of");
+        assert_eq!(info.len, 6);
+        assert_eq!(info.syllables, 2);
+        assert!(WordInfo::of("forty-two").hyphenated);
+        assert_eq!(phrase_syllables("rubber ducks"), 3);
+        assert_eq!(phrase_syllables("space-pirate"), 3);
+        assert_eq!(syllable_counts(&["beneath", "the moonlight"]), [2, 3]);
+    }
+
+    #[test]
+    fn test_filter_keeps_closest_entries_rather_than_emptying_a_list() {
+        let mut base = WordSet::builtin(ContentRating::Workplace).clone();
+        base.animals = vec!["hippopotamus".into(), "armadillo".into(), "walrus".into()];
+        let filter = WordFilter { max_word_len: Some(4), ..WordFilter::default() };
+        let set = filter.apply(&base);
+        assert_eq!(set.animals, vec!["walrus".into()]);
+        assert!(filter.unmet(&base).contains(&ListKind::Animal));
+
+        let filter = WordFilter { max_word_len: Some(6), ..WordFilter::default() };
+        assert_eq!(filter.apply(&base).animals, vec!["walrus".into()]);
+        assert!(!filter.unmet(&base).contains(&ListKind::Animal));
+    }
+
+    #[test]
+    fn test_filtered_sets_are_shared() {
+        let config = WordConfig { filter: WordFilter { max_syllables: Some(2), ..WordFilter::default() }, ..WordConfig::default() };
+        let first = config.words();
+        assert!(std::sync::Arc::ptr_eq(&first, &config.words()));
+        assert!(first.adjectives.len() < WordSet::builtin(ContentRating::Workplace).adjectives.len());
+    }
+}
@@ -0,0 +1,285 @@
+//! Consistency checks for the built-in word lists.
+//!
+//! Entropy is computed as if every entry of a list were a distinct outcome.
+//! A word listed twice in the same list, or in two lists that feed the same
+//! slot of a template, makes some passwords more likely than the figure says
+//! and quietly inflates every estimate. [`audit`] finds those problems, along
+//! with NSFW words that repeat SFW ones and words that break a list's suffix
+//! convention, and the integration tests fail on any of them.
+//!
+//! Words shared between lists that never compete for the same slot are
+//! reported as warnings: they don't affect entropy, but they make a password
+//! harder to parse back into its categories.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::words::*;
+
+/// A named built-in word list.
+#[derive(Clone, Copy, Debug)]
+pub struct WordList {
+    /// The constant's name in [`crate::words`].
+    pub name: &'static str,
+    /// The entries.
+    pub words: &'static [&'static str],
+    /// Whether it is one of the `*_NSFW` lists, whose words are rated cheeky
+    /// or explicit (see [`crate::rating`]).
+    pub nsfw: bool,
+}
+
+const fn sfw(name: &'static str, words: &'static [&'static str]) -> WordList {
+    WordList { name, words, nsfw: false }
+}
+
+const fn nsfw(name: &'static str, words: &'static [&'static str]) -> WordList {
+    WordList { name, words, nsfw: true }
+}
+
+/// Every built-in list that templates and `mkname` draw from.
+pub static LISTS: LazyLock<Vec<WordList>> = LazyLock::new(|| {
+    vec![
+        sfw("ADJECTIVES", ADJECTIVES),
+        sfw("ANIMALS", ANIMALS),
+        sfw("PROFESSIONS", PROFESSIONS),
+        sfw("NOUNS", NOUNS),
+        sfw("GERUNDS", &GERUNDS),
+        sfw("PRESENT_VERBS", &PRESENT_VERBS),
+        sfw("OBJECTS", OBJECTS),
+        sfw("ADVERBS", ADVERBS),
+        sfw("ARTICLES", ARTICLES),
+        sfw("PREPOSITIONS", PREPOSITIONS),
+        sfw("CONNECTORS", CONNECTORS),
+        sfw("QUESTION_STARTERS", QUESTION_STARTERS),
+        sfw("IMPERATIVES", IMPERATIVES),
+        sfw("WARNINGS", WARNINGS),
+        sfw("CONFLICTS", CONFLICTS),
+        sfw("LOCATIONS", LOCATIONS),
+        sfw("PROHIBITIONS", PROHIBITIONS),
+        sfw("DEMANDS", DEMANDS),
+        sfw("TIME_MARKERS", TIME_MARKERS),
+        sfw("NUMBERS", NUMBERS),
+        sfw("STATUS_WORDS", STATUS_WORDS),
+        sfw("CONNECTORS_ACTIVE", CONNECTORS_ACTIVE),
+        sfw("COMPARATIVES", COMPARATIVES),
+        sfw("BASE_VERBS", BASE_VERBS),
+        sfw("BEARER_TITLES", BEARER_TITLES),
+        nsfw("ADJECTIVES_NSFW", ADJECTIVES_NSFW),
+        nsfw("ADVERBS_NSFW", ADVERBS_NSFW),
+        nsfw("PROFESSIONS_NSFW", PROFESSIONS_NSFW),
+        nsfw("NOUNS_NSFW", NOUNS_NSFW),
+        nsfw("OBJECTS_NSFW", OBJECTS_NSFW),
+        nsfw("BASE_VERBS_NSFW", BASE_VERBS_NSFW),
+        nsfw("PRESENT_VERBS_NSFW", &PRESENT_VERBS_NSFW),
+        nsfw("GERUNDS_NSFW", &GERUNDS_NSFW),
+        nsfw("IMPERATIVES_NSFW", IMPERATIVES_NSFW),
+        nsfw("PROHIBITIONS_NSFW", PROHIBITIONS_NSFW),
+        nsfw("DEMANDS_NSFW", DEMANDS_NSFW),
+    ]
+});
+
+/// Lists whose entries compete for one template slot. Each `get_*` function
+/// merges a list with its NSFW counterpart, and subjects and objects mix
+/// several categories.
+const SLOTS: &[&[&str]] = &[
+    &["ANIMALS", "PROFESSIONS", "PROFESSIONS_NSFW", "NOUNS", "NOUNS_NSFW"],
+    &["OBJECTS", "OBJECTS_NSFW", "NOUNS", "NOUNS_NSFW"],
+    &["ADJECTIVES", "ADJECTIVES_NSFW"],
+    &["ADVERBS", "ADVERBS_NSFW"],
+    &["BASE_VERBS", "BASE_VERBS_NSFW"],
+    &["PRESENT_VERBS", "PRESENT_VERBS_NSFW"],
+    &["GERUNDS", "GERUNDS_NSFW"],
+    &["IMPERATIVES", "IMPERATIVES_NSFW"],
+    &["PROHIBITIONS", "PROHIBITIONS_NSFW"],
+    &["DEMANDS", "DEMANDS_NSFW"],
+];
+
+/// Suffixes every entry of a list must end with.
+const CONVENTIONS: &[(&str, &str)] = &[
+    ("GERUNDS", "ing"),
+    ("GERUNDS_NSFW", "ing"),
+    ("ADVERBS", "ly"),
+    ("ADVERBS_NSFW", "ly"),
+];
+
+/// One problem found by [`audit`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// The word appears more than once in one list.
+    Duplicate { list: &'static str, word: &'static str },
+    /// An NSFW list repeats a word that is already in a SFW list.
+    NsfwDuplicate { nsfw_list: &'static str, sfw_list: &'static str, word: &'static str },
+    /// The word is an entry of two different lists.
+    Overlap { first: &'static str, second: &'static str, word: &'static str, same_slot: bool },
+    /// The word is an entry of one list and part of a phrase in another.
+    PhraseOverlap { list: &'static str, word: &'static str, phrase_list: &'static str, phrase: &'static str },
+    /// The entry doesn't end with the list's suffix.
+    Convention { list: &'static str, word: &'static str, suffix: &'static str },
+}
+
+impl Issue {
+    /// Whether the issue makes reported entropy wrong or breaks a list's
+    /// convention, as opposed to only making parsing ambiguous.
+    pub fn is_error(&self) -> bool {
+        match self {
+            Issue::Duplicate { .. } | Issue::NsfwDuplicate { .. } | Issue::Convention { .. } => true,
+            Issue::Overlap { same_slot, .. } => *same_slot,
+            Issue::PhraseOverlap { .. } => false,
+        }
+    }
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Issue::Duplicate { list, word } => write!(f, "\"{}\" appears more than once in {}", word, list),
+            Issue::NsfwDuplicate { nsfw_list, sfw_list, word } => {
+                write!(f, "\"{}\" in {} is already in {}", word, nsfw_list, sfw_list)
+            }
+            Issue::Overlap { first, second, word, same_slot } => {
+                write!(f, "\"{}\" is in both {} and {}", word, first, second)?;
+                if *same_slot {
+                    write!(f, ", which share a slot")?;
+                }
+                Ok(())
+            }
+            Issue::PhraseOverlap { list, word, phrase_list, phrase } => {
+                write!(f, "\"{}\" in {} also appears in \"{}\" in {}", word, list, phrase, phrase_list)
+            }
+            Issue::Convention { list, word, suffix } => {
+                write!(f, "\"{}\" in {} doesn't end in -{}", word, list, suffix)
+            }
+        }
+    }
+}
+
+/// Everything [`audit`] found.
+#[derive(Clone, Debug, Default)]
+pub struct AuditReport {
+    pub issues: Vec<Issue>,
+}
+
+impl AuditReport {
+    /// Issues that should fail the build.
+    pub fn errors(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|issue| issue.is_error())
+    }
+
+    /// Issues worth knowing about that don't affect entropy.
+    pub fn warnings(&self) -> impl Iterator<Item = &Issue> {
+        self.issues.iter().filter(|issue| !issue.is_error())
+    }
+
+    /// True if there are no errors.
+    pub fn is_clean(&self) -> bool {
+        self.errors().next().is_none()
+    }
+}
+
+/// Audit the built-in word lists.
+pub fn audit() -> AuditReport {
+    audit_lists(&LISTS)
+}
+
+fn audit_lists(lists: &[WordList]) -> AuditReport {
+    let mut issues = Vec::new();
+
+    for list in lists {
+        let mut seen = HashMap::new();
+        for &word in list.words {
+            *seen.entry(word).or_insert(0) += 1;
+            if seen[word] == 2 {
+                issues.push(Issue::Duplicate { list: list.name, word });
+            }
+        }
+
+        if let Some(&(_, suffix)) = CONVENTIONS.iter().find(|(name, _)| *name == list.name) {
+            for &word in list.words.iter().filter(|w| !w.ends_with(suffix)) {
+                issues.push(Issue::Convention { list: list.name, word, suffix });
+            }
+        }
+    }
+
+    for (i, first) in lists.iter().enumerate() {
+        for second in &lists[i + 1..] {
+            for &word in first.words.iter().filter(|w| second.words.contains(w)) {
+                if first.nsfw != second.nsfw {
+                    let (nsfw_list, sfw_list) =
+                        if first.nsfw { (first.name, second.name) } else { (second.name, first.name) };
+                    issues.push(Issue::NsfwDuplicate { nsfw_list, sfw_list, word });
+                } else {
+                    let same_slot = share_slot(first.name, second.name);
+                    issues.push(Issue::Overlap { first: first.name, second: second.name, word, same_slot });
+                }
+            }
+        }
+    }
+
+    for list in lists {
+        for phrase_list in lists.iter().filter(|l| l.name != list.name) {
+            for &phrase in phrase_list.words.iter().filter(|p| p.contains([' ', '-'])) {
+                for &word in list.words {
+                    if phrase.split([' ', '-']).any(|part| part == word) {
+                        issues.push(Issue::PhraseOverlap { list: list.name, word, phrase_list: phrase_list.name, phrase });
+                    }
+                }
+            }
+        }
+    }
+
+    AuditReport { issues }
+}
+
+fn share_slot(first: &str, second: &str) -> bool {
+    SLOTS.iter().any(|slot| slot.contains(&first) && slot.contains(&second))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_each_kind_of_issue() {
+        let lists = [
+            sfw("ANIMALS", &["walrus", "goose", "walrus"]),
+            sfw("NOUNS", &["goose", "tax return"]),
+            sfw("OBJECTS", &["tax"]),
+            sfw("GERUNDS", &["plotting", "scheme"]),
+            nsfw("NOUNS_NSFW", &["goose"]),
+        ];
+        let report = audit_lists(&lists);
+
+        assert!(report.issues.contains(&Issue::Duplicate { list: "ANIMALS", word: "walrus" }));
+        assert!(report.issues.contains(&Issue::Overlap {
+            first: "ANIMALS",
+            second: "NOUNS",
+            word: "goose",
+            same_slot: true,
+        }));
+        assert!(report.issues.contains(&Issue::NsfwDuplicate { nsfw_list: "NOUNS_NSFW", sfw_list: "NOUNS", word: "goose" }));
+        assert!(report.issues.contains(&Issue::PhraseOverlap {
+            list: "OBJECTS",
+            word: "tax",
+            phrase_list: "NOUNS",
+            phrase: "tax return",
+        }));
+        assert!(report.issues.contains(&Issue::Convention { list: "GERUNDS", word: "scheme", suffix: "ing" }));
+        assert!(!report.is_clean());
+    }
+
+    #[test]
+    fn test_overlap_across_slots_is_a_warning() {
+        let lists = [sfw("GERUNDS", &["plotting"]), sfw("STATUS_WORDS", &["plotting"])];
+        let report = audit_lists(&lists);
+        assert_eq!(report.warnings().count(), 1);
+        assert!(report.is_clean());
+    }
+
+    #[test]
+    fn test_lists_cover_slots() {
+        for name in SLOTS.iter().flat_map(|slot| slot.iter()).chain(CONVENTIONS.iter().map(|(name, _)| name)) {
+            assert!(LISTS.iter().any(|list| list.name == *name), "{} is not in LISTS", name);
+        }
+    }
+}
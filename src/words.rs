@@ -1,3 +1,8 @@
+use std::sync::LazyLock;
+
+use crate::morphology::{conjugate, Form};
+use crate::rating::{rated, ContentRating};
+
 pub const ADJECTIVES: &[&str] = &[
     // Emotional states
     "suspicious", "grumpy", "anxious", "paranoid", "smug", "indignant",
@@ -136,8 +141,8 @@ pub const ANIMALS: &[&str] = &[
     "kraken", "chimera", "minotaur", "centaur", "unicorn",
     "pegasus", "wyvern", "wyrm", "leviathan", "behemoth",
     "cerberus", "manticore", "sphinx", "gorgon", "harpy",
-    "siren", "mermaid", "cyclops", "banshee", "wraith",
-    "specter", "golem", "gargoyle", "djinn", "ifrit",
+    "siren", "mermaid", "cyclops", "banshee", "lamia",
+    "ghoul", "golem", "gargoyle", "djinn", "ifrit",
     // Cryptids and mysterious creatures
     "bigfoot", "sasquatch", "mothman", "chupacabra", "yeti",
     "nessie", "jackalope", "bunyip", "mokele", "skunk-ape",
@@ -153,10 +158,10 @@ pub const ANIMALS: &[&str] = &[
     "naga", "garuda", "qilin", "fenghuang", "pixiu",
     "barghest", "selkie", "kelpie", "puca", "cait-sith",
     // Unusual real animals (not already listed)
-    "okapi", "pangolin", "quokka", "fossa", "binturong",
+    "tamandua", "fennec", "genet", "bandicoot", "quagga",
     "zorilla", "caracal", "serval", "ocelot", "margay",
-    "quetzal", "hoopoe", "secretary", "marabou", "adjutant",
-    "jerboa", "pika", "desman", "solenodon", "hutia",
+    "quetzal", "hoopoe", "secretarybird", "marabou", "adjutant",
+    "jerboa", "pika", "desman", "hyrax", "hutia",
 ];
 
 pub const PROFESSIONS: &[&str] = &[
@@ -171,14 +176,14 @@ pub const PROFESSIONS: &[&str] = &[
     "farrier", "cobbler", "milliner", "haberdasher", "cordwainer",
     "ostler", "lamplighter", "crier", "jester", "minstrel",
     // Unusual modern jobs
-    "cryptographer", "sommelier", "taxidermist", "actuary", "epidemiologist",
+    "cryptographer", "sommelier", "taxidermist", "underwriter", "epidemiologist",
     "entomologist", "etymologist", "oenologist", "ornithologist", "mycologist",
     "archivist", "curator", "conservator", "docent", "genealogist",
     "futurist", "ethicist", "ombudsman", "mediator", "arbitrator",
     // Fantasy-adjacent
     "wizard", "bard", "ranger", "paladin", "druid",
     "warlock", "sorcerer", "necromancer", "enchanter", "conjurer",
-    "alchemist", "artificer", "illusionist", "diviner", "shaman",
+    "thaumaturge", "artificer", "illusionist", "diviner", "shaman",
     "oracle", "seer", "prophet", "sage", "mystic",
     // Blue collar
     "plumber", "electrician", "mechanic", "welder", "carpenter",
@@ -196,7 +201,7 @@ pub const PROFESSIONS: &[&str] = &[
     "puppeteer", "ventriloquist", "mime", "acrobat", "juggler",
     "calligrapher", "ceramicist", "glassblower", "woodcarver", "weaver",
     // Service and hospitality
-    "barista", "bartender", "sommelier", "concierge", "butler",
+    "barista", "bartender", "cellarmaster", "concierge", "butler",
     "valet", "chauffeur", "doorman", "bellhop", "maitre",
     "chef", "baker", "butcher", "fishmonger", "cheesemonger",
     "florist", "jeweler", "watchmaker", "optician", "tailor",
@@ -204,24 +209,24 @@ pub const PROFESSIONS: &[&str] = &[
     "pirate", "ninja", "viking", "gladiator", "mercenary",
     "bounty", "smuggler", "spy", "saboteur", "assassin",
     "explorer", "mountaineer", "diver", "astronaut", "stuntman",
-    "detective", "investigator", "warden", "ranger", "marshal",
+    "detective", "investigator", "warden", "sheriff", "marshal",
     // Miscellaneous interesting
-    "librarian", "archivist", "cartographer", "navigator", "surveyor",
+    "librarian", "indexer", "cartographer", "navigator", "surveyor",
     "beekeeper", "zookeeper", "falconer", "gamekeeper", "forester",
     "gravedigger", "undertaker", "embalmer", "coroner", "mortician",
     "hypnotist", "exorcist", "medium", "clairvoyant", "mentalist",
     // Religious/mystical
-    "prophet", "martyr", "heretic", "inquisitor", "crusader",
+    "soothsayer", "martyr", "heretic", "inquisitor", "crusader",
     "pilgrim", "monk", "nun", "cardinal", "pope",
     "archbishop", "abbot", "abbess", "deacon", "acolyte",
     "confessor", "zealot", "apostle", "disciple", "ascetic",
     // Space/sci-fi
-    "astronaut", "cosmonaut", "xenobiologist", "starship-captain", "space-pirate",
+    "spacewalker", "cosmonaut", "xenobiologist", "starship-captain", "space-pirate",
     "asteroid-miner", "terraformer", "starchart-navigator", "exoplanetologist", "cyborg",
     "android", "replicant", "time-traveler", "dimension-hopper", "warp-engineer",
     "alien-diplomat", "galactic-smuggler", "clone-wrangler", "nanomancer", "biotech-surgeon",
     // Medieval/fantasy
-    "squire", "page", "herald", "executioner", "torturer",
+    "squire", "page", "armorer", "executioner", "torturer",
     "dungeon-master", "drawbridge-operator", "moat-keeper", "castle-jester", "dragon-tamer",
     "gargoyle-whisperer", "siege-engineer", "court-poisoner", "royal-taster", "jousting-champion",
     "trebuchet-operator", "chainmail-polisher", "quest-giver", "tavern-wench", "stable-master",
@@ -296,98 +301,6 @@ pub const NOUNS: &[&str] = &[
     "decadence", "decrepitude", "desolation", "ruination", "obsequy",
 ];
 
-pub const GERUNDS: &[&str] = &[
-    // Action verbs (physical movement and manipulation)
-    "juggling", "throwing", "building", "destroying", "launching",
-    "catching", "climbing", "crawling", "dancing", "diving",
-    "dodging", "dragging", "dropping", "flinging", "grabbing",
-    "hammering", "hurling", "jumping", "kicking", "leaping",
-    "lifting", "punching", "pushing", "rolling", "running",
-    "shaking", "shoving", "sliding", "spinning", "stomping",
-    "swinging", "tossing", "wrestling", "yanking", "zooming",
-    // Mental verbs (thinking and cognition)
-    "contemplating", "questioning", "analyzing", "doubting", "pondering",
-    "calculating", "considering", "daydreaming", "imagining", "memorizing",
-    "philosophizing", "reasoning", "reflecting", "speculating", "theorizing",
-    "overthinking", "wondering", "brooding", "deliberating", "evaluating",
-    // Funny verbs (whimsical and absurd)
-    "bamboozling", "discombobulating", "flabbergasting", "hornswoggling", "befuddling",
-    "baffling", "bewildering", "boggling", "confounding", "dumbfounding",
-    "flummoxing", "gobsmacking", "mystifying", "perplexing", "stupefying",
-    "waddling", "wobbling", "yodeling", "snorkeling", "gallivanting",
-    "canoodling", "lollygagging", "moseying", "skedaddling", "vamoosing",
-    // Sneaky verbs (covert and secretive)
-    "smuggling", "hoarding", "scheming", "plotting", "lurking",
-    "conniving", "conspiring", "eavesdropping", "pilfering", "prowling",
-    "snooping", "stalking", "sneaking", "tiptoeing", "whispering",
-    "embezzling", "foraging", "scavenging", "sleuthing", "spying",
-    // Professional verbs (formal and business-like)
-    "auditing", "reviewing", "investigating", "cataloging", "documenting",
-    "administrating", "alphabetizing", "annotating", "appraising", "archiving",
-    "assessing", "certifying", "classifying", "compiling", "coordinating",
-    "delegating", "drafting", "facilitating", "forecasting", "implementing",
-    "indexing", "inspecting", "inventorying", "legislating", "notarizing",
-    // Communication verbs
-    "broadcasting", "negotiating", "debating", "arguing", "announcing",
-    "articulating", "chattering", "declaring", "gossiping", "mumbling",
-    "narrating", "proclaiming", "rambling", "ranting", "reciting",
-    // Creation and organization verbs
-    "assembling", "collecting", "organizing", "rearranging", "knitting",
-    "crafting", "designing", "fabricating", "forging", "sculpting",
-    // Consumption verbs
-    "devouring", "guzzling", "munching", "nibbling", "savoring",
-    "slurping", "chomping", "gobbling", "feasting", "sipping",
-    // Miscellaneous
-    "procrastinating", "meandering", "catapulting", "stampeding", "hibernating",
-    "demanding", "perfecting", "practicing", "teleporting", "combusting",
-];
-
-pub const PRESENT_VERBS: &[&str] = &[
-    // Action verbs (physical movement and manipulation)
-    "juggles", "throws", "builds", "destroys", "launches",
-    "catches", "climbs", "crawls", "dances", "dives",
-    "dodges", "drags", "drops", "flings", "grabs",
-    "hammers", "hurls", "jumps", "kicks", "leaps",
-    "lifts", "punches", "pushes", "rolls", "runs",
-    "shakes", "shoves", "slides", "spins", "stomps",
-    "swings", "tosses", "wrestles", "yanks", "zooms",
-    // Mental verbs (thinking and cognition)
-    "contemplates", "questions", "analyzes", "doubts", "ponders",
-    "calculates", "considers", "daydreams", "imagines", "memorizes",
-    "philosophizes", "reasons", "reflects", "speculates", "theorizes",
-    "overthinks", "wonders", "broods", "deliberates", "evaluates",
-    // Funny verbs (whimsical and absurd)
-    "bamboozles", "discombobulates", "flabbergasts", "hornswoggles", "befuddles",
-    "baffles", "bewilders", "boggles", "confounds", "dumbfounds",
-    "flummoxes", "gobsmacks", "mystifies", "perplexes", "stupefies",
-    "waddles", "wobbles", "yodels", "snorkels", "gallivants",
-    "canoodles", "lollygags", "moseys", "skedaddles", "vamooses",
-    // Sneaky verbs (covert and secretive)
-    "smuggles", "hoards", "schemes", "plots", "lurks",
-    "connives", "conspires", "eavesdrops", "pilfers", "prowls",
-    "snoops", "stalks", "sneaks", "tiptoes", "whispers",
-    "embezzles", "forages", "scavenges", "sleuths", "spies",
-    // Professional verbs (formal and business-like)
-    "audits", "reviews", "investigates", "catalogs", "documents",
-    "administrates", "alphabetizes", "annotates", "appraises", "archives",
-    "assesses", "certifies", "classifies", "compiles", "coordinates",
-    "delegates", "drafts", "facilitates", "forecasts", "implements",
-    "indexes", "inspects", "inventories", "legislates", "notarizes",
-    // Communication verbs
-    "broadcasts", "negotiates", "debates", "argues", "announces",
-    "articulates", "chatters", "declares", "gossips", "mumbles",
-    "narrates", "proclaims", "rambles", "rants", "recites",
-    // Creation and organization verbs
-    "assembles", "collects", "organizes", "rearranges", "knits",
-    "crafts", "designs", "fabricates", "forges", "sculpts",
-    // Consumption verbs
-    "devours", "guzzles", "munches", "nibbles", "savors",
-    "slurps", "chomps", "gobbles", "feasts", "sips",
-    // Miscellaneous
-    "procrastinates", "meanders", "catapults", "stampedes", "hibernates",
-    "demands", "perfects", "practices", "teleports", "combusts",
-];
-
 pub const OBJECTS: &[&str] = &[
     // Office items
     "staplers", "paperclips", "spreadsheets", "receipts", "invoices",
@@ -647,11 +560,22 @@ pub const YEARS: &[&str] = &[
     "1987", "1994", "2003", "2012", "1776",
 ];
 
+/// Titles for `mkname`'s "bearer" style ("keeper of the staplers").
+pub const BEARER_TITLES: &[&str] = &[
+    "guardian", "keeper", "bearer", "warden", "master",
+    "lord", "lady", "duke", "duchess", "baron",
+    "baroness", "count", "countess", "herald", "champion",
+    "protector", "defender", "custodian", "steward", "sentinel",
+    "overseer", "caretaker", "harbinger", "envoy", "emissary",
+];
+
 pub const COMPARATIVES: &[&str] = &[
     "versus", "against", "defeats", "fears", "challenges",
     "opposes", "resists", "confronts", "battles", "outsmarts",
 ];
 
+/// Verb lemmas. Every other verb form is derived from these by
+/// [`crate::morphology`], so adding a verb is one entry here.
 pub const BASE_VERBS: &[&str] = &[
     // Action verbs (physical movement and manipulation)
     "juggle", "throw", "build", "destroy", "launch",
@@ -698,80 +622,98 @@ pub const BASE_VERBS: &[&str] = &[
     "demand", "perfect", "practice", "teleport", "combust",
 ];
 
-/// Intransitive verbs that require prepositions before objects.
-/// Maps verb (base/present/gerund forms) to their required preposition.
-/// When these verbs are used, a preposition must be inserted before the object.
-pub const INTRANSITIVE_VERB_PREPS: &[(&str, &str)] = &[
+/// Third-person forms of [`BASE_VERBS`], used directly after a subject
+/// ("juggles").
+pub static PRESENT_VERBS: LazyLock<Vec<&'static str>> = LazyLock::new(|| conjugate(BASE_VERBS, Form::ThirdPerson));
+
+/// Gerunds of [`BASE_VERBS`], used after "is"/"was" ("juggling").
+pub static GERUNDS: LazyLock<Vec<&'static str>> = LazyLock::new(|| conjugate(BASE_VERBS, Form::Gerund));
+
+/// Intransitive verb lemmas and the preposition they require before an
+/// object ("dance with").
+pub const VERB_PREPOSITIONS: &[(&str, &str)] = &[
     // WITH - companionship/instrument
-    ("dance", "with"), ("dances", "with"), ("dancing", "with"),
-    ("gallivant", "with"), ("gallivants", "with"), ("gallivanting", "with"),
-    ("canoodle", "with"), ("canoodles", "with"), ("canoodling", "with"),
-    ("connive", "with"), ("connives", "with"), ("conniving", "with"),
-    ("conspire", "against"), ("conspires", "against"), ("conspiring", "against"),
+    ("dance", "with"),
+    ("gallivant", "with"),
+    ("canoodle", "with"),
+    ("connive", "with"),
+    ("conspire", "against"),
 
     // ABOUT - subject matter
-    ("daydream", "about"), ("daydreams", "about"), ("daydreaming", "about"),
-    ("philosophize", "about"), ("philosophizes", "about"), ("philosophizing", "about"),
-    ("speculate", "about"), ("speculates", "about"), ("speculating", "about"),
-    ("theorize", "about"), ("theorizes", "about"), ("theorizing", "about"),
-    ("wonder", "about"), ("wonders", "about"), ("wondering", "about"),
-    ("brood", "over"), ("broods", "over"), ("brooding", "over"),
-    ("deliberate", "on"), ("deliberates", "on"), ("deliberating", "on"),
-    ("yodel", "about"), ("yodels", "about"), ("yodeling", "about"),
-    ("scheme", "about"), ("schemes", "about"), ("scheming", "about"),
-    ("argue", "about"), ("argues", "about"), ("arguing", "about"),
-    ("chatter", "about"), ("chatters", "about"), ("chattering", "about"),
-    ("gossip", "about"), ("gossips", "about"), ("gossiping", "about"),
-    ("ramble", "about"), ("rambles", "about"), ("rambling", "about"),
-    ("rant", "about"), ("rants", "about"), ("ranting", "about"),
-    ("procrastinate", "on"), ("procrastinates", "on"), ("procrastinating", "on"),
+    ("daydream", "about"),
+    ("philosophize", "about"),
+    ("speculate", "about"),
+    ("theorize", "about"),
+    ("wonder", "about"),
+    ("brood", "over"),
+    ("deliberate", "on"),
+    ("yodel", "about"),
+    ("scheme", "about"),
+    ("argue", "about"),
+    ("chatter", "about"),
+    ("gossip", "about"),
+    ("ramble", "about"),
+    ("rant", "about"),
+    ("procrastinate", "on"),
 
     // ON - surface/target
-    ("climb", "on"), ("climbs", "on"), ("climbing", "on"),
-    ("stomp", "on"), ("stomps", "on"), ("stomping", "on"),
-    ("reflect", "on"), ("reflects", "on"), ("reflecting", "on"),
-    ("eavesdrop", "on"), ("eavesdrops", "on"), ("eavesdropping", "on"),
-    ("snoop", "on"), ("snoops", "on"), ("snooping", "on"),
-    ("spy", "on"), ("spies", "on"), ("spying", "on"),
-    ("feast", "on"), ("feasts", "on"), ("feasting", "on"),
+    ("climb", "on"),
+    ("stomp", "on"),
+    ("reflect", "on"),
+    ("eavesdrop", "on"),
+    ("snoop", "on"),
+    ("spy", "on"),
+    ("feast", "on"),
 
     // MOVEMENT - over/past/through
-    ("crawl", "over"), ("crawls", "over"), ("crawling", "over"),
-    ("jump", "over"), ("jumps", "over"), ("jumping", "over"),
-    ("leap", "over"), ("leaps", "over"), ("leaping", "over"),
-    ("zoom", "past"), ("zooms", "past"), ("zooming", "past"),
-    ("waddle", "toward"), ("waddles", "toward"), ("waddling", "toward"),
-    ("wobble", "toward"), ("wobbles", "toward"), ("wobbling", "toward"),
-    ("mosey", "toward"), ("moseys", "toward"), ("moseying", "toward"),
-    ("meander", "through"), ("meanders", "through"), ("meandering", "through"),
-    ("stampede", "through"), ("stampedes", "through"), ("stampeding", "through"),
+    ("crawl", "over"),
+    ("jump", "over"),
+    ("leap", "over"),
+    ("zoom", "past"),
+    ("waddle", "toward"),
+    ("wobble", "toward"),
+    ("mosey", "toward"),
+    ("meander", "through"),
+    ("stampede", "through"),
 
     // FROM - away from
-    ("run", "from"), ("runs", "from"), ("running", "from"),
-    ("skedaddle", "from"), ("skedaddles", "from"), ("skedaddling", "from"),
-    ("vamoose", "from"), ("vamooses", "from"), ("vamoosing", "from"),
+    ("run", "from"),
+    ("skedaddle", "from"),
+    ("vamoose", "from"),
 
     // INTO/TOWARD - direction
-    ("dive", "into"), ("dives", "into"), ("diving", "into"),
+    ("dive", "into"),
 
     // AROUND/NEAR - proximity
-    ("lurk", "near"), ("lurks", "near"), ("lurking", "near"),
-    ("prowl", "around"), ("prowls", "around"), ("prowling", "around"),
-    ("lollygag", "around"), ("lollygags", "around"), ("lollygagging", "around"),
-    ("snorkel", "near"), ("snorkels", "near"), ("snorkeling", "near"),
-    ("hibernate", "through"), ("hibernates", "through"), ("hibernating", "through"),
-    ("tiptoe", "around"), ("tiptoes", "around"), ("tiptoeing", "around"),
-    ("sneak", "past"), ("sneaks", "past"), ("sneaking", "past"),
+    ("lurk", "near"),
+    ("prowl", "around"),
+    ("lollygag", "around"),
+    ("snorkel", "near"),
+    ("hibernate", "through"),
+    ("tiptoe", "around"),
+    ("sneak", "past"),
 
     // FOR - seeking
-    ("forage", "for"), ("forages", "for"), ("foraging", "for"),
-    ("scavenge", "for"), ("scavenges", "for"), ("scavenging", "for"),
-    ("sleuth", "for"), ("sleuths", "for"), ("sleuthing", "for"),
+    ("forage", "for"),
+    ("scavenge", "for"),
+    ("sleuth", "for"),
 
     // AGAINST - opposition
-    ("plot", "against"), ("plots", "against"), ("plotting", "against"),
+    ("plot", "against"),
 ];
 
+/// Every form of the verbs in [`VERB_PREPOSITIONS`] (base, present and
+/// gerund) with its preposition. When these verbs are used, the preposition
+/// must be inserted before the object.
+pub static INTRANSITIVE_VERB_PREPS: LazyLock<Vec<(&'static str, &'static str)>> = LazyLock::new(|| {
+    let lemmas: Vec<&'static str> = VERB_PREPOSITIONS.iter().map(|&(lemma, _)| lemma).collect();
+    let prepositions = || VERB_PREPOSITIONS.iter().map(|&(_, prep)| prep);
+    [Form::Base, Form::ThirdPerson, Form::Gerund]
+        .iter()
+        .flat_map(|&form| conjugate(&lemmas, form).into_iter().zip(prepositions()))
+        .collect()
+});
+
 /// Get the required preposition for an intransitive verb, if any.
 /// Returns None for transitive verbs that can take direct objects.
 pub fn get_verb_preposition(verb: &str) -> Option<&'static str> {
@@ -832,19 +774,10 @@ pub const BASE_VERBS_NSFW: &[&str] = &[
     "ravish", "tantalize", "motorboat",
 ];
 
-pub const PRESENT_VERBS_NSFW: &[&str] = &[
-    "seduces", "fondles", "gropes", "arouses",
-    "undresses", "spanks", "caresses", "mounts",
-    "twerks", "probes", "titillates", "straddles",
-    "ravishes", "tantalizes", "motorboats",
-];
+pub static PRESENT_VERBS_NSFW: LazyLock<Vec<&'static str>> =
+    LazyLock::new(|| conjugate(BASE_VERBS_NSFW, Form::ThirdPerson));
 
-pub const GERUNDS_NSFW: &[&str] = &[
-    "seducing", "fondling", "groping", "arousing",
-    "undressing", "spanking", "caressing", "mounting",
-    "twerking", "probing", "titillating", "straddling",
-    "ravishing", "tantalizing", "motorboating",
-];
+pub static GERUNDS_NSFW: LazyLock<Vec<&'static str>> = LazyLock::new(|| conjugate(BASE_VERBS_NSFW, Form::Gerund));
 
 pub const IMPERATIVES_NSFW: &[&str] = &[
     "never sleep with", "stop touching", "quit fondling",
@@ -868,104 +801,60 @@ pub const DEMANDS_NSFW: &[&str] = &[
 ];
 
 // =============================================================================
-// Word Getter Functions (for NSFW support)
+// Word Getter Functions (by content rating)
 // =============================================================================
 
-/// Get adjectives, optionally including NSFW words.
-pub fn get_adjectives(nsfw: bool) -> Vec<&'static str> {
-    let mut words = ADJECTIVES.to_vec();
-    if nsfw {
-        words.extend(ADJECTIVES_NSFW);
-    }
-    words
+/// Get adjectives rated at or below `rating`.
+pub fn get_adjectives(rating: ContentRating) -> Vec<&'static str> {
+    rated(ADJECTIVES, ADJECTIVES_NSFW, rating)
 }
 
-/// Get adverbs, optionally including NSFW words.
-pub fn get_adverbs(nsfw: bool) -> Vec<&'static str> {
-    let mut words = ADVERBS.to_vec();
-    if nsfw {
-        words.extend(ADVERBS_NSFW);
-    }
-    words
+/// Get adverbs rated at or below `rating`.
+pub fn get_adverbs(rating: ContentRating) -> Vec<&'static str> {
+    rated(ADVERBS, ADVERBS_NSFW, rating)
 }
 
-/// Get professions, optionally including NSFW words.
-pub fn get_professions(nsfw: bool) -> Vec<&'static str> {
-    let mut words = PROFESSIONS.to_vec();
-    if nsfw {
-        words.extend(PROFESSIONS_NSFW);
-    }
-    words
+/// Get professions rated at or below `rating`.
+pub fn get_professions(rating: ContentRating) -> Vec<&'static str> {
+    rated(PROFESSIONS, PROFESSIONS_NSFW, rating)
 }
 
-/// Get nouns, optionally including NSFW words.
-pub fn get_nouns(nsfw: bool) -> Vec<&'static str> {
-    let mut words = NOUNS.to_vec();
-    if nsfw {
-        words.extend(NOUNS_NSFW);
-    }
-    words
+/// Get nouns rated at or below `rating`.
+pub fn get_nouns(rating: ContentRating) -> Vec<&'static str> {
+    rated(NOUNS, NOUNS_NSFW, rating)
 }
 
-/// Get objects, optionally including NSFW words.
-pub fn get_objects(nsfw: bool) -> Vec<&'static str> {
-    let mut words = OBJECTS.to_vec();
-    if nsfw {
-        words.extend(OBJECTS_NSFW);
-    }
-    words
+/// Get objects rated at or below `rating`.
+pub fn get_objects(rating: ContentRating) -> Vec<&'static str> {
+    rated(OBJECTS, OBJECTS_NSFW, rating)
 }
 
-/// Get base verbs, optionally including NSFW words.
-pub fn get_base_verbs(nsfw: bool) -> Vec<&'static str> {
-    let mut words = BASE_VERBS.to_vec();
-    if nsfw {
-        words.extend(BASE_VERBS_NSFW);
-    }
-    words
+/// Get base verbs rated at or below `rating`.
+pub fn get_base_verbs(rating: ContentRating) -> Vec<&'static str> {
+    rated(BASE_VERBS, BASE_VERBS_NSFW, rating)
 }
 
-/// Get present tense verbs, optionally including NSFW words.
-pub fn get_present_verbs(nsfw: bool) -> Vec<&'static str> {
-    let mut words = PRESENT_VERBS.to_vec();
-    if nsfw {
-        words.extend(PRESENT_VERBS_NSFW);
-    }
-    words
+/// Get present tense verbs rated at or below `rating`.
+pub fn get_present_verbs(rating: ContentRating) -> Vec<&'static str> {
+    rated(&PRESENT_VERBS, &PRESENT_VERBS_NSFW, rating)
 }
 
-/// Get gerunds, optionally including NSFW words.
-pub fn get_gerunds(nsfw: bool) -> Vec<&'static str> {
-    let mut words = GERUNDS.to_vec();
-    if nsfw {
-        words.extend(GERUNDS_NSFW);
-    }
-    words
+/// Get gerunds rated at or below `rating`.
+pub fn get_gerunds(rating: ContentRating) -> Vec<&'static str> {
+    rated(&GERUNDS, &GERUNDS_NSFW, rating)
 }
 
-/// Get imperatives, optionally including NSFW words.
-pub fn get_imperatives(nsfw: bool) -> Vec<&'static str> {
-    let mut words = IMPERATIVES.to_vec();
-    if nsfw {
-        words.extend(IMPERATIVES_NSFW);
-    }
-    words
+/// Get imperatives rated at or below `rating`.
+pub fn get_imperatives(rating: ContentRating) -> Vec<&'static str> {
+    rated(IMPERATIVES, IMPERATIVES_NSFW, rating)
 }
 
-/// Get prohibitions, optionally including NSFW words.
-pub fn get_prohibitions(nsfw: bool) -> Vec<&'static str> {
-    let mut words = PROHIBITIONS.to_vec();
-    if nsfw {
-        words.extend(PROHIBITIONS_NSFW);
-    }
-    words
+/// Get prohibitions rated at or below `rating`.
+pub fn get_prohibitions(rating: ContentRating) -> Vec<&'static str> {
+    rated(PROHIBITIONS, PROHIBITIONS_NSFW, rating)
 }
 
-/// Get demands, optionally including NSFW words.
-pub fn get_demands(nsfw: bool) -> Vec<&'static str> {
-    let mut words = DEMANDS.to_vec();
-    if nsfw {
-        words.extend(DEMANDS_NSFW);
-    }
-    words
+/// Get demands rated at or below `rating`.
+pub fn get_demands(rating: ContentRating) -> Vec<&'static str> {
+    rated(DEMANDS, DEMANDS_NSFW, rating)
 }
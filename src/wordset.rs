@@ -0,0 +1,562 @@
+//! The word lists a generator draws from, merged once per content rating.
+//!
+//! The `get_*` functions in [`crate::words`] build a fresh `Vec` on every call
+//! by filtering a list and its NSFW counterpart by rating. Templates need
+//! several lists per password, so bulk generation spent much of its time
+//! copying. A [`WordSet`] holds every merged list; the built-in ones are
+//! created on first use and shared for the life of the process, and
+//! generators borrow slices from them. Entries are `Arc<str>`, so the sets
+//! derived from one (themed, filtered, pluralised) share its words, and words
+//! loaded at runtime are freed with the last set that holds them.
+//!
+//! # Custom lists
+//!
+//! A set can also be loaded at runtime on top of a base set, either from a
+//! TOML file ([`WordSet::from_toml`]) or from a directory with one text file
+//! per list ([`WordSet::from_dir`]). Lists are keyed by [`ListKind::name`]:
+//!
+//! ```toml
+//! # Replace a list entirely
+//! animals = ["walrus", "office llama"]
+//!
+//! # Add to the base list
+//! [extend]
+//! nouns = ["quarterly roadmap", "ping-pong table"]
+//!
+//! # Drop words from the base list
+//! [remove]
+//! professions = ["accountant"]
+//! ```
+//!
+//! Entropy is always computed from the lists actually drawn from, so a
+//! smaller custom list lowers the reported bits accordingly. Articles,
+//! prepositions and connectors are grammar, not vocabulary, and can't be
+//! replaced.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
+
+use crate::rating::{rated, ContentRating};
+use crate::words::*;
+
+/// Every list a template draws from, with NSFW additions already merged in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WordSet {
+    pub adjectives: Vec<Arc<str>>,
+    pub animals: Vec<Arc<str>>,
+    pub professions: Vec<Arc<str>>,
+    pub nouns: Vec<Arc<str>>,
+    pub objects: Vec<Arc<str>>,
+    pub adverbs: Vec<Arc<str>>,
+    pub present_verbs: Vec<Arc<str>>,
+    pub base_verbs: Vec<Arc<str>>,
+    pub gerunds: Vec<Arc<str>>,
+    pub imperatives: Vec<Arc<str>>,
+    pub prohibitions: Vec<Arc<str>>,
+    pub demands: Vec<Arc<str>>,
+    pub articles: Vec<Arc<str>>,
+    pub prepositions: Vec<Arc<str>>,
+    pub question_starters: Vec<Arc<str>>,
+    pub warnings: Vec<Arc<str>>,
+    pub conflicts: Vec<Arc<str>>,
+    pub locations: Vec<Arc<str>>,
+    pub time_markers: Vec<Arc<str>>,
+    pub numbers: Vec<Arc<str>>,
+    pub connectors_active: Vec<Arc<str>>,
+    pub bearer_titles: Vec<Arc<str>>,
+}
+
+/// A list of a [`WordSet`] that can be loaded from a file.
+///
+/// The word categories mirror [`Category`](crate::transitions::Category);
+/// the phrase lists used by individual templates and `mkname`'s bearer titles
+/// are included as well.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ListKind {
+    Adjective,
+    Animal,
+    Profession,
+    Noun,
+    Gerund,
+    PresentVerb,
+    Object,
+    Adverb,
+    BaseVerb,
+    Imperative,
+    Prohibition,
+    Demand,
+    QuestionStarter,
+    Warning,
+    Conflict,
+    Location,
+    TimeMarker,
+    Number,
+    BearerTitle,
+}
+
+impl ListKind {
+    /// Every loadable list.
+    pub const ALL: &'static [ListKind] = &[
+        ListKind::Adjective,
+        ListKind::Animal,
+        ListKind::Profession,
+        ListKind::Noun,
+        ListKind::Gerund,
+        ListKind::PresentVerb,
+        ListKind::Object,
+        ListKind::Adverb,
+        ListKind::BaseVerb,
+        ListKind::Imperative,
+        ListKind::Prohibition,
+        ListKind::Demand,
+        ListKind::QuestionStarter,
+        ListKind::Warning,
+        ListKind::Conflict,
+        ListKind::Location,
+        ListKind::TimeMarker,
+        ListKind::Number,
+        ListKind::BearerTitle,
+    ];
+
+    /// The key used in TOML files and the file stem used by [`WordSet::from_dir`].
+    pub fn name(self) -> &'static str {
+        match self {
+            ListKind::Adjective => "adjectives",
+            ListKind::Animal => "animals",
+            ListKind::Profession => "professions",
+            ListKind::Noun => "nouns",
+            ListKind::Gerund => "gerunds",
+            ListKind::PresentVerb => "present_verbs",
+            ListKind::Object => "objects",
+            ListKind::Adverb => "adverbs",
+            ListKind::BaseVerb => "base_verbs",
+            ListKind::Imperative => "imperatives",
+            ListKind::Prohibition => "prohibitions",
+            ListKind::Demand => "demands",
+            ListKind::QuestionStarter => "question_starters",
+            ListKind::Warning => "warnings",
+            ListKind::Conflict => "conflicts",
+            ListKind::Location => "locations",
+            ListKind::TimeMarker => "time_markers",
+            ListKind::Number => "numbers",
+            ListKind::BearerTitle => "bearer_titles",
+        }
+    }
+
+    /// Look a list up by its [`name`](ListKind::name).
+    pub fn from_name(name: &str) -> Option<ListKind> {
+        ListKind::ALL.iter().copied().find(|kind| kind.name() == name)
+    }
+}
+
+impl fmt::Display for ListKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Lists whose entries compete for the same template slot, so a word in two
+/// of them would be counted as two different outcomes.
+pub(crate) const SLOTS: &[&[ListKind]] = &[
+    &[ListKind::Animal, ListKind::Profession, ListKind::Noun],
+    &[ListKind::Object, ListKind::Noun],
+];
+
+/// Why a custom word list couldn't be loaded.
+#[derive(Debug)]
+pub enum WordSetError {
+    /// A file or directory couldn't be read.
+    Io { path: PathBuf, error: io::Error },
+    /// The TOML was malformed.
+    Parse(String),
+    /// A key isn't the name of a loadable list.
+    UnknownList(String),
+    /// A key's value isn't an array of strings.
+    NotAList(String),
+    /// A list ended up with no entries.
+    EmptyList(ListKind),
+    /// An entry is blank or has characters other than lowercase letters,
+    /// digits, single spaces, hyphens and apostrophes.
+    InvalidEntry { list: ListKind, entry: String },
+    /// A `[remove]` entry isn't in the list.
+    MissingWord { list: ListKind, word: String },
+    /// A word appears twice in one list.
+    Duplicate { list: ListKind, word: String },
+    /// A word is in two lists that fill the same slot.
+    Overlap { first: ListKind, second: ListKind, word: String },
+}
+
+impl fmt::Display for WordSetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WordSetError::Io { path, error } => write!(f, "cannot read {}: {}", path.display(), error),
+            WordSetError::Parse(message) => write!(f, "invalid word list file: {}", message),
+            WordSetError::UnknownList(name) => {
+                let names: Vec<_> = ListKind::ALL.iter().map(|kind| kind.name()).collect();
+                write!(f, "unknown list \"{}\" (expected one of: {})", name, names.join(", "))
+            }
+            WordSetError::NotAList(name) => write!(f, "\"{}\" must be an array of strings", name),
+            WordSetError::EmptyList(list) => write!(f, "the {} list is empty", list),
+            WordSetError::InvalidEntry { list, entry } => write!(
+                f,
+                "invalid entry {:?} in {}: use lowercase letters, digits, single spaces, hyphens and apostrophes",
+                entry, list
+            ),
+            WordSetError::MissingWord { list, word } => write!(f, "cannot remove \"{}\": not in {}", word, list),
+            WordSetError::Duplicate { list, word } => write!(f, "\"{}\" appears more than once in {}", word, list),
+            WordSetError::Overlap { first, second, word } => {
+                write!(f, "\"{}\" is in both {} and {}, which fill the same slot", word, first, second)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WordSetError {}
+
+static BUILTIN: [LazyLock<Arc<WordSet>>; 4] = [
+    LazyLock::new(|| Arc::new(WordSet::new(ContentRating::Kids))),
+    LazyLock::new(|| Arc::new(WordSet::new(ContentRating::Workplace))),
+    LazyLock::new(|| Arc::new(WordSet::new(ContentRating::Cheeky))),
+    LazyLock::new(|| Arc::new(WordSet::new(ContentRating::Explicit))),
+];
+
+impl WordSet {
+    /// Build the built-in lists with the words rated at or below `rating`.
+    ///
+    /// Prefer [`WordSet::builtin`], which builds each rating only once.
+    pub fn new(rating: ContentRating) -> Self {
+        WordSet {
+            adjectives: entries_of(get_adjectives(rating)),
+            animals: entries_of(rated(ANIMALS, &[], rating)),
+            professions: entries_of(get_professions(rating)),
+            nouns: entries_of(get_nouns(rating)),
+            objects: entries_of(get_objects(rating)),
+            adverbs: entries_of(get_adverbs(rating)),
+            present_verbs: entries_of(get_present_verbs(rating)),
+            base_verbs: entries_of(get_base_verbs(rating)),
+            gerunds: entries_of(get_gerunds(rating)),
+            imperatives: entries_of(get_imperatives(rating)),
+            prohibitions: entries_of(get_prohibitions(rating)),
+            demands: entries_of(get_demands(rating)),
+            articles: entries_of(rated(ARTICLES, &[], rating)),
+            prepositions: entries_of(rated(PREPOSITIONS, &[], rating)),
+            question_starters: entries_of(rated(QUESTION_STARTERS, &[], rating)),
+            warnings: entries_of(rated(WARNINGS, &[], rating)),
+            conflicts: entries_of(rated(CONFLICTS, &[], rating)),
+            locations: entries_of(rated(LOCATIONS, &[], rating)),
+            time_markers: entries_of(rated(TIME_MARKERS, &[], rating)),
+            numbers: entries_of(rated(NUMBERS, &[], rating)),
+            connectors_active: entries_of(rated(CONNECTORS_ACTIVE, &[], rating)),
+            bearer_titles: entries_of(rated(BEARER_TITLES, &[], rating)),
+        }
+    }
+
+    /// The shared built-in lists for a content rating.
+    pub fn builtin(rating: ContentRating) -> &'static WordSet {
+        &BUILTIN[rating as usize]
+    }
+
+    /// The shared built-in lists for a content rating, as an `Arc` that can
+    /// go in a [`WordConfig`](crate::generator::WordConfig).
+    pub fn shared(rating: ContentRating) -> Arc<WordSet> {
+        Arc::clone(&BUILTIN[rating as usize])
+    }
+
+    /// The entries of one list.
+    pub fn list(&self, kind: ListKind) -> &[Arc<str>] {
+        match kind {
+            ListKind::Adjective => &self.adjectives,
+            ListKind::Animal => &self.animals,
+            ListKind::Profession => &self.professions,
+            ListKind::Noun => &self.nouns,
+            ListKind::Gerund => &self.gerunds,
+            ListKind::PresentVerb => &self.present_verbs,
+            ListKind::Object => &self.objects,
+            ListKind::Adverb => &self.adverbs,
+            ListKind::BaseVerb => &self.base_verbs,
+            ListKind::Imperative => &self.imperatives,
+            ListKind::Prohibition => &self.prohibitions,
+            ListKind::Demand => &self.demands,
+            ListKind::QuestionStarter => &self.question_starters,
+            ListKind::Warning => &self.warnings,
+            ListKind::Conflict => &self.conflicts,
+            ListKind::Location => &self.locations,
+            ListKind::TimeMarker => &self.time_markers,
+            ListKind::Number => &self.numbers,
+            ListKind::BearerTitle => &self.bearer_titles,
+        }
+    }
+
+    pub(crate) fn list_mut(&mut self, kind: ListKind) -> &mut Vec<Arc<str>> {
+        match kind {
+            ListKind::Adjective => &mut self.adjectives,
+            ListKind::Animal => &mut self.animals,
+            ListKind::Profession => &mut self.professions,
+            ListKind::Noun => &mut self.nouns,
+            ListKind::Gerund => &mut self.gerunds,
+            ListKind::PresentVerb => &mut self.present_verbs,
+            ListKind::Object => &mut self.objects,
+            ListKind::Adverb => &mut self.adverbs,
+            ListKind::BaseVerb => &mut self.base_verbs,
+            ListKind::Imperative => &mut self.imperatives,
+            ListKind::Prohibition => &mut self.prohibitions,
+            ListKind::Demand => &mut self.demands,
+            ListKind::QuestionStarter => &mut self.question_starters,
+            ListKind::Warning => &mut self.warnings,
+            ListKind::Conflict => &mut self.conflicts,
+            ListKind::Location => &mut self.locations,
+            ListKind::TimeMarker => &mut self.time_markers,
+            ListKind::Number => &mut self.numbers,
+            ListKind::BearerTitle => &mut self.bearer_titles,
+        }
+    }
+
+    /// Load lists from TOML text on top of `base` (see the module docs for
+    /// the format), then [`validate`](WordSet::validate) the result.
+    ///
+    /// The entries kept from `base` are shared with it; loaded ones are
+    /// freed once no set holds them.
+    pub fn from_toml(text: &str, base: &WordSet) -> Result<WordSet, WordSetError> {
+        let table: toml::Table = text.parse().map_err(|e: toml::de::Error| WordSetError::Parse(e.message().to_string()))?;
+        let mut set = base.clone();
+
+        for (key, value) in table.iter().filter(|(key, _)| !matches!(key.as_str(), "extend" | "remove")) {
+            *set.list_mut(list_kind(key)?) = entries(key, value)?;
+        }
+        for (section, value) in table.iter().filter(|(key, _)| matches!(key.as_str(), "extend" | "remove")) {
+            let lists = value.as_table().ok_or_else(|| WordSetError::NotAList(section.clone()))?;
+            for (key, value) in lists {
+                let kind = list_kind(key)?;
+                let words = entries(key, value)?;
+                let list = set.list_mut(kind);
+                if section == "extend" {
+                    list.extend(words);
+                } else {
+                    for word in words {
+                        let position = list
+                            .iter()
+                            .position(|w| *w == word)
+                            .ok_or_else(|| WordSetError::MissingWord { list: kind, word: word.to_string() })?;
+                        list.remove(position);
+                    }
+                }
+            }
+        }
+
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Load a TOML file with [`WordSet::from_toml`].
+    pub fn from_toml_file(path: impl AsRef<Path>, base: &WordSet) -> Result<WordSet, WordSetError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|error| WordSetError::Io { path: path.to_path_buf(), error })?;
+        WordSet::from_toml(&text, base)
+    }
+
+    /// Load lists from a directory on top of `base`, then
+    /// [`validate`](WordSet::validate) the result. Loaded words are shared
+    /// as with [`WordSet::from_toml`].
+    ///
+    /// Each `<name>.txt` file (named after [`ListKind::name`]) replaces that
+    /// list with its lines; blank lines and lines starting with `#` are
+    /// skipped. Other files are ignored, and lists without a file keep their
+    /// `base` entries.
+    pub fn from_dir(path: impl AsRef<Path>, base: &WordSet) -> Result<WordSet, WordSetError> {
+        let path = path.as_ref();
+        fs::read_dir(path).map_err(|error| WordSetError::Io { path: path.to_path_buf(), error })?;
+        let mut set = base.clone();
+
+        for &kind in ListKind::ALL {
+            let file = path.join(format!("{}.txt", kind.name()));
+            let text = match fs::read_to_string(&file) {
+                Ok(text) => text,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => continue,
+                Err(error) => return Err(WordSetError::Io { path: file, error }),
+            };
+            let lines = text.lines().map(str::trim).filter(|line| !line.is_empty() && !line.starts_with('#'));
+            *set.list_mut(kind) = lines.map(Arc::from).collect();
+        }
+
+        set.validate()?;
+        Ok(set)
+    }
+
+    /// Check that every list can be drawn from and that entropy computed from
+    /// the list sizes is honest: no empty lists, no malformed entries, no word
+    /// listed twice, and no word in two lists that fill the same slot.
+    pub fn validate(&self) -> Result<(), WordSetError> {
+        validate(|kind| self.list(kind).iter().map(|word| &**word).collect())
+    }
+}
+
+/// The checks of [`WordSet::validate`], on the lists `list` returns.
+fn validate<'a>(list: impl Fn(ListKind) -> Vec<&'a str>) -> Result<(), WordSetError> {
+    let lists: Vec<Vec<&str>> = ListKind::ALL.iter().map(|&kind| list(kind)).collect();
+    let list = |kind: ListKind| &lists[ListKind::ALL.iter().position(|&k| k == kind).unwrap()];
+
+    for &kind in ListKind::ALL {
+        let list = list(kind);
+        if list.is_empty() {
+            return Err(WordSetError::EmptyList(kind));
+        }
+        for (i, &word) in list.iter().enumerate() {
+            if !is_valid_entry(word) {
+                return Err(WordSetError::InvalidEntry { list: kind, entry: word.to_string() });
+            }
+            if list[..i].contains(&word) {
+                return Err(WordSetError::Duplicate { list: kind, word: word.to_string() });
+            }
+        }
+    }
+
+    for slot in SLOTS {
+        for (i, &first) in slot.iter().enumerate() {
+            for &second in &slot[i + 1..] {
+                if let Some(&word) = list(first).iter().find(|w| list(second).contains(w)) {
+                    return Err(WordSetError::Overlap { first, second, word: word.to_string() });
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn list_kind(name: &str) -> Result<ListKind, WordSetError> {
+    ListKind::from_name(name).ok_or_else(|| WordSetError::UnknownList(name.to_string()))
+}
+
+/// The trimmed strings of a TOML array.
+fn entries(name: &str, value: &toml::Value) -> Result<Vec<Arc<str>>, WordSetError> {
+    let not_a_list = || WordSetError::NotAList(name.to_string());
+    value
+        .as_array()
+        .ok_or_else(not_a_list)?
+        .iter()
+        .map(|entry| entry.as_str().map(|s| Arc::from(s.trim())).ok_or_else(not_a_list))
+        .collect()
+}
+
+/// The entries of a built-in list.
+fn entries_of(list: Vec<&'static str>) -> Vec<Arc<str>> {
+    list.into_iter().map(Arc::from).collect()
+}
+
+fn is_valid_entry(word: &str) -> bool {
+    !word.is_empty()
+        && !word.starts_with([' ', '-'])
+        && !word.ends_with([' ', '-'])
+        && !word.contains("  ")
+        && word.chars().all(|c| c.is_lowercase() || c.is_ascii_digit() || matches!(c, ' ' | '-' | '\''))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(list: &[Arc<str>]) -> Vec<&str> {
+        list.iter().map(|word| &**word).collect()
+    }
+
+    #[test]
+    fn test_builtin_matches_getters() {
+        assert_eq!(WordSet::builtin(ContentRating::Workplace), &WordSet::new(ContentRating::Workplace));
+        let explicit = WordSet::builtin(ContentRating::Explicit);
+        assert_eq!(words(&explicit.adjectives), get_adjectives(ContentRating::Explicit));
+        assert_eq!(words(&WordSet::builtin(ContentRating::Explicit).animals), ANIMALS);
+    }
+
+    #[test]
+    fn test_builtin_is_shared() {
+        assert!(std::ptr::eq(WordSet::builtin(ContentRating::Explicit), WordSet::builtin(ContentRating::Explicit)));
+        assert!(std::ptr::eq(WordSet::builtin(ContentRating::Explicit), &*WordSet::shared(ContentRating::Explicit)));
+        assert!(!std::ptr::eq(WordSet::builtin(ContentRating::Explicit), WordSet::builtin(ContentRating::Workplace)));
+    }
+
+    #[test]
+    fn test_builtin_sets_are_valid() {
+        for &rating in ContentRating::ALL {
+            WordSet::builtin(rating).validate().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_toml_replace_extend_remove() {
+        let text = r#"
+            animals = ["walrus", "office llama"]
+
+            [extend]
+            nouns = ["quarterly roadmap"]
+
+            [remove]
+            professions = ["accountant"]
+        "#;
+        let base = WordSet::builtin(ContentRating::Workplace);
+        let set = WordSet::from_toml(text, base).unwrap();
+
+        assert_eq!(words(&set.animals), ["walrus", "office llama"]);
+        assert_eq!(set.nouns.len(), base.nouns.len() + 1);
+        assert!(words(&set.nouns).contains(&"quarterly roadmap"));
+        assert_eq!(set.professions.len(), base.professions.len() - 1);
+        assert!(!words(&set.professions).contains(&"accountant"));
+        assert_eq!(set.adjectives, base.adjectives);
+    }
+
+    #[test]
+    fn test_toml_errors() {
+        let base = WordSet::builtin(ContentRating::Workplace);
+        let error = |text: &str| WordSet::from_toml(text, base).unwrap_err();
+
+        assert!(matches!(error("animals = ["), WordSetError::Parse(_)));
+        assert!(matches!(error("llamas = [\"a\"]"), WordSetError::UnknownList(name) if name == "llamas"));
+        assert!(matches!(error("animals = \"walrus\""), WordSetError::NotAList(_)));
+        assert!(matches!(error("animals = []"), WordSetError::EmptyList(ListKind::Animal)));
+        assert!(matches!(error("animals = [\"Walrus\"]"), WordSetError::InvalidEntry { .. }));
+        assert!(matches!(
+            error("animals = [\"walrus\", \"walrus\"]"),
+            WordSetError::Duplicate { list: ListKind::Animal, word } if word == "walrus"
+        ));
+        assert!(matches!(
+            error("[extend]\nanimals = [\"accountant\"]"),
+            WordSetError::Overlap { first: ListKind::Animal, second: ListKind::Profession, word } if word == "accountant"
+        ));
+        assert!(matches!(error("[remove]\nanimals = [\"unicorn cat\"]"), WordSetError::MissingWord { .. }));
+    }
+
+    #[test]
+    fn test_loaded_words_are_freed_with_their_set() {
+        let base = WordSet::builtin(ContentRating::Workplace);
+        let set = WordSet::from_toml("animals = [\"passing quokka\"]", base).unwrap();
+        // Entries kept from the base are shared, not copied
+        assert!(Arc::ptr_eq(&set.nouns[0], &base.nouns[0]));
+
+        let quokka = Arc::downgrade(&set.animals[0]);
+        drop(set);
+        assert!(quokka.upgrade().is_none());
+    }
+
+    #[test]
+    fn test_from_dir() {
+        let dir = std::env::temp_dir().join(format!("wordset-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("animals.txt"), "# our mascots\nwalrus\n\noffice llama\n").unwrap();
+        fs::write(dir.join("README.md"), "not a list").unwrap();
+
+        let set = WordSet::from_dir(&dir, WordSet::builtin(ContentRating::Workplace));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let set = set.unwrap();
+        assert_eq!(words(&set.animals), ["walrus", "office llama"]);
+        assert_eq!(set.nouns, WordSet::builtin(ContentRating::Workplace).nouns);
+    }
+
+    #[test]
+    fn test_missing_dir_is_an_error() {
+        let error = WordSet::from_dir("/nonexistent/word/lists", WordSet::builtin(ContentRating::Workplace)).unwrap_err();
+        assert!(matches!(error, WordSetError::Io { .. }));
+    }
+}
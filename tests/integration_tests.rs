@@ -1,13 +1,28 @@
+use std::sync::Arc;
+
+use funny_password_generator::article::starts_with_vowel_sound;
 use funny_password_generator::entropy::{
     calculate_entropy, estimate_entropy_from_words, estimate_password_entropy, format_entropy,
 };
-use funny_password_generator::generator::{generate_password, generate_password_with_template, WordConfig};
+use funny_password_generator::generator::{
+    generate_password, generate_password_declarative, generate_password_declarative_with_rng,
+    generate_password_with_rng, generate_password_with_template, generate_password_with_template_with_rng,
+    generate_with_min_entropy_with_rng,
+    WordConfig,
+};
+use funny_password_generator::formatting::{format_password_with_rng, FormatOptions, OPENERS};
+use funny_password_generator::mix::TemplateMix;
+use funny_password_generator::pattern::Pattern;
+use funny_password_generator::plural::{amount_of, count, is_mass, plural, plural_verb, Count};
 use funny_password_generator::templates::Template;
 use funny_password_generator::transitions::{get_transitions, Category};
+use funny_password_generator::wordlists::audit;
 use funny_password_generator::words::*;
+use rand::rngs::{OsRng, StdRng};
+use rand::SeedableRng;
 
 fn default_config() -> WordConfig {
-    WordConfig { nsfw: false }
+    WordConfig::default()
 }
 
 // ============================================================================
@@ -16,7 +31,7 @@ fn default_config() -> WordConfig {
 
 #[test]
 fn test_generate_password_returns_non_empty() {
-    let password = generate_password(4, &default_config());
+    let password = generate_password(4, &default_config()).unwrap().words;
     assert!(!password.is_empty(), "Password should not be empty");
 }
 
@@ -25,7 +40,7 @@ fn test_generate_password_minimum_words_4() {
     // Run multiple times to account for randomness
     // Use declarative template which guarantees article-first structure
     for _ in 0..10 {
-        let password = generate_password_with_template(Template::Declarative, 4, &default_config());
+        let password = generate_password_with_template(Template::Declarative, 4, &default_config()).unwrap().words;
         // Count content words (excluding articles, prepositions, connectors)
         let content_words: Vec<_> = password
             .iter()
@@ -42,13 +57,13 @@ fn test_generate_password_minimum_words_4() {
 #[test]
 fn test_generate_password_minimum_words_1() {
     for _ in 0..10 {
-        let password = generate_password(1, &default_config());
+        let password = generate_password(1, &default_config()).unwrap().words;
         let content_words: Vec<_> = password
             .iter()
             .filter(|w| !is_function_word(w))
             .collect();
         assert!(
-            content_words.len() >= 1,
+            !content_words.is_empty(),
             "Password with min_words=1 should have at least 1 content word, got: {:?}",
             password
         );
@@ -59,7 +74,7 @@ fn test_generate_password_minimum_words_1() {
 fn test_generate_password_minimum_words_0() {
     // min_words=0 should still generate something (starts with article)
     for _ in 0..10 {
-        let password = generate_password(0, &default_config());
+        let password = generate_password(0, &default_config()).unwrap().words;
         // Should at least have the starting article
         assert!(
             !password.is_empty(),
@@ -73,7 +88,7 @@ fn test_generate_password_large_minimum() {
     // Test with a larger minimum to ensure it extends properly
     // Use declarative template which supports proper extension
     for _ in 0..5 {
-        let password = generate_password_with_template(Template::Declarative, 10, &default_config());
+        let password = generate_password_with_template(Template::Declarative, 10, &default_config()).unwrap().words;
         let content_words: Vec<_> = password
             .iter()
             .filter(|w| !is_function_word(w))
@@ -91,7 +106,7 @@ fn test_generate_password_large_minimum() {
 fn test_generate_password_starts_with_article() {
     // Test specifically with declarative template which always starts with article
     for _ in 0..10 {
-        let password = generate_password_with_template(Template::Declarative, 4, &default_config());
+        let password = generate_password_with_template(Template::Declarative, 4, &default_config()).unwrap().words;
         let first_word = password.first().expect("Password should not be empty");
         // The first word should be "a", "an", or "the"
         // Note: "an" is transformed from "a" by fix_articles when followed by a vowel
@@ -107,7 +122,7 @@ fn test_generate_password_starts_with_article() {
 fn test_generate_password_produces_different_results() {
     // Generate multiple passwords and verify they're not all identical
     let config = default_config();
-    let passwords: Vec<_> = (0..10).map(|_| generate_password(4, &config)).collect();
+    let passwords: Vec<_> = (0..10).map(|_| generate_password(4, &config).unwrap().words).collect();
     let unique_count = passwords
         .iter()
         .map(|p| p.join(" "))
@@ -122,20 +137,51 @@ fn test_generate_password_produces_different_results() {
     );
 }
 
+#[test]
+fn test_seeded_rng_reproduces_exact_output() {
+    let config = default_config();
+    for seed in 0..20 {
+        let first = generate_password_with_rng(&mut StdRng::seed_from_u64(seed), 5, &config).unwrap();
+        let second = generate_password_with_rng(&mut StdRng::seed_from_u64(seed), 5, &config).unwrap();
+        assert_eq!(first.words, second.words);
+        assert_eq!(first.template, second.template);
+        assert_eq!(first.entropy(), second.entropy());
+    }
+}
+
+#[test]
+fn test_seeded_rng_reproduces_template_and_legacy_output() {
+    let config = default_config();
+    let a = generate_password_with_template_with_rng(&mut StdRng::seed_from_u64(42), Template::Since, 4, &config)
+        .unwrap();
+    let b = generate_password_with_template_with_rng(&mut StdRng::seed_from_u64(42), Template::Since, 4, &config)
+        .unwrap();
+    assert_eq!(a.words, b.words);
+
+    let a = generate_password_declarative_with_rng(&mut StdRng::seed_from_u64(42), 4);
+    let b = generate_password_declarative_with_rng(&mut StdRng::seed_from_u64(42), 4);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn test_os_rng_can_be_pinned() {
+    let password = generate_password_with_rng(&mut OsRng, 4, &default_config()).unwrap();
+    assert!(!password.words.is_empty());
+}
+
 // ============================================================================
-// ARTICLE FIXING TESTS ("a" -> "an" before vowels)
+// ARTICLE FIXING TESTS ("a" -> "an" before vowel sounds)
 // ============================================================================
 
 #[test]
 fn test_article_a_before_consonant_unchanged() {
     // Generate passwords and check that "a" before consonants stays "a"
     for _ in 0..50 {
-        let password = generate_password(4, &default_config());
+        let password = generate_password(4, &default_config()).unwrap().words;
         for (i, word) in password.iter().enumerate() {
             if word == "a" {
                 if let Some(next_word) = password.get(i + 1) {
-                    let first_char = next_word.chars().next().unwrap_or(' ');
-                    if !is_vowel(first_char) {
+                    if !starts_with_vowel_sound(next_word) {
                         // "a" before consonant is correct
                         assert_eq!(word, "a");
                     }
@@ -149,13 +195,12 @@ fn test_article_a_before_consonant_unchanged() {
 fn test_article_an_before_vowel() {
     // Generate passwords and check that "an" appears before vowel-starting words
     for _ in 0..50 {
-        let password = generate_password(4, &default_config());
+        let password = generate_password(4, &default_config()).unwrap().words;
         for (i, word) in password.iter().enumerate() {
             if word == "an" {
                 if let Some(next_word) = password.get(i + 1) {
-                    let first_char = next_word.chars().next().unwrap_or(' ');
                     assert!(
-                        is_vowel(first_char),
+                        starts_with_vowel_sound(next_word),
                         "\"an\" should only appear before vowel words, but found before: {}",
                         next_word
                     );
@@ -169,13 +214,12 @@ fn test_article_an_before_vowel() {
 fn test_no_incorrect_a_before_vowel() {
     // Ensure "a" never appears directly before a vowel-starting word
     for _ in 0..100 {
-        let password = generate_password(4, &default_config());
+        let password = generate_password(4, &default_config()).unwrap().words;
         for (i, word) in password.iter().enumerate() {
             if word == "a" {
                 if let Some(next_word) = password.get(i + 1) {
-                    let first_char = next_word.chars().next().unwrap_or(' ');
                     assert!(
-                        !is_vowel(first_char),
+                        !starts_with_vowel_sound(next_word),
                         "\"a\" should not appear before vowel word: {}, in password: {:?}",
                         next_word,
                         password
@@ -335,8 +379,8 @@ fn test_category_words_returns_correct_arrays() {
     assert_eq!(Category::Animal.words(), ANIMALS);
     assert_eq!(Category::Profession.words(), PROFESSIONS);
     assert_eq!(Category::Noun.words(), NOUNS);
-    assert_eq!(Category::Gerund.words(), GERUNDS);
-    assert_eq!(Category::PresentVerb.words(), PRESENT_VERBS);
+    assert_eq!(Category::Gerund.words(), GERUNDS.as_slice());
+    assert_eq!(Category::PresentVerb.words(), PRESENT_VERBS.as_slice());
     assert_eq!(Category::Object.words(), OBJECTS);
     assert_eq!(Category::Adverb.words(), ADVERBS);
     assert_eq!(Category::Article.words(), ARTICLES);
@@ -403,6 +447,13 @@ fn test_counts_toward_minimum_function_words() {
 // WORD LIST TESTS
 // ============================================================================
 
+#[test]
+fn test_word_lists_pass_audit() {
+    let report = audit();
+    let errors: Vec<String> = report.errors().map(|issue| issue.to_string()).collect();
+    assert!(errors.is_empty(), "word list audit failed:\n{}", errors.join("\n"));
+}
+
 #[test]
 fn test_word_lists_not_empty() {
     assert!(!ADJECTIVES.is_empty());
@@ -437,8 +488,8 @@ fn test_no_empty_strings_in_word_lists() {
         ("ANIMALS", ANIMALS),
         ("PROFESSIONS", PROFESSIONS),
         ("NOUNS", NOUNS),
-        ("GERUNDS", GERUNDS),
-        ("PRESENT_VERBS", PRESENT_VERBS),
+        ("GERUNDS", &GERUNDS),
+        ("PRESENT_VERBS", &PRESENT_VERBS),
         ("OBJECTS", OBJECTS),
         ("ADVERBS", ADVERBS),
         ("ARTICLES", ARTICLES),
@@ -459,7 +510,7 @@ fn test_no_empty_strings_in_word_lists() {
 
 #[test]
 fn test_gerunds_end_with_ing() {
-    for word in GERUNDS {
+    for word in GERUNDS.iter() {
         assert!(
             word.ends_with("ing"),
             "Gerund '{}' should end with 'ing'",
@@ -486,13 +537,13 @@ fn test_adverbs_end_with_ly() {
 #[test]
 fn test_entropy_is_positive_for_generated_passwords() {
     for _ in 0..10 {
-        let password = generate_password(4, &default_config());
-        let entropy = estimate_entropy_from_words(&password);
+        let password = generate_password(4, &default_config()).unwrap();
+        let entropy = password.entropy();
         assert!(
             entropy > 0.0,
             "Entropy should be positive, got: {} for {:?}",
             entropy,
-            password
+            password.words
         );
     }
 }
@@ -505,11 +556,11 @@ fn test_entropy_increases_with_more_words() {
     let config = default_config();
 
     for _ in 0..20 {
-        let short_password = generate_password(2, &config);
-        let long_password = generate_password(8, &config);
+        let short_password = generate_password(2, &config).unwrap();
+        let long_password = generate_password(8, &config).unwrap();
 
-        short_entropies.push(estimate_entropy_from_words(&short_password));
-        long_entropies.push(estimate_entropy_from_words(&long_password));
+        short_entropies.push(short_password.entropy());
+        long_entropies.push(long_password.entropy());
     }
 
     let avg_short: f64 = short_entropies.iter().sum::<f64>() / short_entropies.len() as f64;
@@ -523,6 +574,57 @@ fn test_entropy_increases_with_more_words() {
     );
 }
 
+#[test]
+fn test_generated_entropy_includes_template_choice() {
+    // A random template costs log2(10) bits on top of the template's own choices
+    let template_bits = (Template::ALL.len() as f64).log2();
+    for _ in 0..10 {
+        let password = generate_password(4, &default_config()).unwrap();
+        assert_eq!(password.decisions[0].label, "template");
+        assert!(
+            password.entropy() > template_bits,
+            "Entropy {} should exceed the template choice alone for {:?}",
+            password.entropy(),
+            password.words
+        );
+    }
+}
+
+#[test]
+fn test_forced_template_has_no_template_choice() {
+    let password = generate_password_with_template(Template::Warning, 4, &default_config()).unwrap();
+    assert_eq!(password.template, Template::Warning);
+    assert!(password.decisions.iter().all(|d| d.label != "template"));
+}
+
+#[test]
+fn test_declarative_extension_adds_exact_entropy() {
+    // Each forced prepositional phrase adds a preposition, article, adjective and subject
+    let phrase_bits = (PREPOSITIONS.len() as f64).log2()
+        + (ARTICLES.len() as f64).log2()
+        + (ADJECTIVES.len() as f64).log2();
+    for _ in 0..10 {
+        let password = generate_password_with_template(Template::Declarative, 12, &default_config()).unwrap();
+        let phrases = password.decisions.iter().filter(|d| d.label == "preposition").count();
+        assert!(phrases >= 3, "Expected extensions in {:?}", password.words);
+        assert!(
+            password.entropy() > phrases as f64 * phrase_bits,
+            "Entropy {} should cover {} prepositional phrases",
+            password.entropy(),
+            phrases
+        );
+    }
+}
+
+#[test]
+fn test_legacy_generator_entropy_is_positive() {
+    for _ in 0..10 {
+        let password = generate_password_declarative(4);
+        let entropy = estimate_entropy_from_words(&password);
+        assert!(entropy > 0.0, "Entropy should be positive for {:?}", password);
+    }
+}
+
 #[test]
 fn test_entropy_calculation_with_known_categories() {
     // Test with known categories to verify calculation
@@ -585,7 +687,7 @@ fn test_entropy_consistency() {
 fn test_generate_password_very_large_minimum() {
     // Test with a very large minimum (20 words)
     // Use declarative template which supports proper extension
-    let password = generate_password_with_template(Template::Declarative, 20, &default_config());
+    let password = generate_password_with_template(Template::Declarative, 20, &default_config()).unwrap().words;
     let content_words: Vec<_> = password
         .iter()
         .filter(|w| !is_function_word(w))
@@ -603,7 +705,7 @@ fn test_generate_password_very_large_minimum() {
 fn test_password_words_are_valid() {
     // All words in generated passwords should be from known word lists or template-specific words
     for _ in 0..20 {
-        let password = generate_password(4, &default_config());
+        let password = generate_password(4, &default_config()).unwrap().words;
         for word in &password {
             let word_str = word.as_str();
             let is_known = word_str == "an"
@@ -612,6 +714,8 @@ fn test_password_words_are_valid() {
                 || ANIMALS.contains(&word_str)
                 || PROFESSIONS.contains(&word_str)
                 || NOUNS.contains(&word_str)
+                // Counted subjects in fragments ("three wizards")
+                || [ANIMALS, PROFESSIONS, NOUNS].iter().any(|list| list.iter().any(|n| plural(n) == word_str))
                 || GERUNDS.contains(&word_str)
                 || PRESENT_VERBS.contains(&word_str)
                 || OBJECTS.contains(&word_str)
@@ -635,10 +739,13 @@ fn test_password_words_are_valid() {
                 || TIME_MARKERS.iter().any(|s| s.split_whitespace().any(|w| w == word_str))
                 || STATUS_WORDS.contains(&word_str)
                 || CONNECTORS_ACTIVE.contains(&word_str)
+                // Prepositions required by intransitive verbs ("stampedes through")
+                || INTRANSITIVE_VERB_PREPS.iter().any(|(_, prep)| *prep == word_str)
                 // Template-specific fixed words
                 || ["since", "somewhere", "should", "not", "requires", "more", "is"].contains(&word_str)
-                // Comma marker (internal token)
-                || word_str == "__COMMA__";
+                // Comma and haiku line markers (internal tokens)
+                || word_str == "__COMMA__"
+                || word_str == "__LINE__";
 
             assert!(
                 is_known,
@@ -716,6 +823,574 @@ fn is_function_word(word: &str) -> bool {
         || word == "an" // "an" is also an article (transformed from "a")
 }
 
-fn is_vowel(c: char) -> bool {
-    matches!(c.to_ascii_lowercase(), 'a' | 'e' | 'i' | 'o' | 'u')
+#[test]
+fn test_formatting_matches_cli_style_and_adds_entropy() {
+    let mut rng = StdRng::seed_from_u64(11);
+    let config = WordConfig::default();
+    let options = FormatOptions::default();
+    for _ in 0..50 {
+        let generated = generate_password_with_rng(&mut rng, 5, &config).unwrap();
+        let formatted = format_password_with_rng(generated.words, &options, &mut rng);
+        assert!(formatted.text.chars().next().unwrap().is_uppercase() || formatted.text.starts_with('"'));
+        assert!(formatted.text.ends_with(['.', '!', '?']), "{}", formatted.text);
+        assert!(!formatted.text.contains(" ,"), "{}", formatted.text);
+        // At minimum the opener coin is always flipped
+        assert!(formatted.entropy() > 0.0);
+    }
+}
+
+#[test]
+fn test_min_entropy_generation_meets_target() {
+    let mut rng = StdRng::seed_from_u64(21);
+    let config = default_config();
+    for target in [20.0, 50.0, 80.0, 128.0] {
+        for _ in 0..30 {
+            let password = generate_with_min_entropy_with_rng(&mut rng, target, &config).unwrap();
+            assert!(password.entropy() >= target, "{:?} has {} bits", password.words, password.entropy());
+            assert_eq!(password.decisions[0].label, "template");
+        }
+    }
+}
+
+#[test]
+fn test_min_entropy_generation_stops_when_extensions_add_nothing() {
+    use funny_password_generator::generator::GenerateError;
+    use funny_password_generator::rating::ContentRating;
+    use funny_password_generator::wordset::WordSet;
+
+    // Every haiku slot has exactly one entry that fits, so a stanza adds no bits
+    let mut words = WordSet::builtin(ContentRating::Workplace).clone();
+    words.articles = vec!["the".into()];
+    words.prepositions = vec!["with".into()];
+    words.adjectives = vec!["red".into()];
+    words.animals = vec!["kangaroo".into()];
+    words.professions = vec!["chef".into()];
+    words.nouns = vec!["tax".into()];
+    words.objects = vec!["harmonica".into()];
+    words.present_verbs = vec!["juggles".into()];
+    let config = WordConfig { words: Some(Arc::new(words)), ..WordConfig::default() };
+    let mut rng = StdRng::seed_from_u64(6);
+
+    let error = Template::Haiku.generate_with_min_entropy(&mut rng, 30.0, &config).unwrap_err();
+    assert!(matches!(error, GenerateError::Stalled { target: 30.0, .. }), "{:?}", error);
+    // Other templates still choose among subject kinds
+    assert!(Template::Declarative.generate_with_min_entropy(&mut rng, 30.0, &config).is_ok());
+
+    for target in [f64::NAN, f64::INFINITY] {
+        let error = generate_with_min_entropy_with_rng(&mut rng, target, &default_config()).unwrap_err();
+        assert!(matches!(error, GenerateError::InvalidTarget(_)), "{:?}", error);
+    }
+}
+
+#[test]
+fn test_unavailable_templates_are_errors_not_panics() {
+    use funny_password_generator::generator::{generate_password_of_length_with_rng, GenerateError, PasswordLength};
+    use funny_password_generator::rating::ContentRating;
+    use funny_password_generator::wordset::WordSet;
+
+    // No adjective is short enough to open a five-syllable line
+    let mut words = WordSet::builtin(ContentRating::Workplace).clone();
+    words.adjectives = vec!["incomprehensibly-overcaffeinated".into()];
+    let config = WordConfig { words: Some(Arc::new(words.clone())), ..WordConfig::default() };
+    let mut rng = StdRng::seed_from_u64(23);
+    assert!(!config.can_generate(&Template::Haiku));
+    let unavailable = GenerateError::Unavailable(Template::Haiku);
+    assert_eq!(Template::Haiku.generate(&mut rng, 5, &config).unwrap_err(), unavailable);
+    let generated = generate_password_with_template_with_rng(&mut rng, Template::Haiku, 5, &config);
+    assert_eq!(generated.unwrap_err(), unavailable);
+    let length = PasswordLength::MinWords(5);
+    let generated = generate_password_of_length_with_rng(&mut rng, Some(Template::Haiku), length, &config);
+    assert_eq!(generated.unwrap_err(), unavailable);
+    assert!(Template::Couplet.generate(&mut rng, 5, &config).is_ok());
+
+    // Every template extends with an article, so none is left without one
+    words.articles.clear();
+    let config = WordConfig { words: Some(Arc::new(words)), ..WordConfig::default() };
+    assert_eq!(generate_password_with_rng(&mut rng, 5, &config).unwrap_err(), GenerateError::NoTemplate);
+    assert_eq!(generate_with_min_entropy_with_rng(&mut rng, 60.0, &config).unwrap_err(), GenerateError::NoTemplate);
+    let pattern = Template::custom(Pattern::parse("{animal} {present_verb}").unwrap());
+    assert_eq!(pattern.generate(&mut rng, 5, &config).unwrap_err(), GenerateError::Unavailable(pattern.clone()));
+}
+
+#[test]
+fn test_custom_word_lists_drive_generation_and_entropy() {
+    use funny_password_generator::rating::ContentRating;
+    use funny_password_generator::wordset::WordSet;
+
+    let text = "animals = [\"office llama\", \"intern hamster\"]";
+    let base = WordSet::builtin(ContentRating::Workplace);
+    let words = WordSet::from_toml(text, base).unwrap();
+    let config = WordConfig { words: Some(Arc::new(words)), ..WordConfig::default() };
+    let mut rng = StdRng::seed_from_u64(11);
+
+    let mut animals = 0;
+    for _ in 0..200 {
+        let password = generate_password_with_template_with_rng(&mut rng, Template::Declarative, 4, &config).unwrap();
+        let text = password.words.join(" ");
+        for decision in password.decisions.iter().filter(|d| d.label == "animal") {
+            // Entropy comes from the loaded list's size, not the built-in one
+            assert_eq!(decision.probability, 0.5);
+            assert!(text.contains("office llama") || text.contains("intern hamster"), "{}", text);
+            animals += 1;
+        }
+    }
+    assert!(animals > 0);
+}
+
+#[test]
+fn test_themes_shrink_or_grow_the_entropy_of_their_lists() {
+    use funny_password_generator::themes::{Theme, ThemeMode};
+
+    let swapped = WordConfig { themes: vec![Theme::Pirates], ..WordConfig::default() };
+    let augmented = WordConfig { theme_mode: ThemeMode::Augment, ..swapped.clone() };
+    let animals = |config: &WordConfig| config.words().animals.len();
+    assert!(animals(&swapped) < ANIMALS.len());
+    assert!(animals(&augmented) > ANIMALS.len());
+
+    let mut rng = StdRng::seed_from_u64(12);
+    for _ in 0..100 {
+        let password = generate_password_with_template_with_rng(&mut rng, Template::Declarative, 4, &swapped).unwrap();
+        for decision in password.decisions.iter().filter(|d| d.label == "animal") {
+            assert_eq!(decision.probability, 1.0 / animals(&swapped) as f64);
+        }
+    }
+}
+
+#[test]
+fn test_word_filters_shorten_words_and_lower_entropy() {
+    use funny_password_generator::wordinfo::{WordFilter, WordInfo};
+
+    let filter = WordFilter { max_word_len: Some(7), max_syllables: Some(2), ..WordFilter::default() };
+    let config = WordConfig { filter, ..WordConfig::default() };
+    let words = config.words();
+    assert!(words.adjectives.len() < ADJECTIVES.len());
+    assert!(words.adjectives.iter().all(|w| filter.allows(w)));
+
+    let mut rng = StdRng::seed_from_u64(14);
+    for _ in 0..100 {
+        let password = generate_password_with_template_with_rng(&mut rng, Template::Declarative, 5, &config).unwrap();
+        for word in &password.words {
+            assert!(WordInfo::of(word).len <= 7, "{} in {:?}", word, password.words);
+        }
+        for decision in password.decisions.iter().filter(|d| d.label == "adjective") {
+            assert_eq!(decision.probability, 1.0 / words.adjectives.len() as f64);
+        }
+    }
+}
+
+#[test]
+fn test_denylist_removes_words_everywhere_and_drops_emptied_templates() {
+    use funny_password_generator::denylist::Denylist;
+    use funny_password_generator::generator::generate_password_declarative_with_config;
+
+    let mut terms = vec!["walrus", "penguin"];
+    terms.extend(CONFLICTS);
+    let denylist = Arc::new(Denylist::new(terms));
+    let config = WordConfig { denylist: Some(denylist.clone()), ..WordConfig::default() };
+    let templates = config.templates();
+    assert!(!templates.contains(&Template::Versus));
+    assert_eq!(templates.len(), Template::ALL.len() - 1);
+
+    let animals = config.words().animals.len();
+    assert_eq!(animals, ANIMALS.len() - 2);
+
+    let mut rng = StdRng::seed_from_u64(15);
+    for _ in 0..200 {
+        let password = generate_password_with_rng(&mut rng, 5, &config).unwrap();
+        assert!(!password.words.iter().any(|w| denylist.denies(w)), "{:?}", password.words);
+        assert_eq!(password.decisions[0].probability, 1.0 / templates.len() as f64);
+        // A haiku picks among the animals that fit its syllable count, a
+        // couplet among those that rhyme
+        for decision in password.decisions.iter().filter(|d| d.label == "animal") {
+            if matches!(password.template, Template::Haiku | Template::Couplet) {
+                assert!(decision.probability >= 1.0 / animals as f64);
+            } else {
+                assert_eq!(decision.probability, 1.0 / animals as f64);
+            }
+        }
+
+        let legacy = generate_password_declarative_with_config(&mut rng, 5, &config);
+        assert!(!legacy.iter().any(|w| denylist.denies(w)), "{:?}", legacy);
+    }
+}
+
+#[test]
+fn test_legacy_generator_stops_when_every_preposition_is_denied() {
+    use funny_password_generator::denylist::Denylist;
+    use funny_password_generator::generator::generate_password_declarative_with_config;
+    use funny_password_generator::words::PREPOSITIONS;
+
+    let config = WordConfig { denylist: Some(Arc::new(Denylist::new(PREPOSITIONS.to_vec()))), ..WordConfig::default() };
+    assert!(config.words().prepositions.is_empty());
+
+    let mut rng = StdRng::seed_from_u64(16);
+    for _ in 0..200 {
+        let legacy = generate_password_declarative_with_config(&mut rng, 8, &config);
+        assert!(!legacy.is_empty());
+        assert!(!legacy.iter().any(|w| PREPOSITIONS.contains(&w.as_str())), "{:?}", legacy);
+    }
+}
+
+#[test]
+fn test_content_ratings_exclude_stronger_words_and_lower_entropy() {
+    use funny_password_generator::rating::{rating, ContentRating};
+    use funny_password_generator::wordset::WordSet;
+
+    let sizes: Vec<usize> = ContentRating::ALL.iter().map(|&r| WordSet::builtin(r).adjectives.len()).collect();
+    assert!(sizes.windows(2).all(|pair| pair[0] < pair[1]), "{:?}", sizes);
+
+    let kids = WordSet::builtin(ContentRating::Kids);
+    assert!(!kids.adjectives.contains(&"gangrenous".into()));
+    assert!(!kids.adjectives.contains(&"traumatized".into()));
+    let cheeky = WordSet::builtin(ContentRating::Cheeky);
+    assert!(cheeky.nouns.contains(&"midlife-crisis".into()));
+    assert!(!cheeky.professions.contains(&"dominatrix".into()));
+
+    let config = WordConfig { rating: ContentRating::Kids, ..WordConfig::default() };
+    let mut rng = StdRng::seed_from_u64(16);
+    for _ in 0..200 {
+        let password = generate_password_with_rng(&mut rng, 5, &config).unwrap();
+        for word in &password.words {
+            assert!(rating(word).is_none_or(|r| r == ContentRating::Kids), "{} in {:?}", word, password.words);
+        }
+        for decision in password.decisions.iter().filter(|d| d.label == "adjective") {
+            if password.template == Template::Haiku {
+                assert!(decision.probability >= 1.0 / kids.adjectives.len() as f64);
+            } else {
+                assert_eq!(decision.probability, 1.0 / kids.adjectives.len() as f64);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_other_languages_use_their_own_templates_and_openers() {
+    use funny_password_generator::lang::Language;
+
+    let mut rng = StdRng::seed_from_u64(13);
+    for language in [Language::French, Language::German, Language::Spanish] {
+        let config = WordConfig { language, ..WordConfig::default() };
+        let options = FormatOptions { language, ..FormatOptions::default() };
+        for _ in 0..50 {
+            let password = generate_password_with_rng(&mut rng, 5, &config).unwrap();
+            assert!(language.supports(&password.template));
+            assert_eq!(password.decisions[0].probability, 1.0 / language.templates().len() as f64);
+            let formatted = format_password_with_rng(password.words, &options, &mut rng);
+            assert!(!OPENERS.iter().any(|o| formatted.text.starts_with(o)));
+        }
+    }
+}
+
+#[test]
+fn test_encode_decode_random_keys() {
+    use funny_password_generator::encoding::{decode, encode};
+    use rand::RngCore;
+    let mut rng = StdRng::seed_from_u64(99);
+    for len in [16, 16, 16, 20, 32] {
+        let mut key = vec![0u8; len];
+        rng.fill_bytes(&mut key);
+        let text = encode(&key).unwrap();
+        assert_eq!(decode(&text).unwrap(), key, "{}", text);
+    }
+}
+
+#[test]
+fn test_parse_recovers_generated_passwords() {
+    use funny_password_generator::parser::parse_password;
+    let mut rng = StdRng::seed_from_u64(13);
+    let config = default_config();
+    for _ in 0..5 {
+        let generated = generate_with_min_entropy_with_rng(&mut rng, 45.0, &config).unwrap();
+        let formatted = format_password_with_rng(generated.words.clone(), &FormatOptions::default(), &mut rng);
+        let readings = parse_password(&formatted.text, &config);
+        assert!(
+            readings.iter().any(|r| r.template == generated.template && (r.entropy() - generated.entropy()).abs() < 1e-9),
+            "{}",
+            formatted.text
+        );
+        // The weakest reading never claims more than the generator recorded
+        assert!(readings[0].entropy() <= generated.entropy() + 1e-9);
+    }
+}
+
+#[test]
+fn test_counted_fragments_agree_in_number() {
+    let subjects = || ANIMALS.iter().chain(PROFESSIONS).chain(NOUNS);
+    let mut rng = StdRng::seed_from_u64(19);
+    let (mut counted, mut amounts) = (0, 0);
+    for _ in 0..400 {
+        let words =
+            generate_password_with_template_with_rng(&mut rng, Template::Fragment, 5, &default_config()).unwrap().words;
+        let text = words.join(" ");
+        let Some(number) = NUMBERS.iter().filter(|n| text.starts_with(&format!("{} ", n))).max_by_key(|n| n.len())
+        else {
+            continue;
+        };
+        // The number, then the adjective, the subject and the verb
+        let rest = &words[number.split_whitespace().count() + 1..];
+        let (subject, verb) = (rest[0].as_str(), rest[1].as_str());
+        match count(number) {
+            Count::Many => {
+                counted += 1;
+                assert!(subjects().any(|s| plural(s) == subject), "{}", text);
+                assert!(PRESENT_VERBS.iter().any(|v| plural_verb(v) == verb), "{}", text);
+            }
+            // A mass noun stays singular after an amount, anything else is plural
+            Count::Mass if subjects().any(|s| *s == subject) => {
+                amounts += 1;
+                assert!(is_mass(subject) || amount_of(subject) == subject, "{}", text);
+                assert!(PRESENT_VERBS.contains(&verb), "{}", text);
+            }
+            Count::Mass => {
+                amounts += 1;
+                assert!(subjects().any(|s| amount_of(s) == subject && plural(s) == subject), "{}", text);
+                assert!(PRESENT_VERBS.iter().any(|v| plural_verb(v) == verb), "{}", text);
+            }
+            Count::One => {
+                assert!(subjects().any(|s| *s == subject), "{}", text);
+                assert!(PRESENT_VERBS.contains(&verb), "{}", text);
+            }
+        }
+    }
+    assert!(counted > 0 && amounts > 0, "{} {}", counted, amounts);
+}
+
+#[test]
+fn test_memorability_favours_common_words_and_counts_min_entropy() {
+    use funny_password_generator::memorability::{ListEntropy, COMMON};
+
+    let memorable = WordConfig { memorability: 1.0, ..WordConfig::default() };
+    let adjectives = &memorable.words().adjectives;
+    let min_bits = ListEntropy::of(adjectives, 1.0).min;
+    assert!(min_bits < (adjectives.len() as f64).log2());
+
+    let common_share = |config: &WordConfig| {
+        let mut rng = StdRng::seed_from_u64(20);
+        let mut common = 0;
+        for _ in 0..400 {
+            let password =
+                generate_password_with_template_with_rng(&mut rng, Template::Declarative, 5, config).unwrap();
+            common += password.words.iter().filter(|w| COMMON.contains(&w.as_str())).count();
+            for decision in password.decisions.iter().filter(|d| d.label == "adjective") {
+                let expected = if config.memorability > 0.0 { min_bits } else { (adjectives.len() as f64).log2() };
+                assert!((decision.bits() - expected).abs() < 1e-9);
+            }
+        }
+        common
+    };
+    assert!(common_share(&memorable) > common_share(&default_config()));
+}
+
+#[test]
+fn test_custom_patterns_fix_articles_add_prepositions_and_count_exactly() {
+    let config = default_config();
+    let words = config.words();
+    let pattern = Pattern::parse("{article} {adjective} {animal} {present_verb} {object}").unwrap();
+    let template = Template::custom(pattern);
+    let mut rng = StdRng::seed_from_u64(21);
+    for _ in 0..200 {
+        let password = generate_password_with_template_with_rng(&mut rng, template.clone(), 5, &config).unwrap();
+        let w = &password.words;
+        assert!(["a", "an", "the"].contains(&w[0].as_str()), "{:?}", w);
+        if w[0] != "the" {
+            assert_eq!(w[0] == "an", starts_with_vowel_sound(&w[1]), "{:?}", w);
+        }
+        // The verb's preposition, if it needs one, comes right after it
+        let rest = w[3..].join(" ");
+        let verb = words.present_verbs.iter().filter(|v| rest.starts_with(&***v)).max_by_key(|v| v.len()).unwrap();
+        let after = verb.split_whitespace().count() + 3;
+        if let Some(preposition) = get_verb_preposition(verb) {
+            assert_eq!(w[after], preposition, "{:?}", w);
+        }
+        // Five uniform picks make the sentence, whatever extends it comes after
+        let bits: f64 = password.decisions.iter().take(5).map(|d| d.bits()).sum();
+        let expected = [
+            words.articles.len(),
+            words.adjectives.len(),
+            words.animals.len(),
+            words.present_verbs.len(),
+            words.objects.len(),
+        ]
+        .iter()
+        .map(|&n| (n as f64).log2())
+        .sum::<f64>();
+        assert!((bits - expected).abs() < 1e-9);
+        assert_eq!(password.template, template);
+    }
+}
+
+#[test]
+fn test_template_mix_draws_by_weight_and_counts_the_choice() {
+    let mix: TemplateMix = "declarative:3,question:1".parse().unwrap();
+    let config = WordConfig { mix: Some(Arc::new(mix.clone())), ..WordConfig::default() };
+    assert_eq!(*config.templates(), [Template::Declarative, Template::Question]);
+
+    let mut rng = StdRng::seed_from_u64(22);
+    let mut declarative = 0;
+    for _ in 0..400 {
+        let password = generate_password_with_rng(&mut rng, 5, &config).unwrap();
+        assert!(matches!(password.template, Template::Declarative | Template::Question));
+        declarative += usize::from(password.template == Template::Declarative);
+        let choice = &password.decisions[0];
+        assert_eq!(choice.label, "template");
+        assert!((choice.bits() - (4.0_f64 / 3.0).log2()).abs() < 1e-9);
+    }
+    // Three to one, give or take
+    assert!((250..350).contains(&declarative), "{}", declarative);
+
+    // A bits target still counts the choice
+    let password = generate_with_min_entropy_with_rng(&mut rng, 60.0, &config).unwrap();
+    assert!(password.entropy() >= 60.0);
+}
+
+#[test]
+fn test_haiku_lines_scan_five_seven_five_and_count_the_constrained_picks() {
+    use funny_password_generator::parser::parse_password;
+    use funny_password_generator::templates::LINE_MARKER;
+    use funny_password_generator::wordinfo::phrase_syllables;
+
+    let config = WordConfig::default();
+    let mut rng = StdRng::seed_from_u64(23);
+    for _ in 0..50 {
+        let password = Template::Haiku.generate(&mut rng, 5, &config).unwrap();
+        let lines: Vec<usize> = password
+            .words
+            .split(|w| w == LINE_MARKER)
+            .map(|line| line.iter().map(|w| phrase_syllables(w)).sum())
+            .collect();
+        assert_eq!(lines.len() % 3, 0, "{:?}", password.words);
+        for stanza in lines.chunks(3) {
+            assert_eq!(stanza, [5, 7, 5], "{:?}", password.words);
+        }
+        // Each pick is counted among the entries that fit, never the whole list
+        let adjectives = config.words().adjectives.len() as f64;
+        assert!(password.decisions.iter().filter(|d| d.label == "adjective").any(|d| d.probability > 1.0 / adjectives));
+    }
+
+    // The parser replays the same constrained choices, so it recovers the same bits
+    let password = Template::Haiku.generate(&mut rng, 5, &config).unwrap();
+    let options = FormatOptions { openers: false, ..FormatOptions::default() };
+    let formatted = format_password_with_rng(password.words.clone(), &options, &mut rng);
+    assert_eq!(formatted.text.matches(" / ").count(), 2, "{}", formatted.text);
+    let reading = parse_password(&formatted.text, &config)
+        .into_iter()
+        .find(|r| r.template == Template::Haiku)
+        .expect("a haiku reading");
+    assert!((reading.entropy() - reading.decisions[0].bits() - password.entropy()).abs() < 1e-9);
+}
+
+#[test]
+fn test_couplet_lines_rhyme_and_count_the_constrained_endings() {
+    use funny_password_generator::parser::parse_password;
+    use funny_password_generator::rhyme::rhymes;
+    use funny_password_generator::templates::LINE_MARKER;
+
+    let config = WordConfig::default();
+    let mut rng = StdRng::seed_from_u64(24);
+    for _ in 0..50 {
+        let password = Template::Couplet.generate(&mut rng, 5, &config).unwrap();
+        let endings: Vec<&String> = password.words.split(|w| w == LINE_MARKER).map(|line| line.last().unwrap()).collect();
+        assert_eq!(endings.len() % 2, 0, "{:?}", password.words);
+        for pair in endings.chunks(2) {
+            assert!(rhymes(pair[0], pair[1]), "{:?}", password.words);
+        }
+        // The rhyming ending is counted among its partners, never the whole list
+        let last = password.decisions.last().unwrap();
+        assert!(last.probability > 1.0 / config.words().nouns.len() as f64, "{:?}", last);
+    }
+
+    // The parser replays the same constrained choices, so it recovers the same bits
+    let password = Template::Couplet.generate(&mut rng, 5, &config).unwrap();
+    let options = FormatOptions { openers: false, ..FormatOptions::default() };
+    let formatted = format_password_with_rng(password.words.clone(), &options, &mut rng);
+    assert_eq!(formatted.text.matches(" / ").count(), 1, "{}", formatted.text);
+    let reading = parse_password(&formatted.text, &config)
+        .into_iter()
+        .find(|r| r.template == Template::Couplet)
+        .expect("a couplet reading");
+    assert!((reading.entropy() - reading.decisions[0].bits() - password.entropy()).abs() < 1e-9);
+}
+
+#[test]
+fn test_compound_sentences_reach_vault_strength_and_read_as_sentences() {
+    use funny_password_generator::compound::generate_compound_with_rng;
+    use funny_password_generator::formatting::format_compound_with_rng;
+    use funny_password_generator::generator::PasswordLength;
+
+    let config = WordConfig::default();
+    let options = FormatOptions { openers: false, ..FormatOptions::default() };
+    let mut rng = StdRng::seed_from_u64(25);
+    for _ in 0..50 {
+        let compound = generate_compound_with_rng(&mut rng, 2, None, PasswordLength::MinEntropy(100.0), &config).unwrap();
+        assert!(compound.entropy() >= 100.0, "{}", compound.entropy());
+
+        let formatted = format_compound_with_rng(&compound, &options, &mut rng);
+        let text = &formatted.text;
+        assert!(!text.contains("__"), "{}", text);
+        assert!(text.starts_with(|c: char| c.is_uppercase()), "{}", text);
+        assert!(text.ends_with(['.', '!', '?']), "{}", text);
+        let joined = [", and then ", " because ", " Meanwhile, "];
+        assert!(joined.iter().filter(|c| text.contains(*c)).count() >= 1, "{}", text);
+        // A sentence carried on after "and then" or "because" starts in lowercase
+        for connective in &joined[..2] {
+            if let Some((_, rest)) = text.split_once(connective) {
+                assert!(!rest.starts_with(|c: char| c.is_uppercase()), "{}", text);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_mkpass_encode_rejects_bad_hex() {
+    let mkpass = |hex: &str| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_mkpass")).args(["encode", hex]).output().unwrap()
+    };
+    for hex in ["aéb", "0x0xdead", "abc"] {
+        let output = mkpass(hex);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        assert_eq!(output.status.code(), Some(1), "{}: {}", hex, stderr);
+        assert!(stderr.starts_with("Error:"), "{}: {}", hex, stderr);
+    }
+    assert!(mkpass("0xdead beef").status.success());
+}
+
+#[test]
+fn test_mkpass_rejects_unreachable_bits() {
+    let mkpass = |bits: &str| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_mkpass")).arg(format!("--bits={}", bits)).output().unwrap()
+    };
+    for bits in ["inf", "NaN", "0", "-5", "100000"] {
+        let output = mkpass(bits);
+        assert!(!output.status.success(), "{} was accepted", bits);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Bits must be"), "{}", bits);
+    }
+    assert!(mkpass("60").status.success());
+}
+
+#[test]
+fn test_denylist_covers_counted_fragments() {
+    use funny_password_generator::denylist::Denylist;
+
+    let denylist = Arc::new(Denylist::new(["wizards", "juggle"]));
+    let config = WordConfig { denylist: Some(denylist.clone()), ..WordConfig::default() };
+    let mut rng = StdRng::seed_from_u64(19);
+    for _ in 0..2000 {
+        let password = generate_password_with_template_with_rng(&mut rng, Template::Fragment, 0, &config).unwrap();
+        assert!(!password.words.iter().any(|w| denylist.denies(w)), "{:?}", password.words);
+    }
+}
+
+#[test]
+fn test_mkpass_analyze_reads_pattern_passwords() {
+    let pattern = "{adjective} {animal} versus the {noun}";
+    let mkpass = |args: &[&str]| {
+        let output = std::process::Command::new(env!("CARGO_BIN_EXE_mkpass")).args(args).output().unwrap();
+        (output.status.success(), String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or("").to_string())
+    };
+    let (generated, password) = mkpass(&["--pattern", pattern, "--sfw"]);
+    assert!(generated, "{}", password);
+    // The first line of the report names the template
+    let (recognized, template) = mkpass(&["analyze", "--sfw", "--pattern", pattern, &password]);
+    assert!(recognized, "{}", password);
+    assert_eq!(template, "Template: custom", "{}", password);
 }